@@ -0,0 +1,224 @@
+use num_bigint::{BigInt, Sign};
+
+use crate::{
+    errors::EvaluatorError,
+    types::{bytevector::Bytevector, Cons, ConsValue},
+};
+
+use super::{arity, index_arg, int_arg, integer_arg, invalid_argument, string_arg};
+
+fn bytevector(bytes: Vec<u8>) -> Cons {
+    Cons::Value(ConsValue::Bytevector(Bytevector::new(bytes)))
+}
+
+fn bytevector_arg<'a>(name: &str, arg: &'a Cons) -> Result<&'a Bytevector, EvaluatorError> {
+    match arg {
+        Cons::Value(ConsValue::Bytevector(b)) => Ok(b),
+        _ => Err(invalid_argument(name)),
+    }
+}
+
+fn byte_arg(name: &str, arg: &Cons) -> Result<u8, EvaluatorError> {
+    u8::try_from(int_arg(name, arg)?)
+        .map_err(|_| EvaluatorError::InvalidArgument(format!("{} is not a byte for {}", arg, name)))
+}
+
+fn out_of_range(name: &str, index: usize, size: usize, len: usize) -> EvaluatorError {
+    EvaluatorError::InvalidArgument(format!(
+        "{} bytes at index {} out of range for {} on a bytevector of length {}",
+        size, index, name, len
+    ))
+}
+
+/// Checks that `size` bytes from `index` lie inside a bytevector of length
+/// `len`, before anything of that size is allocated.
+fn check_range(name: &str, index: usize, size: usize, len: usize) -> Result<(), EvaluatorError> {
+    match index.checked_add(size) {
+        Some(end) if end <= len => Ok(()),
+        _ => Err(out_of_range(name, index, size, len)),
+    }
+}
+
+/// Reads the optional `start` and `end` arguments from `args[from]` on,
+/// defaulting to the whole of `len`.
+fn range_args(
+    name: &str,
+    args: &[Cons],
+    from: usize,
+    len: usize,
+) -> Result<(usize, usize), EvaluatorError> {
+    let start = match args.get(from) {
+        Some(start) => index_arg(name, start)?,
+        None => 0,
+    };
+    let end = match args.get(from + 1) {
+        Some(end) => index_arg(name, end)?,
+        None => len,
+    };
+    if start > end || end > len {
+        return Err(EvaluatorError::InvalidArgument(format!(
+            "Range {}..{} out of bounds for bytevector of length {}",
+            start, end, len
+        )));
+    }
+    Ok((start, end))
+}
+
+/// `(make-bytevector n [fill])`: bytes default to 0.
+pub fn make(args: &[Cons]) -> Result<Cons, EvaluatorError> {
+    arity("make-bytevector", args, 1, 2)?;
+    let len = index_arg("make-bytevector", &args[0])?;
+    let fill = match args.get(1) {
+        Some(fill) => byte_arg("make-bytevector", fill)?,
+        None => 0,
+    };
+    Ok(bytevector(vec![fill; len]))
+}
+
+pub fn from_args(args: &[Cons]) -> Result<Cons, EvaluatorError> {
+    Ok(bytevector(
+        args.iter()
+            .map(|arg| byte_arg("bytevector", arg))
+            .collect::<Result<Vec<u8>, EvaluatorError>>()?,
+    ))
+}
+
+pub fn length(args: &[Cons]) -> Result<Cons, EvaluatorError> {
+    arity("bytevector-length", args, 1, 1)?;
+    let len = bytevector_arg("bytevector-length", &args[0])?.len();
+    Ok(Cons::Value(ConsValue::Int(len as i64)))
+}
+
+pub fn get(args: &[Cons]) -> Result<Cons, EvaluatorError> {
+    arity("bytevector-u8-ref", args, 2, 2)?;
+    let b = bytevector_arg("bytevector-u8-ref", &args[0])?;
+    let index = index_arg("bytevector-u8-ref", &args[1])?;
+    b.get(index)
+        .map(|byte| Cons::Value(ConsValue::Int(byte.into())))
+        .ok_or_else(|| out_of_range("bytevector-u8-ref", index, 1, b.len()))
+}
+
+pub fn set(args: &[Cons]) -> Result<Cons, EvaluatorError> {
+    arity("bytevector-u8-set!", args, 3, 3)?;
+    let b = bytevector_arg("bytevector-u8-set!", &args[0])?;
+    let index = index_arg("bytevector-u8-set!", &args[1])?;
+    let byte = byte_arg("bytevector-u8-set!", &args[2])?;
+    if !b.write(index, &[byte]) {
+        return Err(out_of_range("bytevector-u8-set!", index, 1, b.len()));
+    }
+    Ok(Cons::Value(ConsValue::NIL))
+}
+
+/// `(bytevector-copy bv [start [end]])`: a new bytevector with the bytes from
+/// `start` up to `end`.
+pub fn copy(args: &[Cons]) -> Result<Cons, EvaluatorError> {
+    arity("bytevector-copy", args, 1, 3)?;
+    let bytes = bytevector_arg("bytevector-copy", &args[0])?.to_vec();
+    let (start, end) = range_args("bytevector-copy", args, 1, bytes.len())?;
+    Ok(bytevector(bytes[start..end].to_vec()))
+}
+
+pub fn append(args: &[Cons]) -> Result<Cons, EvaluatorError> {
+    let mut result = Vec::new();
+    for arg in args {
+        result.extend(bytevector_arg("bytevector-append", arg)?.to_vec());
+    }
+    Ok(bytevector(result))
+}
+
+/// `(utf8->string bv [start [end]])`: invalid UTF-8 is an error.
+pub fn to_string(args: &[Cons]) -> Result<Cons, EvaluatorError> {
+    arity("utf8->string", args, 1, 3)?;
+    let bytes = bytevector_arg("utf8->string", &args[0])?.to_vec();
+    let (start, end) = range_args("utf8->string", args, 1, bytes.len())?;
+    String::from_utf8(bytes[start..end].to_vec())
+        .map(|s| Cons::Value(ConsValue::String(s)))
+        .map_err(|e| {
+            EvaluatorError::InvalidArgument(format!("Invalid UTF-8 for utf8->string: {}", e))
+        })
+}
+
+pub fn from_string(args: &[Cons]) -> Result<Cons, EvaluatorError> {
+    arity("string->utf8", args, 1, 1)?;
+    Ok(bytevector(
+        string_arg("string->utf8", &args[0])?.as_bytes().to_vec(),
+    ))
+}
+
+#[derive(Clone, Copy)]
+enum Endianness {
+    Big,
+    Little,
+}
+
+fn endianness_arg(name: &str, arg: &Cons) -> Result<Endianness, EvaluatorError> {
+    match arg {
+        Cons::Value(ConsValue::Symbol(s)) if s.as_str() == "big" => Ok(Endianness::Big),
+        Cons::Value(ConsValue::Symbol(s)) if s.as_str() == "little" => Ok(Endianness::Little),
+        _ => Err(EvaluatorError::InvalidArgument(format!(
+            "Endianness for {} must be 'big or 'little, got {}",
+            name, arg
+        ))),
+    }
+}
+
+/// `(bytevector-uint-ref bv k endianness size)` and the signed `sint`
+/// variant: the integer stored in `size` bytes from index `k`.
+pub fn int_ref(name: &str, args: &[Cons], signed: bool) -> Result<Cons, EvaluatorError> {
+    arity(name, args, 4, 4)?;
+    let b = bytevector_arg(name, &args[0])?;
+    let index = index_arg(name, &args[1])?;
+    let endianness = endianness_arg(name, &args[2])?;
+    let size = index_arg(name, &args[3])?;
+    check_range(name, index, size, b.len())?;
+    let bytes = b.to_vec();
+    let bytes = &bytes[index..index + size];
+    let n = match (endianness, signed) {
+        (Endianness::Big, false) => BigInt::from_bytes_be(Sign::Plus, bytes),
+        (Endianness::Little, false) => BigInt::from_bytes_le(Sign::Plus, bytes),
+        (Endianness::Big, true) => BigInt::from_signed_bytes_be(bytes),
+        (Endianness::Little, true) => BigInt::from_signed_bytes_le(bytes),
+    };
+    Ok(Cons::Value(ConsValue::integer(n)))
+}
+
+/// `(bytevector-uint-set! bv k n endianness size)` and the signed `sint`
+/// variant: stores `n` in `size` bytes from index `k`. `n` must fit.
+pub fn int_set(name: &str, args: &[Cons], signed: bool) -> Result<Cons, EvaluatorError> {
+    arity(name, args, 5, 5)?;
+    let b = bytevector_arg(name, &args[0])?;
+    let index = index_arg(name, &args[1])?;
+    let n = integer_arg(name, &args[2])?;
+    let endianness = endianness_arg(name, &args[3])?;
+    let size = index_arg(name, &args[4])?;
+    check_range(name, index, size, b.len())?;
+    let does_not_fit = || {
+        EvaluatorError::InvalidArgument(format!(
+            "{} does not fit in {} bytes for {}",
+            n, size, name
+        ))
+    };
+    // Little-endian two's complement, padded with the sign to `size` bytes.
+    let mut bytes = if signed {
+        n.to_signed_bytes_le()
+    } else if n.sign() == Sign::Minus {
+        return Err(does_not_fit());
+    } else {
+        n.to_bytes_le().1
+    };
+    if n.sign() == Sign::NoSign {
+        bytes.clear();
+    }
+    if bytes.len() > size {
+        return Err(does_not_fit());
+    }
+    let pad = if n.sign() == Sign::Minus { 0xff } else { 0 };
+    bytes.resize(size, pad);
+    if let Endianness::Big = endianness {
+        bytes.reverse();
+    }
+    if !b.write(index, &bytes) {
+        return Err(out_of_range(name, index, size, b.len()));
+    }
+    Ok(Cons::Value(ConsValue::NIL))
+}
@@ -0,0 +1,94 @@
+use std::cmp::Ordering;
+
+use crate::{
+    errors::EvaluatorError,
+    types::{Cons, ConsValue},
+};
+
+use super::{arity, int_arg, invalid_argument, order};
+
+fn character(c: char) -> Cons {
+    Cons::Value(ConsValue::Char(c))
+}
+
+fn char_arg(name: &str, arg: &Cons) -> Result<char, EvaluatorError> {
+    match arg {
+        Cons::Value(ConsValue::Char(c)) => Ok(*c),
+        _ => Err(invalid_argument(name)),
+    }
+}
+
+pub fn to_integer(args: &[Cons]) -> Result<Cons, EvaluatorError> {
+    arity("char->integer", args, 1, 1)?;
+    Ok(Cons::Value(ConsValue::Int(
+        char_arg("char->integer", &args[0])? as i64,
+    )))
+}
+
+/// Fails for surrogates and values beyond the Unicode range.
+pub fn from_integer(args: &[Cons]) -> Result<Cons, EvaluatorError> {
+    arity("integer->char", args, 1, 1)?;
+    let code = int_arg("integer->char", &args[0])?;
+    u32::try_from(code)
+        .ok()
+        .and_then(char::from_u32)
+        .map(character)
+        .ok_or_else(|| {
+            EvaluatorError::InvalidArgument(format!("No character with code point {}", code))
+        })
+}
+
+/// Case conversions that would produce several characters, like that of
+/// `ß`, leave the character unchanged.
+fn convert<I: Iterator<Item = char>>(c: char, converted: impl Fn(char) -> I) -> char {
+    let mut chars = converted(c);
+    match (chars.next(), chars.next()) {
+        (Some(single), None) => single,
+        _ => c,
+    }
+}
+
+pub fn upcase(args: &[Cons]) -> Result<Cons, EvaluatorError> {
+    arity("char-upcase", args, 1, 1)?;
+    Ok(character(convert(
+        char_arg("char-upcase", &args[0])?,
+        char::to_uppercase,
+    )))
+}
+
+pub fn downcase(args: &[Cons]) -> Result<Cons, EvaluatorError> {
+    arity("char-downcase", args, 1, 1)?;
+    Ok(character(convert(
+        char_arg("char-downcase", &args[0])?,
+        char::to_lowercase,
+    )))
+}
+
+pub fn test(name: &str, args: &[Cons], property: fn(char) -> bool) -> Result<Cons, EvaluatorError> {
+    arity(name, args, 1, 1)?;
+    Ok(Cons::Value(ConsValue::Boolean(property(char_arg(
+        name, &args[0],
+    )?))))
+}
+
+/// Compares characters by code point.
+pub fn compare(name: &str, args: &[Cons], accepted: &[Ordering]) -> Result<Cons, EvaluatorError> {
+    let chars = args
+        .iter()
+        .map(|arg| char_arg(name, arg))
+        .collect::<Result<Vec<char>, EvaluatorError>>()?;
+    Ok(order::chain(&chars, accepted))
+}
+
+/// Like `compare`, ignoring case.
+pub fn compare_ci(
+    name: &str,
+    args: &[Cons],
+    accepted: &[Ordering],
+) -> Result<Cons, EvaluatorError> {
+    let chars = args
+        .iter()
+        .map(|arg| Ok(convert(char_arg(name, arg)?, char::to_lowercase)))
+        .collect::<Result<Vec<char>, EvaluatorError>>()?;
+    Ok(order::chain(&chars, accepted))
+}
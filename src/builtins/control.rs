@@ -0,0 +1,74 @@
+//! Builtins that call the procedures they are given.
+
+use crate::{
+    errors::EvaluatorError,
+    types::{continuation::Continuation, scope::LexicalVarStorage, Cons, ConsValue},
+};
+
+use super::{arity, call, procedure_arg};
+
+/// Returns the elements of a list that ends in the empty list.
+fn proper_list(name: &str, list: &Cons) -> Result<Vec<Cons>, EvaluatorError> {
+    let mut elems = Vec::new();
+    let mut rest = list.clone();
+    loop {
+        match rest {
+            Cons::Value(ConsValue::NIL) => return Ok(elems),
+            Cons::Cell(car, cdr) => {
+                elems.push(Cons::clone(&car));
+                rest = Cons::clone(&cdr);
+            }
+            _ => {
+                return Err(EvaluatorError::InvalidArgument(format!(
+                    "The last argument of {} must be a proper list, got {}",
+                    name, list
+                )));
+            }
+        }
+    }
+}
+
+/// `(apply proc arg ... list)`: calls proc with the args followed by the
+/// elements of list.
+pub fn apply(args: &[Cons], stg: &mut LexicalVarStorage) -> Result<Cons, EvaluatorError> {
+    let [proc, rest @ .., list] = args else {
+        return Err(EvaluatorError::InvalidArgument(
+            "apply needs a procedure and a list".to_string(),
+        ));
+    };
+    let proc = procedure_arg("apply", proc)?;
+    let mut call_args = rest.to_vec();
+    call_args.extend(proper_list("apply", list)?);
+    call(&proc, call_args, stg)
+}
+
+/// `(call/cc proc)`: calls proc with an escape continuation. Calling the
+/// continuation returns its argument from here.
+pub fn call_cc(args: &[Cons], stg: &mut LexicalVarStorage) -> Result<Cons, EvaluatorError> {
+    arity("call/cc", args, 1, 1)?;
+    let proc = procedure_arg("call/cc", &args[0])?;
+    let k = Continuation::new();
+    let result = call(
+        &proc,
+        vec![Cons::Value(ConsValue::Continuation(k.clone()))],
+        stg,
+    );
+    k.expire();
+    match result {
+        Err(EvaluatorError::Escape(target, value)) if target == k => Ok(value),
+        result => result,
+    }
+}
+
+/// `(dynamic-wind before thunk after)`: after runs however thunk is left,
+/// whether it returns, escapes through a continuation or fails.
+pub fn dynamic_wind(args: &[Cons], stg: &mut LexicalVarStorage) -> Result<Cons, EvaluatorError> {
+    arity("dynamic-wind", args, 3, 3)?;
+    let before = procedure_arg("dynamic-wind", &args[0])?;
+    let thunk = procedure_arg("dynamic-wind", &args[1])?;
+    let after = procedure_arg("dynamic-wind", &args[2])?;
+    call(&before, Vec::new(), stg)?;
+    let result = call(&thunk, Vec::new(), stg);
+    call(&after, Vec::new(), stg)?;
+    result
+}
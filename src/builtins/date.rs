@@ -0,0 +1,264 @@
+use std::fmt::Write;
+
+use chrono::{
+    DateTime, Datelike, FixedOffset, NaiveDate, NaiveDateTime, TimeDelta, TimeZone, Timelike, Utc,
+};
+
+use crate::{
+    errors::EvaluatorError,
+    types::{scope::LexicalVarStorage, Cons, ConsValue},
+};
+
+use super::{arity, index_arg, int_arg, invalid_argument, string_arg};
+
+type Date = DateTime<FixedOffset>;
+
+fn date(d: Date) -> Cons {
+    Cons::Value(ConsValue::Date(d))
+}
+
+fn duration(d: TimeDelta) -> Cons {
+    Cons::Value(ConsValue::Duration(d))
+}
+
+fn date_arg(name: &str, arg: &Cons) -> Result<Date, EvaluatorError> {
+    match arg {
+        Cons::Value(ConsValue::Date(d)) => Ok(*d),
+        _ => Err(invalid_argument(name)),
+    }
+}
+
+fn duration_arg(name: &str, arg: &Cons) -> Result<TimeDelta, EvaluatorError> {
+    match arg {
+        Cons::Value(ConsValue::Duration(d)) => Ok(*d),
+        _ => Err(invalid_argument(name)),
+    }
+}
+
+fn out_of_range(name: &str) -> EvaluatorError {
+    EvaluatorError::InvalidArgument(format!("Date out of range for {}", name))
+}
+
+/// `(current-time)`: milliseconds since the Unix epoch.
+pub fn current_time(args: &[Cons], stg: &LexicalVarStorage) -> Result<Cons, EvaluatorError> {
+    arity("current-time", args, 0, 0)?;
+    Ok(Cons::Value(ConsValue::Int(
+        stg.clock().now().timestamp_millis(),
+    )))
+}
+
+/// `(current-date)`: the current date in the local time zone.
+pub fn current_date(args: &[Cons], stg: &LexicalVarStorage) -> Result<Cons, EvaluatorError> {
+    arity("current-date", args, 0, 0)?;
+    Ok(date(stg.clock().local_now()))
+}
+
+/// `(make-date year month day [hour minute second [offset]])`, where offset is
+/// in seconds east of UTC and defaults to 0.
+pub fn make(args: &[Cons]) -> Result<Cons, EvaluatorError> {
+    if !matches!(args.len(), 3 | 6 | 7) {
+        return Err(EvaluatorError::InvalidArgument(format!(
+            "Wrong number of arguments for make-date: {}",
+            args.len()
+        )));
+    }
+    let int = |i: usize| args.get(i).map_or(Ok(0), |arg| int_arg("make-date", arg));
+    let invalid = || EvaluatorError::InvalidArgument("Invalid date for make-date".to_string());
+    let year = i32::try_from(int(0)?).map_err(|_| invalid())?;
+    let mut fields = [0u32; 5];
+    for (i, field) in fields.iter_mut().enumerate() {
+        *field = u32::try_from(int(i + 1)?).map_err(|_| invalid())?;
+    }
+    let [month, day, hour, minute, second] = fields;
+    let offset = i32::try_from(int(6)?)
+        .ok()
+        .and_then(FixedOffset::east_opt)
+        .ok_or_else(invalid)?;
+    offset
+        .with_ymd_and_hms(year, month, day, hour, minute, second)
+        .single()
+        .map(date)
+        .ok_or_else(invalid)
+}
+
+/// The date accessors, like `(date-year date)`.
+pub fn field(name: &str, args: &[Cons], get: fn(&Date) -> i64) -> Result<Cons, EvaluatorError> {
+    arity(name, args, 1, 1)?;
+    Ok(Cons::Value(ConsValue::Int(get(&date_arg(name, &args[0])?))))
+}
+
+pub fn year(d: &Date) -> i64 {
+    d.year() as i64
+}
+pub fn month(d: &Date) -> i64 {
+    d.month() as i64
+}
+pub fn day(d: &Date) -> i64 {
+    d.day() as i64
+}
+pub fn hour(d: &Date) -> i64 {
+    d.hour() as i64
+}
+pub fn minute(d: &Date) -> i64 {
+    d.minute() as i64
+}
+pub fn second(d: &Date) -> i64 {
+    d.second() as i64
+}
+pub fn millisecond(d: &Date) -> i64 {
+    d.timestamp_subsec_millis() as i64
+}
+/// 0 for Sunday up to 6 for Saturday.
+pub fn week_day(d: &Date) -> i64 {
+    d.weekday().num_days_from_sunday() as i64
+}
+/// 1 for the first of January.
+pub fn year_day(d: &Date) -> i64 {
+    d.ordinal() as i64
+}
+/// Seconds east of UTC.
+pub fn zone_offset(d: &Date) -> i64 {
+    d.offset().local_minus_utc() as i64
+}
+
+/// `(date->string date [format])`: RFC 3339 by default, otherwise a strftime
+/// format like "%Y-%m-%d".
+pub fn to_string(args: &[Cons]) -> Result<Cons, EvaluatorError> {
+    arity("date->string", args, 1, 2)?;
+    let d = date_arg("date->string", &args[0])?;
+    let s = match args.get(1) {
+        None => d.to_rfc3339(),
+        Some(format) => {
+            let format = string_arg("date->string", format)?;
+            let mut s = String::new();
+            // Writing fails rather than panicking on an invalid format.
+            write!(s, "{}", d.format(format)).map_err(|_| {
+                EvaluatorError::InvalidArgument(format!("Invalid date format {:?}", format))
+            })?;
+            s
+        }
+    };
+    Ok(Cons::Value(ConsValue::String(s)))
+}
+
+/// `(string->date str [format])`: parses RFC 3339 by default, otherwise a
+/// strftime format. Formats without an offset are read as UTC, and formats
+/// without a time as midnight.
+pub fn from_string(args: &[Cons]) -> Result<Cons, EvaluatorError> {
+    arity("string->date", args, 1, 2)?;
+    let s = string_arg("string->date", &args[0])?;
+    let parsed = match args.get(1) {
+        None => DateTime::parse_from_rfc3339(s),
+        Some(format) => {
+            let format = string_arg("string->date", format)?;
+            DateTime::parse_from_str(s, format)
+                .or_else(|e| {
+                    NaiveDateTime::parse_from_str(s, format)
+                        .map(|d| d.and_utc().fixed_offset())
+                        .map_err(|_| e)
+                })
+                .or_else(|e| {
+                    NaiveDate::parse_from_str(s, format)
+                        .map(|d| d.and_time(Default::default()).and_utc().fixed_offset())
+                        .map_err(|_| e)
+                })
+        }
+    };
+    parsed
+        .map(date)
+        .map_err(|e| EvaluatorError::InvalidArgument(format!("Cannot parse date {:?}: {}", s, e)))
+}
+
+/// `(make-duration days [hours minutes seconds [milliseconds]])`. Any of them
+/// may be negative.
+pub fn make_duration(args: &[Cons]) -> Result<Cons, EvaluatorError> {
+    if !matches!(args.len(), 1 | 4 | 5) {
+        return Err(EvaluatorError::InvalidArgument(format!(
+            "Wrong number of arguments for make-duration: {}",
+            args.len()
+        )));
+    }
+    let mut millis: i64 = 0;
+    for (i, factor) in [24, 60, 60, 1000, 1].into_iter().enumerate() {
+        let part = match args.get(i) {
+            Some(arg) => int_arg("make-duration", arg)?,
+            None => 0,
+        };
+        millis = millis
+            .checked_add(part)
+            .and_then(|m| m.checked_mul(factor))
+            .ok_or_else(|| {
+                EvaluatorError::InvalidArgument("Duration too long for make-duration".to_string())
+            })?;
+    }
+    TimeDelta::try_milliseconds(millis)
+        .map(duration)
+        .ok_or_else(|| {
+            EvaluatorError::InvalidArgument("Duration too long for make-duration".to_string())
+        })
+}
+
+pub fn duration_to_millis(args: &[Cons]) -> Result<Cons, EvaluatorError> {
+    arity("duration->milliseconds", args, 1, 1)?;
+    let d = duration_arg("duration->milliseconds", &args[0])?;
+    Ok(Cons::Value(ConsValue::Int(d.num_milliseconds())))
+}
+
+/// `(date-add date duration)`: the date `duration` later.
+pub fn add(args: &[Cons]) -> Result<Cons, EvaluatorError> {
+    arity("date-add", args, 2, 2)?;
+    let d = date_arg("date-add", &args[0])?;
+    let delta = duration_arg("date-add", &args[1])?;
+    d.checked_add_signed(delta)
+        .map(date)
+        .ok_or_else(|| out_of_range("date-add"))
+}
+
+/// `(date-difference a b)`: the duration from `b` to `a`.
+pub fn difference(args: &[Cons]) -> Result<Cons, EvaluatorError> {
+    arity("date-difference", args, 2, 2)?;
+    let a = date_arg("date-difference", &args[0])?;
+    let b = date_arg("date-difference", &args[1])?;
+    Ok(duration(a.signed_duration_since(b)))
+}
+
+/// `(date->utc date)`: the same moment in UTC.
+pub fn to_utc(args: &[Cons]) -> Result<Cons, EvaluatorError> {
+    arity("date->utc", args, 1, 1)?;
+    Ok(date(
+        date_arg("date->utc", &args[0])?.to_utc().fixed_offset(),
+    ))
+}
+
+/// `(date->local date)`: the same moment in the local time zone.
+pub fn to_local(args: &[Cons], stg: &LexicalVarStorage) -> Result<Cons, EvaluatorError> {
+    arity("date->local", args, 1, 1)?;
+    let d = date_arg("date->local", &args[0])?;
+    Ok(date(d.with_timezone(&stg.clock().local_offset(d.to_utc()))))
+}
+
+/// `(date->time date)`: milliseconds since the Unix epoch, like
+/// `current-time`.
+pub fn to_time(args: &[Cons]) -> Result<Cons, EvaluatorError> {
+    arity("date->time", args, 1, 1)?;
+    Ok(Cons::Value(ConsValue::Int(
+        date_arg("date->time", &args[0])?.timestamp_millis(),
+    )))
+}
+
+/// `(time->date ms)`: the UTC date `ms` milliseconds after the Unix epoch.
+pub fn from_time(args: &[Cons]) -> Result<Cons, EvaluatorError> {
+    arity("time->date", args, 1, 1)?;
+    Utc.timestamp_millis_opt(int_arg("time->date", &args[0])?)
+        .single()
+        .map(|d| date(d.fixed_offset()))
+        .ok_or_else(|| out_of_range("time->date"))
+}
+
+/// `(sleep ms)`: waits `ms` milliseconds, or moves a frozen clock on.
+pub fn sleep(args: &[Cons], stg: &LexicalVarStorage) -> Result<Cons, EvaluatorError> {
+    arity("sleep", args, 1, 1)?;
+    let millis = index_arg("sleep", &args[0])?;
+    stg.clock().sleep(TimeDelta::milliseconds(millis as i64));
+    Ok(Cons::Value(ConsValue::NIL))
+}
@@ -0,0 +1,71 @@
+use std::sync::Arc;
+
+use crate::{
+    errors::EvaluatorError,
+    types::{Cons, ConsValue},
+};
+
+use super::arity;
+
+fn eqv_value(a: &ConsValue, b: &ConsValue) -> bool {
+    match (a, b) {
+        // Compares bit patterns, so 0.0 and -0.0 differ and NaN is eqv to
+        // itself.
+        (ConsValue::Float(a), ConsValue::Float(b)) => a.to_bits() == b.to_bits(),
+        _ => a == b,
+    }
+}
+
+/// Identity: pairs and quoted forms are the same only if they share their
+/// storage. Atoms are immutable values without an identity of their own, so
+/// they compare by value, and numbers must also agree in exactness:
+/// `(eqv? 2 2.0)` is false.
+pub fn is_eqv(a: &Cons, b: &Cons) -> bool {
+    match (a, b) {
+        (Cons::Cell(car_a, cdr_a), Cons::Cell(car_b, cdr_b)) => {
+            Arc::ptr_eq(car_a, car_b) && Arc::ptr_eq(cdr_a, cdr_b)
+        }
+        (Cons::Quoted(a), Cons::Quoted(b)) => Arc::ptr_eq(a, b),
+        (Cons::Value(a), Cons::Value(b)) => eqv_value(a, b),
+        _ => false,
+    }
+}
+
+/// Since atoms have no identity, `eq?` can only differ from `eqv?` on values
+/// that do, and those are compared by identity by both.
+pub fn is_eq(a: &Cons, b: &Cons) -> bool {
+    is_eqv(a, b)
+}
+
+/// Structural equality: lists, vectors and bytevectors are equal when their
+/// elements are.
+pub fn is_equal(a: &Cons, b: &Cons) -> bool {
+    match (a, b) {
+        (Cons::Cell(car_a, cdr_a), Cons::Cell(car_b, cdr_b)) => {
+            is_equal(car_a, car_b) && is_equal(cdr_a, cdr_b)
+        }
+        (Cons::Quoted(a), Cons::Quoted(b)) => is_equal(a, b),
+        (Cons::Value(ConsValue::Vector(a)), Cons::Value(ConsValue::Vector(b))) => {
+            if a.ptr_eq(b) {
+                return true;
+            }
+            let (a, b) = (a.to_vec(), b.to_vec());
+            a.len() == b.len() && a.iter().zip(&b).all(|(x, y)| is_equal(x, y))
+        }
+        (Cons::Value(ConsValue::Bytevector(a)), Cons::Value(ConsValue::Bytevector(b))) => {
+            a.ptr_eq(b) || a.to_vec() == b.to_vec()
+        }
+        _ => is_eqv(a, b),
+    }
+}
+
+pub fn compare(
+    name: &str,
+    args: &[Cons],
+    equivalent: fn(&Cons, &Cons) -> bool,
+) -> Result<Cons, EvaluatorError> {
+    arity(name, args, 2, 2)?;
+    Ok(Cons::Value(ConsValue::Boolean(equivalent(
+        &args[0], &args[1],
+    ))))
+}
@@ -0,0 +1,143 @@
+use std::{
+    fs::{self, OpenOptions},
+    io::{self, Write},
+};
+
+use chrono::{DateTime, Utc};
+
+use crate::{
+    errors::EvaluatorError,
+    loader,
+    types::{scope::LexicalVarStorage, Cons, ConsValue},
+};
+
+use super::{arity, string_arg};
+
+/// Checks that `name` may touch the file system and returns its path
+/// argument.
+fn path_arg<'a>(
+    name: &str,
+    args: &'a [Cons],
+    stg: &LexicalVarStorage,
+) -> Result<&'a str, EvaluatorError> {
+    loader::check_file_system(name, stg)?;
+    string_arg(name, &args[0])
+}
+
+fn io_error(name: &str, path: &str, e: io::Error) -> EvaluatorError {
+    EvaluatorError::IoError(format!("{} {}: {}", name, path, e))
+}
+
+fn string(s: String) -> Cons {
+    Cons::Value(ConsValue::String(s))
+}
+
+/// `(read-file path)`: the whole file as a string.
+pub fn read(args: &[Cons], stg: &LexicalVarStorage) -> Result<Cons, EvaluatorError> {
+    arity("read-file", args, 1, 1)?;
+    let path = path_arg("read-file", args, stg)?;
+    fs::read_to_string(path)
+        .map(string)
+        .map_err(|e| io_error("read-file", path, e))
+}
+
+/// `(read-lines path)`: the lines of the file, without their line endings.
+pub fn read_lines(args: &[Cons], stg: &LexicalVarStorage) -> Result<Cons, EvaluatorError> {
+    arity("read-lines", args, 1, 1)?;
+    let path = path_arg("read-lines", args, stg)?;
+    let text = fs::read_to_string(path).map_err(|e| io_error("read-lines", path, e))?;
+    Ok(text.lines().map(|line| string(line.to_string())).collect())
+}
+
+/// `(write-file path str)` and `(append-file path str)`: create the file if
+/// needed, then replace its contents with `str` or add `str` to the end.
+pub fn write(
+    name: &str,
+    append: bool,
+    args: &[Cons],
+    stg: &LexicalVarStorage,
+) -> Result<Cons, EvaluatorError> {
+    arity(name, args, 2, 2)?;
+    let path = path_arg(name, args, stg)?;
+    let text = string_arg(name, &args[1])?;
+    OpenOptions::new()
+        .create(true)
+        .write(true)
+        .append(append)
+        .truncate(!append)
+        .open(path)
+        .and_then(|mut file| file.write_all(text.as_bytes()))
+        .map_err(|e| io_error(name, path, e))?;
+    Ok(Cons::Value(ConsValue::NIL))
+}
+
+/// `(file-exists? path)`: whether there is a file or directory at `path`.
+pub fn exists(args: &[Cons], stg: &LexicalVarStorage) -> Result<Cons, EvaluatorError> {
+    arity("file-exists?", args, 1, 1)?;
+    let path = path_arg("file-exists?", args, stg)?;
+    fs::exists(path)
+        .map(|exists| Cons::Value(ConsValue::Boolean(exists)))
+        .map_err(|e| io_error("file-exists?", path, e))
+}
+
+pub fn delete(args: &[Cons], stg: &LexicalVarStorage) -> Result<Cons, EvaluatorError> {
+    arity("delete-file", args, 1, 1)?;
+    let path = path_arg("delete-file", args, stg)?;
+    fs::remove_file(path).map_err(|e| io_error("delete-file", path, e))?;
+    Ok(Cons::Value(ConsValue::NIL))
+}
+
+/// `(rename-file from to)`: moves `from` to `to`, replacing any file there.
+pub fn rename(args: &[Cons], stg: &LexicalVarStorage) -> Result<Cons, EvaluatorError> {
+    arity("rename-file", args, 2, 2)?;
+    let from = path_arg("rename-file", args, stg)?;
+    let to = string_arg("rename-file", &args[1])?;
+    fs::rename(from, to).map_err(|e| io_error("rename-file", from, e))?;
+    Ok(Cons::Value(ConsValue::NIL))
+}
+
+/// `(make-directory path)`: creates the directory and any missing parents.
+pub fn make_directory(args: &[Cons], stg: &LexicalVarStorage) -> Result<Cons, EvaluatorError> {
+    arity("make-directory", args, 1, 1)?;
+    let path = path_arg("make-directory", args, stg)?;
+    fs::create_dir_all(path).map_err(|e| io_error("make-directory", path, e))?;
+    Ok(Cons::Value(ConsValue::NIL))
+}
+
+/// `(directory-list path)`: the names of the entries in the directory, sorted
+/// so that the result does not depend on the platform.
+pub fn directory_list(args: &[Cons], stg: &LexicalVarStorage) -> Result<Cons, EvaluatorError> {
+    arity("directory-list", args, 1, 1)?;
+    let path = path_arg("directory-list", args, stg)?;
+    let mut names = fs::read_dir(path)
+        .and_then(|entries| {
+            entries
+                .map(|entry| entry.map(|e| e.file_name().to_string_lossy().into_owned()))
+                .collect::<Result<Vec<_>, _>>()
+        })
+        .map_err(|e| io_error("directory-list", path, e))?;
+    names.sort();
+    Ok(names.into_iter().map(string).collect())
+}
+
+/// `(file-size path)`: the size of the file in bytes.
+pub fn size(args: &[Cons], stg: &LexicalVarStorage) -> Result<Cons, EvaluatorError> {
+    arity("file-size", args, 1, 1)?;
+    let path = path_arg("file-size", args, stg)?;
+    let metadata = fs::metadata(path).map_err(|e| io_error("file-size", path, e))?;
+    i64::try_from(metadata.len())
+        .map(|len| Cons::Value(ConsValue::Int(len)))
+        .map_err(|_| EvaluatorError::IoError(format!("file-size {}: file too large", path)))
+}
+
+/// `(file-mtime path)`: when the file was last modified, as a UTC date.
+pub fn mtime(args: &[Cons], stg: &LexicalVarStorage) -> Result<Cons, EvaluatorError> {
+    arity("file-mtime", args, 1, 1)?;
+    let path = path_arg("file-mtime", args, stg)?;
+    let modified = fs::metadata(path)
+        .and_then(|metadata| metadata.modified())
+        .map_err(|e| io_error("file-mtime", path, e))?;
+    Ok(Cons::Value(ConsValue::Date(
+        DateTime::<Utc>::from(modified).fixed_offset(),
+    )))
+}
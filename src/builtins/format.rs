@@ -0,0 +1,162 @@
+use std::{iter::Peekable, str::Chars};
+
+use crate::{
+    errors::EvaluatorError,
+    types::{port::Port, scope::LexicalVarStorage, Cons, ConsValue},
+};
+
+use super::{arity, invalid_argument, numeric::Number, string_arg};
+
+fn format_error(message: String) -> EvaluatorError {
+    EvaluatorError::InvalidArgument(format!("format: {}", message))
+}
+
+/// The parameters between a `~` and its directive character, as in
+/// `~8,2f`, `~5,'0d` or `~10@a`.
+#[derive(Debug, Default)]
+struct Directive {
+    width: Option<usize>,
+    precision: Option<usize>,
+    pad: Option<char>,
+    pad_left: bool,
+}
+
+fn parse_number(chars: &mut Peekable<Chars>) -> Option<usize> {
+    let mut digits = String::new();
+    while let Some(c) = chars.peek().filter(|c| c.is_ascii_digit()) {
+        digits.push(*c);
+        chars.next();
+    }
+    digits.parse().ok()
+}
+
+fn parse_directive(chars: &mut Peekable<Chars>) -> Result<(Directive, char), EvaluatorError> {
+    let mut directive = Directive {
+        width: parse_number(chars),
+        ..Directive::default()
+    };
+    if chars.peek() == Some(&',') {
+        chars.next();
+        if chars.peek() == Some(&'\'') {
+            chars.next();
+            directive.pad = chars.next();
+        } else {
+            directive.precision = parse_number(chars);
+        }
+    }
+    if chars.peek() == Some(&'@') {
+        chars.next();
+        directive.pad_left = true;
+    }
+    match chars.next() {
+        Some(c) => Ok((directive, c)),
+        None => Err(format_error("template ends inside a directive".to_string())),
+    }
+}
+
+/// Pads `s` to `width`. Numbers padded with anything but spaces keep their
+/// sign in front, so `-5` becomes `-0005` rather than `000-5`.
+fn pad(s: String, width: Option<usize>, pad: char, left: bool, numeric: bool) -> String {
+    let len = s.chars().count();
+    match width {
+        Some(width) if width > len => {
+            let padding: String = std::iter::repeat_n(pad, width - len).collect();
+            match s.strip_prefix('-') {
+                Some(digits) if left && numeric && pad != ' ' => format!("-{}{}", padding, digits),
+                _ if left => padding + &s,
+                _ => s + &padding,
+            }
+        }
+        _ => s,
+    }
+}
+
+fn format_integer(arg: &Cons, radix: u32, directive: char) -> Result<String, EvaluatorError> {
+    match arg {
+        Cons::Value(ConsValue::Int(i)) => Ok(match radix {
+            2 => format!("{}{:b}", if *i < 0 { "-" } else { "" }, i.unsigned_abs()),
+            8 => format!("{}{:o}", if *i < 0 { "-" } else { "" }, i.unsigned_abs()),
+            16 => format!("{}{:x}", if *i < 0 { "-" } else { "" }, i.unsigned_abs()),
+            _ => i.to_string(),
+        }),
+        Cons::Value(ConsValue::BigInt(i)) => Ok(i.to_str_radix(radix)),
+        _ => Err(format_error(format!(
+            "~{} expects an integer, got {}",
+            directive, arg
+        ))),
+    }
+}
+
+fn format_float(arg: &Cons, precision: Option<usize>) -> Result<String, EvaluatorError> {
+    let f = match Number::from_cons("format", arg) {
+        Ok(n) => n.to_f64(),
+        Err(_) => return Err(format_error(format!("~f expects a number, got {}", arg))),
+    };
+    Ok(match precision {
+        Some(precision) => format!("{:.*}", precision, f),
+        None => format!("{:?}", f),
+    })
+}
+
+/// Expands the directives of `template` with `args`:
+///
+/// * `~a` displays an argument and `~s` writes it (strings keep their quotes),
+///   left-justified to an optional width, or right-justified with `@`.
+/// * `~d`, `~x`, `~b` and `~o` print an integer in base 10, 16, 2 or 8,
+///   right-justified to an optional width with an optional pad character,
+///   as in `~5,'0d`.
+/// * `~f` prints a number as a float, as in `~8,2f` for width 8 with two
+///   decimal places.
+/// * `~%` is a newline and `~~` a literal tilde.
+pub fn expand(template: &str, args: &[Cons]) -> Result<String, EvaluatorError> {
+    let mut result = String::new();
+    let mut args = args.iter();
+    let mut next_arg = |directive: char| {
+        args.next()
+            .ok_or_else(|| format_error(format!("not enough arguments for ~{}", directive)))
+    };
+    let mut chars = template.chars().peekable();
+    while let Some(c) = chars.next() {
+        if c != '~' {
+            result.push(c);
+            continue;
+        }
+        let (params, directive) = parse_directive(&mut chars)?;
+        let (text, pad_left) = match directive.to_ascii_lowercase() {
+            'a' => (next_arg(directive)?.to_display_string(), params.pad_left),
+            's' => (next_arg(directive)?.to_string(), params.pad_left),
+            'd' => (format_integer(next_arg(directive)?, 10, directive)?, true),
+            'x' => (format_integer(next_arg(directive)?, 16, directive)?, true),
+            'b' => (format_integer(next_arg(directive)?, 2, directive)?, true),
+            'o' => (format_integer(next_arg(directive)?, 8, directive)?, true),
+            'f' => (format_float(next_arg(directive)?, params.precision)?, true),
+            '%' => ("\n".to_string(), false),
+            '~' => ("~".to_string(), false),
+            _ => return Err(format_error(format!("unknown directive ~{}", directive))),
+        };
+        let fill = params.pad.unwrap_or(' ');
+        let numeric = matches!(directive.to_ascii_lowercase(), 'd' | 'x' | 'b' | 'o' | 'f');
+        result.push_str(&pad(text, params.width, fill, pad_left, numeric));
+    }
+    if args.next().is_some() {
+        return Err(format_error("too many arguments".to_string()));
+    }
+    Ok(result)
+}
+
+/// `(format dest template arg ...)`: returns the expanded string when `dest`
+/// is #f, otherwise writes it to the current output (#t) or the given port.
+pub fn format(args: &[Cons], stg: &LexicalVarStorage) -> Result<Cons, EvaluatorError> {
+    arity("format", args, 2, usize::MAX)?;
+    let text = expand(string_arg("format", &args[1])?, &args[2..])?;
+    let port: Port = match &args[0] {
+        Cons::Value(ConsValue::Boolean(false)) => {
+            return Ok(Cons::Value(ConsValue::String(text)));
+        }
+        Cons::Value(ConsValue::Boolean(true)) => stg.output().clone(),
+        Cons::Value(ConsValue::Port(port)) => port.clone(),
+        _ => return Err(invalid_argument("format")),
+    };
+    port.write_str(&text)?;
+    Ok(Cons::Value(ConsValue::NIL))
+}
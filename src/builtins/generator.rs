@@ -0,0 +1,81 @@
+use std::sync::Arc;
+
+use crate::{
+    errors::EvaluatorError,
+    types::{
+        generator::{end_of_generator, Generator},
+        promise::Promise,
+        scope::LexicalVarStorage,
+        Cons, ConsValue,
+    },
+};
+
+use super::{arity, call, index_arg, invalid_argument, procedure_arg};
+
+fn generator_arg<'a>(name: &str, arg: &'a Cons) -> Result<&'a Generator, EvaluatorError> {
+    match arg {
+        Cons::Value(ConsValue::Generator(g)) => Ok(g),
+        _ => Err(invalid_argument(name)),
+    }
+}
+
+/// `(make-generator proc)`: a generator that calls proc with a yield
+/// procedure on a thread of its own.
+pub fn make(args: &[Cons], stg: &LexicalVarStorage) -> Result<Cons, EvaluatorError> {
+    arity("make-generator", args, 1, 1)?;
+    let proc = procedure_arg("make-generator", &args[0])?;
+    let mut env = stg.fork();
+    let generator = Generator::spawn(move |yielder| {
+        call(
+            &proc,
+            vec![Cons::Value(ConsValue::Yielder(yielder))],
+            &mut env,
+        )
+    })?;
+    Ok(Cons::Value(ConsValue::Generator(generator)))
+}
+
+fn is_end(value: &Cons) -> bool {
+    *value == Cons::Value(end_of_generator())
+}
+
+/// `(generator->list gen [n])`: a list of the values `gen` yields until it
+/// ends, or of at most the next `n`.
+pub fn to_list(args: &[Cons]) -> Result<Cons, EvaluatorError> {
+    arity("generator->list", args, 1, 2)?;
+    let generator = generator_arg("generator->list", &args[0])?;
+    let limit = match args.get(1) {
+        Some(n) => Some(index_arg("generator->list", n)?),
+        None => None,
+    };
+    let mut elems = Vec::new();
+    while limit != Some(elems.len()) {
+        let value = generator.next()?;
+        if is_end(&value) {
+            break;
+        }
+        elems.push(value);
+    }
+    Ok(elems.into_iter().collect())
+}
+
+/// `(generator->stream gen)`: a stream of the values `gen` yields. Each value
+/// is only asked for when the stream is forced that far.
+pub fn to_stream(args: &[Cons], stg: &LexicalVarStorage) -> Result<Cons, EvaluatorError> {
+    arity("generator->stream", args, 1, 1)?;
+    let value = generator_arg("generator->stream", &args[0])?.next()?;
+    if is_end(&value) {
+        return Ok(Cons::Value(ConsValue::NIL));
+    }
+    let rest = Cons::from_iter([
+        Cons::Value(ConsValue::symbol("generator->stream")),
+        Cons::Quoted(Arc::new(args[0].clone())),
+    ]);
+    Ok(Cons::Cell(
+        Arc::new(value),
+        Arc::new(Cons::Value(ConsValue::Promise(Promise::delayed(
+            rest,
+            stg.detached(),
+        )))),
+    ))
+}
@@ -0,0 +1,98 @@
+use crate::{
+    errors::EvaluatorError,
+    types::{hash_table::HashTable, scope::LexicalVarStorage, Cons, ConsValue},
+};
+
+use super::{arity, call, invalid_argument, procedure_arg};
+
+pub(crate) fn table_arg<'a>(name: &str, arg: &'a Cons) -> Result<&'a HashTable, EvaluatorError> {
+    match arg {
+        Cons::Value(ConsValue::HashTable(t)) => Ok(t),
+        _ => Err(invalid_argument(name)),
+    }
+}
+
+pub fn make(args: &[Cons]) -> Result<Cons, EvaluatorError> {
+    arity("make-hash-table", args, 0, 0)?;
+    Ok(Cons::Value(ConsValue::HashTable(HashTable::new())))
+}
+
+/// `(hash-ref table key [default])`: a missing key is an error unless a
+/// default is given.
+pub fn get(args: &[Cons]) -> Result<Cons, EvaluatorError> {
+    arity("hash-ref", args, 2, 3)?;
+    let table = table_arg("hash-ref", &args[0])?;
+    match (table.get(&args[1]), args.get(2)) {
+        (Some(value), _) => Ok(value),
+        (None, Some(default)) => Ok(default.clone()),
+        (None, None) => Err(EvaluatorError::InvalidArgument(format!(
+            "No value for key {} in hash table",
+            args[1]
+        ))),
+    }
+}
+
+pub fn set(args: &[Cons]) -> Result<Cons, EvaluatorError> {
+    arity("hash-set!", args, 3, 3)?;
+    table_arg("hash-set!", &args[0])?.insert(args[1].clone(), args[2].clone());
+    Ok(Cons::Value(ConsValue::NIL))
+}
+
+/// Removing a missing key does nothing.
+pub fn delete(args: &[Cons]) -> Result<Cons, EvaluatorError> {
+    arity("hash-delete!", args, 2, 2)?;
+    table_arg("hash-delete!", &args[0])?.remove(&args[1]);
+    Ok(Cons::Value(ConsValue::NIL))
+}
+
+pub fn contains(args: &[Cons]) -> Result<Cons, EvaluatorError> {
+    arity("hash-contains?", args, 2, 2)?;
+    Ok(Cons::Value(ConsValue::Boolean(
+        table_arg("hash-contains?", &args[0])?.contains_key(&args[1]),
+    )))
+}
+
+pub fn count(args: &[Cons]) -> Result<Cons, EvaluatorError> {
+    arity("hash-count", args, 1, 1)?;
+    let len = table_arg("hash-count", &args[0])?.len();
+    Ok(Cons::Value(ConsValue::Int(len as i64)))
+}
+
+pub fn keys(args: &[Cons]) -> Result<Cons, EvaluatorError> {
+    arity("hash-keys", args, 1, 1)?;
+    Ok(table_arg("hash-keys", &args[0])?
+        .entries()
+        .into_iter()
+        .map(|(k, _)| k)
+        .collect())
+}
+
+pub fn values(args: &[Cons]) -> Result<Cons, EvaluatorError> {
+    arity("hash-values", args, 1, 1)?;
+    Ok(table_arg("hash-values", &args[0])?
+        .entries()
+        .into_iter()
+        .map(|(_, v)| v)
+        .collect())
+}
+
+/// Returns the entries as a list of `(key value)` lists.
+pub fn to_alist(args: &[Cons]) -> Result<Cons, EvaluatorError> {
+    arity("hash->alist", args, 1, 1)?;
+    Ok(table_arg("hash->alist", &args[0])?
+        .entries()
+        .into_iter()
+        .map(|(k, v)| Cons::from_iter(vec![k, v]))
+        .collect())
+}
+
+/// `(hash-for-each table proc)`: calls proc with each key and value.
+pub fn for_each(args: &[Cons], stg: &mut LexicalVarStorage) -> Result<Cons, EvaluatorError> {
+    arity("hash-for-each", args, 2, 2)?;
+    let table = table_arg("hash-for-each", &args[0])?;
+    let proc = procedure_arg("hash-for-each", &args[1])?;
+    for (key, value) in table.entries() {
+        call(&proc, vec![key, value], stg)?;
+    }
+    Ok(Cons::Value(ConsValue::NIL))
+}
@@ -0,0 +1,184 @@
+use num_bigint::BigInt;
+use num_integer::Integer;
+use num_traits::{Signed, ToPrimitive, Zero};
+
+use crate::{
+    errors::EvaluatorError,
+    types::{Cons, ConsValue},
+};
+
+use super::{
+    arity, integer_arg, math,
+    numeric::{check_result_bits, exact_pow, Number, MAX_RESULT_BITS},
+};
+
+fn integer(n: BigInt) -> Cons {
+    Cons::Value(ConsValue::integer(n))
+}
+
+fn divisor_arg(name: &str, arg: &Cons) -> Result<BigInt, EvaluatorError> {
+    let d = integer_arg(name, arg)?;
+    if d.is_zero() {
+        Err(EvaluatorError::DivisionByZero(name.to_string()))
+    } else {
+        Ok(d)
+    }
+}
+
+fn integer_args(name: &str, args: &[Cons]) -> Result<Vec<BigInt>, EvaluatorError> {
+    args.iter().map(|arg| integer_arg(name, arg)).collect()
+}
+
+/// Division rounding towards zero.
+pub fn quotient(args: &[Cons]) -> Result<Cons, EvaluatorError> {
+    arity("quotient", args, 2, 2)?;
+    let n = integer_arg("quotient", &args[0])?;
+    let d = divisor_arg("quotient", &args[1])?;
+    Ok(integer(n / d))
+}
+
+/// The remainder has the sign of the dividend, matching `quotient`.
+pub fn remainder(args: &[Cons]) -> Result<Cons, EvaluatorError> {
+    arity("remainder", args, 2, 2)?;
+    let n = integer_arg("remainder", &args[0])?;
+    let d = divisor_arg("remainder", &args[1])?;
+    Ok(integer(n % d))
+}
+
+/// The modulus has the sign of the divisor.
+pub fn modulo(args: &[Cons]) -> Result<Cons, EvaluatorError> {
+    arity("modulo", args, 2, 2)?;
+    let n = integer_arg("modulo", &args[0])?;
+    let d = divisor_arg("modulo", &args[1])?;
+    Ok(integer(n.mod_floor(&d)))
+}
+
+/// `%` on integers: the remainder of the first argument by each of the rest
+/// in turn. Floats keep the old floating-point behaviour, except that a zero
+/// divisor is an error as it is for integers.
+pub fn rem_all(args: &[Cons]) -> Result<Cons, EvaluatorError> {
+    arity("%", args, 1, usize::MAX)?;
+    if args
+        .iter()
+        .all(|arg| matches!(arg, Cons::Value(ConsValue::Int(_) | ConsValue::BigInt(_))))
+    {
+        let mut result = integer_arg("%", &args[0])?;
+        for arg in &args[1..] {
+            result %= divisor_arg("%", arg)?;
+        }
+        return Ok(integer(result));
+    }
+    let mut result = math::float_arg("%", &args[0])?;
+    for arg in &args[1..] {
+        let d = math::float_arg("%", arg)?;
+        if d == 0.0 {
+            return Err(EvaluatorError::DivisionByZero("%".to_string()));
+        }
+        result %= d;
+    }
+    Ok(math::float_result(result))
+}
+
+pub fn gcd(args: &[Cons]) -> Result<Cons, EvaluatorError> {
+    Ok(integer(
+        integer_args("gcd", args)?
+            .iter()
+            .fold(BigInt::zero(), |acc, n| acc.gcd(n)),
+    ))
+}
+
+pub fn lcm(args: &[Cons]) -> Result<Cons, EvaluatorError> {
+    Ok(integer(
+        integer_args("lcm", args)?
+            .iter()
+            .fold(BigInt::from(1), |acc, n| acc.lcm(n)),
+    ))
+}
+
+/// Exact for exact arguments and an integer exponent, like `^`; otherwise
+/// computed with floats.
+pub fn expt(args: &[Cons]) -> Result<Cons, EvaluatorError> {
+    arity("expt", args, 2, 2)?;
+    let base = Number::from_cons("expt", &args[0])?;
+    let exp = Number::from_cons("expt", &args[1])?;
+    Ok(match (base, exp) {
+        (Number::Exact(base), Number::Exact(exp)) => exact_pow(base, &exp, "expt")?.into_cons(),
+        (base, exp) => math::float_result(base.to_f64().powf(exp.to_f64())),
+    })
+}
+
+/// Returns `(s r)` where `s` is the largest integer whose square is at most
+/// `n`, and `r` is `n - s * s`.
+pub fn exact_integer_sqrt(args: &[Cons]) -> Result<Cons, EvaluatorError> {
+    arity("exact-integer-sqrt", args, 1, 1)?;
+    let n = integer_arg("exact-integer-sqrt", &args[0])?;
+    if n.is_negative() {
+        return Err(EvaluatorError::InvalidArgument(format!(
+            "Negative argument for exact-integer-sqrt: {}",
+            n
+        )));
+    }
+    let s = n.sqrt();
+    let r = &n - &s * &s;
+    Ok(Cons::from_iter(vec![integer(s), integer(r)]))
+}
+
+fn fold_bits(
+    name: &str,
+    args: &[Cons],
+    init: i64,
+    op: fn(BigInt, BigInt) -> BigInt,
+) -> Result<Cons, EvaluatorError> {
+    Ok(integer(
+        integer_args(name, args)?
+            .into_iter()
+            .fold(BigInt::from(init), op),
+    ))
+}
+
+/// The bitwise operations treat integers as infinitely sign-extended two's
+/// complement numbers.
+pub fn bit_and(args: &[Cons]) -> Result<Cons, EvaluatorError> {
+    fold_bits("bit-and", args, -1, |a, b| a & b)
+}
+
+pub fn bit_or(args: &[Cons]) -> Result<Cons, EvaluatorError> {
+    fold_bits("bit-or", args, 0, |a, b| a | b)
+}
+
+pub fn bit_xor(args: &[Cons]) -> Result<Cons, EvaluatorError> {
+    fold_bits("bit-xor", args, 0, |a, b| a ^ b)
+}
+
+pub fn bit_not(args: &[Cons]) -> Result<Cons, EvaluatorError> {
+    arity("bit-not", args, 1, 1)?;
+    Ok(integer(!integer_arg("bit-not", &args[0])?))
+}
+
+/// Shifts left for a positive count and right (rounding down) for a negative
+/// one.
+pub fn arithmetic_shift(args: &[Cons]) -> Result<Cons, EvaluatorError> {
+    arity("arithmetic-shift", args, 2, 2)?;
+    let n = integer_arg("arithmetic-shift", &args[0])?;
+    let count = integer_arg("arithmetic-shift", &args[1])?;
+    if count.is_negative() {
+        // Shifting right by at least the width leaves only the sign.
+        let amount = count.magnitude().to_u64().unwrap_or(u64::MAX).min(n.bits());
+        Ok(integer(n >> amount))
+    } else {
+        let amount = count.to_u64().unwrap_or(u64::MAX);
+        if !n.is_zero() {
+            check_result_bits("arithmetic-shift", n.bits().saturating_add(amount))?;
+        }
+        Ok(integer(n << amount.min(MAX_RESULT_BITS)))
+    }
+}
+
+/// Counts the one bits of a non-negative integer, or the zero bits of a
+/// negative one.
+pub fn bit_count(args: &[Cons]) -> Result<Cons, EvaluatorError> {
+    arity("bit-count", args, 1, 1)?;
+    let n = integer_arg("bit-count", &args[0])?;
+    let n = if n.is_negative() { !n } else { n };
+    Ok(integer(BigInt::from(n.magnitude().count_ones())))
+}
@@ -0,0 +1,191 @@
+use std::cmp::Ordering;
+
+use num_integer::Integer;
+use num_rational::BigRational;
+use num_traits::{FromPrimitive, One, Signed};
+
+use crate::{
+    errors::EvaluatorError,
+    types::{Cons, ConsValue},
+};
+
+use super::{arity, invalid_argument, numeric::Number};
+
+pub(crate) fn float_arg(name: &str, arg: &Cons) -> Result<f64, EvaluatorError> {
+    Number::from_cons(name, arg).map(|n| n.to_f64())
+}
+
+/// Inexact results stay floats even when whole, so `(+ 1.5 1.5)` is `3.0`.
+pub(crate) fn float_result(f: f64) -> Cons {
+    Cons::Value(ConsValue::Float(f))
+}
+
+fn unary(name: &str, args: &[Cons], op: fn(f64) -> f64) -> Result<Cons, EvaluatorError> {
+    arity(name, args, 1, 1)?;
+    Ok(float_result(op(float_arg(name, &args[0])?)))
+}
+
+/// Rounds a single numeric argument to an integer, exactly for exact numbers.
+fn rounding(
+    name: &str,
+    args: &[Cons],
+    exact: fn(&BigRational) -> BigRational,
+    inexact: fn(f64) -> f64,
+) -> Result<Cons, EvaluatorError> {
+    arity(name, args, 1, 1)?;
+    match Number::from_cons(name, &args[0])? {
+        Number::Exact(r) => Ok(Cons::Value(ConsValue::rational(exact(&r)))),
+        Number::Inexact(f) => Ok(float_result(inexact(f))),
+    }
+}
+
+fn round_ties_even(r: &BigRational) -> BigRational {
+    let floor = r.floor();
+    if r - &floor == BigRational::new(1.into(), 2.into()) {
+        if floor.to_integer().is_even() {
+            floor
+        } else {
+            floor + BigRational::one()
+        }
+    } else {
+        r.round()
+    }
+}
+
+pub fn abs(args: &[Cons]) -> Result<Cons, EvaluatorError> {
+    arity("abs", args, 1, 1)?;
+    match Number::from_cons("abs", &args[0])? {
+        Number::Exact(r) => Ok(Cons::Value(ConsValue::rational(r.abs()))),
+        Number::Inexact(f) => Ok(float_result(f.abs())),
+    }
+}
+
+/// Shared by `min` and `max`: keeps the argument that compares as `wanted`
+/// against the best so far. The result is a float if any argument is.
+fn extremum(name: &str, args: &[Cons], wanted: Ordering) -> Result<Cons, EvaluatorError> {
+    arity(name, args, 1, usize::MAX)?;
+    let mut best = Number::from_cons(name, &args[0])?;
+    let mut inexact = matches!(best, Number::Inexact(_));
+    for arg in &args[1..] {
+        let n = Number::from_cons(name, arg)?;
+        inexact |= matches!(n, Number::Inexact(_));
+        if n.compare(&best) == Some(wanted) {
+            best = n;
+        }
+    }
+    if inexact {
+        Ok(float_result(best.to_f64()))
+    } else {
+        Ok(best.into_cons())
+    }
+}
+
+pub fn min(args: &[Cons]) -> Result<Cons, EvaluatorError> {
+    extremum("min", args, Ordering::Less)
+}
+
+pub fn max(args: &[Cons]) -> Result<Cons, EvaluatorError> {
+    extremum("max", args, Ordering::Greater)
+}
+
+pub fn floor(args: &[Cons]) -> Result<Cons, EvaluatorError> {
+    rounding("floor", args, BigRational::floor, f64::floor)
+}
+
+pub fn ceiling(args: &[Cons]) -> Result<Cons, EvaluatorError> {
+    rounding("ceiling", args, BigRational::ceil, f64::ceil)
+}
+
+/// Rounds halfway cases to even, as Scheme does.
+pub fn round(args: &[Cons]) -> Result<Cons, EvaluatorError> {
+    rounding("round", args, round_ties_even, f64::round_ties_even)
+}
+
+pub fn truncate(args: &[Cons]) -> Result<Cons, EvaluatorError> {
+    rounding("truncate", args, BigRational::trunc, f64::trunc)
+}
+
+/// Exact for exact perfect squares like 16 or 4/9.
+pub fn sqrt(args: &[Cons]) -> Result<Cons, EvaluatorError> {
+    arity("sqrt", args, 1, 1)?;
+    if let Number::Exact(r) = Number::from_cons("sqrt", &args[0])?
+        && !r.is_negative()
+    {
+        let (numer, denom) = (r.numer().sqrt(), r.denom().sqrt());
+        if &numer * &numer == *r.numer() && &denom * &denom == *r.denom() {
+            return Ok(Cons::Value(ConsValue::rational(BigRational::new(
+                numer, denom,
+            ))));
+        }
+    }
+    unary("sqrt", args, f64::sqrt)
+}
+
+pub fn exp(args: &[Cons]) -> Result<Cons, EvaluatorError> {
+    unary("exp", args, f64::exp)
+}
+
+/// The natural logarithm, or the logarithm in an optional base.
+pub fn log(args: &[Cons]) -> Result<Cons, EvaluatorError> {
+    arity("log", args, 1, 2)?;
+    let x = float_arg("log", &args[0])?;
+    match args.get(1) {
+        Some(base) => Ok(float_result(x.ln() / float_arg("log", base)?.ln())),
+        None => Ok(float_result(x.ln())),
+    }
+}
+
+pub fn sin(args: &[Cons]) -> Result<Cons, EvaluatorError> {
+    unary("sin", args, f64::sin)
+}
+
+pub fn cos(args: &[Cons]) -> Result<Cons, EvaluatorError> {
+    unary("cos", args, f64::cos)
+}
+
+pub fn tan(args: &[Cons]) -> Result<Cons, EvaluatorError> {
+    unary("tan", args, f64::tan)
+}
+
+pub fn asin(args: &[Cons]) -> Result<Cons, EvaluatorError> {
+    unary("asin", args, f64::asin)
+}
+
+pub fn acos(args: &[Cons]) -> Result<Cons, EvaluatorError> {
+    unary("acos", args, f64::acos)
+}
+
+/// `(atan y)`, or `(atan y x)` for the angle of the point (x, y).
+pub fn atan(args: &[Cons]) -> Result<Cons, EvaluatorError> {
+    arity("atan", args, 1, 2)?;
+    let y = float_arg("atan", &args[0])?;
+    match args.get(1) {
+        Some(x) => Ok(float_result(y.atan2(float_arg("atan", x)?))),
+        None => Ok(float_result(y.atan())),
+    }
+}
+
+pub fn exact_to_inexact(args: &[Cons]) -> Result<Cons, EvaluatorError> {
+    arity("exact->inexact", args, 1, 1)?;
+    Ok(Cons::Value(ConsValue::Float(float_arg(
+        "exact->inexact",
+        &args[0],
+    )?)))
+}
+
+pub fn inexact_to_exact(args: &[Cons]) -> Result<Cons, EvaluatorError> {
+    arity("inexact->exact", args, 1, 1)?;
+    match &args[0] {
+        Cons::Value(ConsValue::Int(_) | ConsValue::BigInt(_) | ConsValue::Rational(_)) => {
+            Ok(args[0].clone())
+        }
+        Cons::Value(ConsValue::Float(f)) => match BigRational::from_f64(*f) {
+            Some(r) => Ok(Cons::Value(ConsValue::rational(r))),
+            None => Err(EvaluatorError::InvalidArgument(format!(
+                "No exact value for {}",
+                f
+            ))),
+        },
+        _ => Err(invalid_argument("inexact->exact")),
+    }
+}
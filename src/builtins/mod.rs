@@ -0,0 +1,110 @@
+//! Implementations of the larger families of `BuiltinFunction`s. Each
+//! function takes the already-evaluated arguments, like
+//! `BuiltinFunction::call`.
+
+pub mod bytevector;
+pub mod character;
+pub mod control;
+pub mod date;
+pub mod equality;
+pub mod file;
+pub mod format;
+pub mod generator;
+pub mod hash_table;
+pub mod integer;
+pub mod math;
+pub mod numeric;
+pub mod order;
+pub mod parameter;
+pub mod promise;
+pub mod random;
+pub mod stream;
+pub mod string;
+pub mod symbol;
+pub mod vector;
+
+use num_bigint::BigInt;
+
+use crate::{
+    errors::EvaluatorError,
+    evaluator::{call_procedure, into_cons, EvalReturnType},
+    types::{scope::LexicalVarStorage, Cons, ConsValue},
+};
+
+pub(crate) fn invalid_argument(name: &str) -> EvaluatorError {
+    EvaluatorError::InvalidArgument(format!("Invalid argument type for {}", name))
+}
+
+pub(crate) fn arity(
+    name: &str,
+    args: &[Cons],
+    min: usize,
+    max: usize,
+) -> Result<(), EvaluatorError> {
+    if args.len() < min || args.len() > max {
+        Err(EvaluatorError::InvalidArgument(format!(
+            "Wrong number of arguments for {}: {}",
+            name,
+            args.len()
+        )))
+    } else {
+        Ok(())
+    }
+}
+
+pub(crate) fn string_arg<'a>(name: &str, arg: &'a Cons) -> Result<&'a str, EvaluatorError> {
+    match arg {
+        Cons::Value(ConsValue::String(s)) => Ok(s),
+        _ => Err(invalid_argument(name)),
+    }
+}
+
+pub(crate) fn int_arg(name: &str, arg: &Cons) -> Result<i64, EvaluatorError> {
+    match arg {
+        Cons::Value(ConsValue::Int(i)) => Ok(*i),
+        _ => Err(invalid_argument(name)),
+    }
+}
+
+/// Reads an integer argument of any size.
+pub(crate) fn integer_arg(name: &str, arg: &Cons) -> Result<BigInt, EvaluatorError> {
+    match arg {
+        Cons::Value(ConsValue::Int(i)) => Ok(BigInt::from(*i)),
+        Cons::Value(ConsValue::BigInt(i)) => Ok(BigInt::clone(i)),
+        _ => Err(invalid_argument(name)),
+    }
+}
+
+/// Reads a non-negative integer argument used as an index or length.
+pub(crate) fn index_arg(name: &str, arg: &Cons) -> Result<usize, EvaluatorError> {
+    usize::try_from(int_arg(name, arg)?).map_err(|_| {
+        EvaluatorError::InvalidArgument(format!("Negative index for {}: {}", name, arg))
+    })
+}
+
+/// Checks that `arg` can be called, like a procedure or a continuation, for
+/// the builtins that take one.
+pub(crate) fn procedure_arg(name: &str, arg: &Cons) -> Result<EvalReturnType, EvaluatorError> {
+    match arg {
+        Cons::Value(ConsValue::Procedure(p)) => Ok(p.clone().into()),
+        Cons::Value(
+            ConsValue::Continuation(_)
+            | ConsValue::Generator(_)
+            | ConsValue::Yielder(_)
+            | ConsValue::Parameter(_),
+        ) => Ok(EvalReturnType::CONS(arg.clone())),
+        _ => Err(EvaluatorError::NotAFunction(format!(
+            "{} is not a procedure for {}",
+            arg, name
+        ))),
+    }
+}
+
+/// Calls a procedure from `procedure_arg` with already-evaluated arguments.
+pub(crate) fn call(
+    proc: &EvalReturnType,
+    args: Vec<Cons>,
+    stg: &mut LexicalVarStorage,
+) -> Result<Cons, EvaluatorError> {
+    into_cons(call_procedure(proc.clone(), args, stg)?)
+}
@@ -0,0 +1,298 @@
+use std::cmp::Ordering;
+
+use num_bigint::BigInt;
+use num_rational::BigRational;
+use num_traits::{FromPrimitive, One, Signed, ToPrimitive, Zero};
+
+use crate::{
+    errors::EvaluatorError,
+    types::{Cons, ConsValue},
+};
+
+use super::{invalid_argument, math};
+
+/// A numeric argument of the arithmetic builtins. Integers and rationals are
+/// kept exact and only become floats when mixed with one.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) enum Number {
+    Exact(BigRational),
+    Inexact(f64),
+}
+
+impl Number {
+    pub(crate) fn from_cons(name: &str, arg: &Cons) -> Result<Number, EvaluatorError> {
+        match arg {
+            Cons::Value(ConsValue::Int(i)) => {
+                Ok(Number::Exact(BigRational::from_integer(BigInt::from(*i))))
+            }
+            Cons::Value(ConsValue::BigInt(i)) => {
+                Ok(Number::Exact(BigRational::from_integer(BigInt::clone(i))))
+            }
+            Cons::Value(ConsValue::Rational(r)) => Ok(Number::Exact(BigRational::clone(r))),
+            Cons::Value(ConsValue::Float(f)) => Ok(Number::Inexact(*f)),
+            _ => Err(invalid_argument(name)),
+        }
+    }
+
+    pub(crate) fn to_f64(&self) -> f64 {
+        match self {
+            Number::Exact(r) => r.to_f64().unwrap_or(f64::NAN),
+            Number::Inexact(f) => *f,
+        }
+    }
+
+    pub(crate) fn into_cons(self) -> Cons {
+        match self {
+            Number::Exact(r) => Cons::Value(ConsValue::rational(r)),
+            Number::Inexact(f) => math::float_result(f),
+        }
+    }
+
+    fn binary(
+        self,
+        other: Number,
+        exact: fn(BigRational, BigRational) -> BigRational,
+        inexact: fn(f64, f64) -> f64,
+    ) -> Number {
+        match (self, other) {
+            (Number::Exact(a), Number::Exact(b)) => Number::Exact(exact(a, b)),
+            (a, b) => Number::Inexact(inexact(a.to_f64(), b.to_f64())),
+        }
+    }
+
+    pub(crate) fn compare(&self, other: &Number) -> Option<Ordering> {
+        match (self, other) {
+            (Number::Exact(a), Number::Exact(b)) => Some(a.cmp(b)),
+            (a, b) => a.to_f64().partial_cmp(&b.to_f64()),
+        }
+    }
+}
+
+fn numbers(name: &str, args: &[Cons]) -> Result<Vec<Number>, EvaluatorError> {
+    args.iter()
+        .map(|arg| Number::from_cons(name, arg))
+        .collect()
+}
+
+fn fold(
+    name: &str,
+    args: &[Cons],
+    init: i64,
+    exact: fn(BigRational, BigRational) -> BigRational,
+    inexact: fn(f64, f64) -> f64,
+) -> Result<Cons, EvaluatorError> {
+    Ok(numbers(name, args)?
+        .into_iter()
+        .fold(
+            Number::Exact(BigRational::from_integer(BigInt::from(init))),
+            |acc, n| acc.binary(n, exact, inexact),
+        )
+        .into_cons())
+}
+
+pub fn add(args: &[Cons]) -> Result<Cons, EvaluatorError> {
+    fold("+", args, 0, |a, b| a + b, |a, b| a + b)
+}
+
+pub fn mul(args: &[Cons]) -> Result<Cons, EvaluatorError> {
+    fold("*", args, 1, |a, b| a * b, |a, b| a * b)
+}
+
+/// Subtracts the rest of the arguments from the first, or negates a single
+/// argument.
+pub fn sub(args: &[Cons]) -> Result<Cons, EvaluatorError> {
+    let mut numbers = numbers("-", args)?.into_iter();
+    let first = match numbers.next() {
+        Some(first) => first,
+        None => return Ok(Cons::Value(ConsValue::Int(0))),
+    };
+    if args.len() == 1 {
+        return Ok(match first {
+            Number::Exact(r) => Cons::Value(ConsValue::rational(-r)),
+            Number::Inexact(f) => Cons::Value(ConsValue::Float(-f)),
+        });
+    }
+    Ok(numbers
+        .fold(first, |acc, n| acc.binary(n, |a, b| a - b, |a, b| a - b))
+        .into_cons())
+}
+
+fn exact_divide(
+    a: BigRational,
+    b: &BigRational,
+    name: &str,
+) -> Result<BigRational, EvaluatorError> {
+    if b.is_zero() {
+        Err(EvaluatorError::DivisionByZero(name.to_string()))
+    } else {
+        Ok(a / b)
+    }
+}
+
+/// Divides the first argument by the rest, or inverts a single argument.
+/// Integers that do not divide evenly give a rational.
+pub fn div(args: &[Cons]) -> Result<Cons, EvaluatorError> {
+    let mut numbers = numbers("/", args)?;
+    if numbers.len() == 1 {
+        numbers.insert(0, Number::Exact(BigRational::one()));
+    }
+    let mut numbers = numbers.into_iter();
+    let mut result = match numbers.next() {
+        Some(first) => first,
+        None => return Ok(Cons::Value(ConsValue::Int(1))),
+    };
+    for n in numbers {
+        result = match (result, n) {
+            (Number::Exact(a), Number::Exact(b)) => Number::Exact(exact_divide(a, &b, "/")?),
+            (a, b) => Number::Inexact(a.to_f64() / b.to_f64()),
+        };
+    }
+    Ok(result.into_cons())
+}
+
+/// Divides and truncates towards zero; exact when every argument is, a whole
+/// float otherwise.
+pub fn floor_div(args: &[Cons]) -> Result<Cons, EvaluatorError> {
+    let numbers = numbers("floordiv", args)?;
+    let mut numbers = numbers.into_iter();
+    let mut result = match numbers.next() {
+        Some(first) => first,
+        None => return Ok(Cons::Value(ConsValue::Int(1))),
+    };
+    for n in numbers {
+        result = match (result, n) {
+            (Number::Exact(a), Number::Exact(b)) => Number::Exact(exact_divide(a, &b, "floordiv")?),
+            (a, b) => Number::Inexact(a.to_f64() / b.to_f64()),
+        };
+    }
+    match result {
+        Number::Exact(r) => Ok(Cons::Value(ConsValue::integer(r.trunc().to_integer()))),
+        Number::Inexact(f) => Ok(math::float_result(f.trunc())),
+    }
+}
+
+/// The largest result, in bits, that exact powers and `arithmetic-shift`
+/// will build, so that a typo in an exponent fails instead of exhausting
+/// memory.
+pub(crate) const MAX_RESULT_BITS: u64 = 1 << 24;
+
+pub(crate) fn check_result_bits(name: &str, bits: u64) -> Result<(), EvaluatorError> {
+    if bits > MAX_RESULT_BITS {
+        Err(EvaluatorError::InvalidArgument(format!(
+            "Result of {} would exceed {} bits",
+            name, MAX_RESULT_BITS
+        )))
+    } else {
+        Ok(())
+    }
+}
+
+/// Raises `base` to an exact power: exactly for integer exponents, through
+/// floats otherwise.
+pub(crate) fn exact_pow(
+    base: BigRational,
+    exp: &BigRational,
+    name: &str,
+) -> Result<Number, EvaluatorError> {
+    if !exp.is_integer() {
+        return Ok(Number::Inexact(
+            base.to_f64()
+                .unwrap_or(f64::NAN)
+                .powf(exp.to_f64().unwrap_or(f64::NAN)),
+        ));
+    }
+    let magnitude = exp.to_integer().abs();
+    let power = if base.denom().is_one() && base.numer().magnitude().bits() <= 1 {
+        // 0, 1 and -1 only depend on the parity of a large exponent.
+        if magnitude > BigInt::one() {
+            if magnitude.bit(0) {
+                1
+            } else {
+                2
+            }
+        } else {
+            magnitude.to_usize().unwrap_or(0)
+        }
+    } else {
+        let bits = base.numer().bits().max(base.denom().bits());
+        check_result_bits(
+            name,
+            bits.saturating_mul(magnitude.to_u64().unwrap_or(u64::MAX)),
+        )?;
+        magnitude.to_usize().ok_or_else(|| {
+            EvaluatorError::InvalidArgument(format!("Exponent {} is too large for {}", exp, name))
+        })?
+    };
+    let result = num_traits::pow(base, power);
+    if exp.is_negative() {
+        Ok(Number::Exact(exact_divide(
+            BigRational::one(),
+            &result,
+            name,
+        )?))
+    } else {
+        Ok(Number::Exact(result))
+    }
+}
+
+/// Raises the first argument to each of the rest in turn, exactly for exact
+/// bases and integer exponents.
+pub fn pow(args: &[Cons]) -> Result<Cons, EvaluatorError> {
+    let numbers = numbers("^", args)?;
+    let mut numbers = numbers.into_iter();
+    let mut result = match numbers.next() {
+        Some(first) => first,
+        None => return Ok(Cons::Value(ConsValue::Int(1))),
+    };
+    for n in numbers {
+        result = match (result, n) {
+            (Number::Exact(base), Number::Exact(exp)) => exact_pow(base, &exp, "^")?,
+            (base, exp) => Number::Inexact(base.to_f64().powf(exp.to_f64())),
+        };
+    }
+    Ok(result.into_cons())
+}
+
+/// Checks that every adjacent pair of numeric arguments is ordered as one of
+/// `accepted`. Comparisons involving NaN are always false.
+pub fn compare(name: &str, args: &[Cons], accepted: &[Ordering]) -> Result<Cons, EvaluatorError> {
+    let numbers = numbers(name, args)?;
+    Ok(Cons::Value(ConsValue::Boolean(numbers.windows(2).all(
+        |pair| matches!(pair[0].compare(&pair[1]), Some(o) if accepted.contains(&o)),
+    ))))
+}
+
+/// Splits a number into its numerator and denominator in lowest terms. A
+/// float is split through its exact binary value and gives floats.
+fn ratio_parts(name: &str, args: &[Cons]) -> Result<(Number, Number), EvaluatorError> {
+    super::arity(name, args, 1, 1)?;
+    let (r, exact) = match Number::from_cons(name, &args[0])? {
+        Number::Exact(r) => (r, true),
+        Number::Inexact(f) => match BigRational::from_f64(f) {
+            Some(r) => (r, false),
+            None => {
+                return Err(EvaluatorError::InvalidArgument(format!(
+                    "No rational value for {}",
+                    f
+                )));
+            }
+        },
+    };
+    let part = |n: &BigInt| {
+        let n = BigRational::from_integer(n.clone());
+        if exact {
+            Number::Exact(n)
+        } else {
+            Number::Inexact(n.to_f64().unwrap_or(f64::NAN))
+        }
+    };
+    Ok((part(r.numer()), part(r.denom())))
+}
+
+pub fn numerator(args: &[Cons]) -> Result<Cons, EvaluatorError> {
+    Ok(ratio_parts("numerator", args)?.0.into_cons())
+}
+
+pub fn denominator(args: &[Cons]) -> Result<Cons, EvaluatorError> {
+    Ok(ratio_parts("denominator", args)?.1.into_cons())
+}
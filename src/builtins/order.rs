@@ -0,0 +1,148 @@
+use std::cmp::Ordering;
+
+use crate::{
+    errors::EvaluatorError,
+    types::{Cons, ConsValue},
+};
+
+use super::{arity, invalid_argument, numeric::Number};
+
+/// Where each kind of value sorts relative to the others in `total_cmp`.
+fn rank(c: &Cons) -> u8 {
+    match c {
+        Cons::Value(ConsValue::NIL) => 0,
+        Cons::Value(ConsValue::Boolean(_)) => 1,
+        Cons::Value(
+            ConsValue::Int(_) | ConsValue::BigInt(_) | ConsValue::Rational(_) | ConsValue::Float(_),
+        ) => 2,
+        Cons::Value(ConsValue::Char(_)) => 3,
+        Cons::Value(ConsValue::String(_)) => 4,
+        Cons::Value(ConsValue::Symbol(_)) => 5,
+        Cons::Cell(..) => 6,
+        Cons::Value(ConsValue::Vector(_)) => 7,
+        Cons::Value(ConsValue::Bytevector(_)) => 8,
+        Cons::Quoted(_) => 9,
+        Cons::Value(ConsValue::Comment(_)) => 10,
+        Cons::Value(ConsValue::Port(_)) => 11,
+        Cons::Value(ConsValue::HashTable(_)) => 12,
+        Cons::Value(ConsValue::Promise(_)) => 13,
+        Cons::Value(ConsValue::Continuation(_)) => 14,
+        Cons::Value(ConsValue::Generator(_)) => 15,
+        Cons::Value(ConsValue::Yielder(_)) => 16,
+        Cons::Value(ConsValue::Parameter(_)) => 17,
+        Cons::Value(ConsValue::Date(_)) => 18,
+        Cons::Value(ConsValue::Duration(_)) => 19,
+        Cons::Value(ConsValue::Procedure(_)) => 20,
+    }
+}
+
+fn number_cmp(a: &Number, b: &Number) -> Ordering {
+    match (a, b) {
+        (Number::Exact(a), Number::Exact(b)) => a.cmp(b),
+        (Number::Inexact(a), Number::Inexact(b)) => a.total_cmp(b),
+        // Equal exact and inexact numbers are told apart so that only `eqv?`
+        // values compare as equal; the exact one goes first.
+        (a, b) => a
+            .to_f64()
+            .total_cmp(&b.to_f64())
+            .then_with(|| matches!(a, Number::Inexact(_)).cmp(&matches!(b, Number::Inexact(_)))),
+    }
+}
+
+/// A total order over all values: the empty list, then booleans, numbers,
+/// characters, strings, symbols, lists, vectors and bytevectors (element by
+/// element) and quoted forms. Numbers compare numerically, characters by
+/// code point and strings and symbols lexicographically.
+pub fn total_cmp(a: &Cons, b: &Cons) -> Ordering {
+    match (a, b) {
+        (Cons::Value(ConsValue::Boolean(a)), Cons::Value(ConsValue::Boolean(b))) => a.cmp(b),
+        (Cons::Value(ConsValue::Char(a)), Cons::Value(ConsValue::Char(b))) => a.cmp(b),
+        (Cons::Value(ConsValue::String(a)), Cons::Value(ConsValue::String(b))) => a.cmp(b),
+        (Cons::Value(ConsValue::Symbol(a)), Cons::Value(ConsValue::Symbol(b))) => {
+            a.as_str().cmp(b.as_str())
+        }
+        (Cons::Value(ConsValue::Comment(a)), Cons::Value(ConsValue::Comment(b))) => a.cmp(b),
+        (Cons::Value(ConsValue::Date(a)), Cons::Value(ConsValue::Date(b))) => a.cmp(b),
+        (Cons::Value(ConsValue::Duration(a)), Cons::Value(ConsValue::Duration(b))) => a.cmp(b),
+        (Cons::Cell(car_a, cdr_a), Cons::Cell(car_b, cdr_b)) => {
+            total_cmp(car_a, car_b).then_with(|| total_cmp(cdr_a, cdr_b))
+        }
+        (Cons::Value(ConsValue::Vector(a)), Cons::Value(ConsValue::Vector(b))) => {
+            if a.ptr_eq(b) {
+                return Ordering::Equal;
+            }
+            let (a, b) = (a.to_vec(), b.to_vec());
+            a.iter()
+                .zip(&b)
+                .map(|(x, y)| total_cmp(x, y))
+                .find(|o| o.is_ne())
+                .unwrap_or_else(|| a.len().cmp(&b.len()))
+        }
+        (Cons::Value(ConsValue::Bytevector(a)), Cons::Value(ConsValue::Bytevector(b))) => {
+            a.to_vec().cmp(&b.to_vec())
+        }
+        (Cons::Quoted(a), Cons::Quoted(b)) => total_cmp(a, b),
+        _ if rank(a) == 2 && rank(b) == 2 => number_cmp(
+            &Number::from_cons("compare", a).unwrap(),
+            &Number::from_cons("compare", b).unwrap(),
+        ),
+        _ => rank(a).cmp(&rank(b)),
+    }
+}
+
+/// `(compare a b)`: -1, 0 or 1 as `a` sorts before, with or after `b`.
+pub fn compare(args: &[Cons]) -> Result<Cons, EvaluatorError> {
+    arity("compare", args, 2, 2)?;
+    Ok(Cons::Value(ConsValue::Int(
+        total_cmp(&args[0], &args[1]) as i64
+    )))
+}
+
+/// `(sort list)`: the elements of `list` in the order of `compare`. The sort
+/// is stable.
+pub fn sort(args: &[Cons]) -> Result<Cons, EvaluatorError> {
+    arity("sort", args, 1, 1)?;
+    match &args[0] {
+        Cons::Cell(..) | Cons::Value(ConsValue::NIL) => {
+            let mut elems: Vec<Cons> = args[0].clone().into_iter().collect();
+            elems.sort_by(total_cmp);
+            Ok(elems.into_iter().collect())
+        }
+        _ => Err(invalid_argument("sort")),
+    }
+}
+
+fn text(arg: &Cons) -> Option<&str> {
+    match arg {
+        Cons::Value(ConsValue::String(s)) => Some(s),
+        Cons::Value(ConsValue::Symbol(s)) => Some(s.as_str()),
+        _ => None,
+    }
+}
+
+/// Checks that every adjacent pair of `items` is ordered as one of
+/// `accepted`.
+pub(crate) fn chain<T: Ord>(items: &[T], accepted: &[Ordering]) -> Cons {
+    Cons::Value(ConsValue::Boolean(
+        items
+            .windows(2)
+            .all(|pair| accepted.contains(&pair[0].cmp(&pair[1]))),
+    ))
+}
+
+/// The `<` family: numbers compare numerically, characters by code point,
+/// and strings and symbols lexicographically. All arguments must be of one
+/// of these kinds.
+pub fn ordered(name: &str, args: &[Cons], accepted: &[Ordering]) -> Result<Cons, EvaluatorError> {
+    let all = |kind: fn(&Cons) -> bool| args.iter().all(kind);
+    if all(|c| matches!(c, Cons::Value(ConsValue::Char(_)))) {
+        return super::character::compare(name, args, accepted);
+    }
+    if all(|c| matches!(c, Cons::Value(ConsValue::String(_))))
+        || all(|c| matches!(c, Cons::Value(ConsValue::Symbol(_))))
+    {
+        let texts: Vec<&str> = args.iter().filter_map(text).collect();
+        return Ok(chain(&texts, accepted));
+    }
+    super::numeric::compare(name, args, accepted)
+}
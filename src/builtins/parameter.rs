@@ -0,0 +1,23 @@
+use crate::{
+    errors::EvaluatorError,
+    types::{parameter::Parameter, scope::LexicalVarStorage, Cons, ConsValue},
+};
+
+use super::{arity, call, procedure_arg};
+
+/// `(make-parameter value [converter])`: the converter is applied to value
+/// now and to each value the parameter is later bound to.
+pub fn make(args: &[Cons], stg: &mut LexicalVarStorage) -> Result<Cons, EvaluatorError> {
+    arity("make-parameter", args, 1, 2)?;
+    let converter = match args.get(1) {
+        Some(c) => Some(procedure_arg("make-parameter", c)?),
+        None => None,
+    };
+    let value = match &converter {
+        Some(c) => call(c, vec![args[0].clone()], stg)?,
+        None => args[0].clone(),
+    };
+    Ok(Cons::Value(ConsValue::Parameter(Parameter::new(
+        value, converter,
+    ))))
+}
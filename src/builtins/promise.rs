@@ -0,0 +1,57 @@
+use crate::{
+    errors::EvaluatorError,
+    evaluator::lisp_eval,
+    types::{promise::Promise, Cons, ConsValue},
+};
+
+use super::arity;
+
+/// Returns the value of `value` if it is a promise, forcing it first if
+/// needed, and `value` itself otherwise. A chain of `delay-force` promises is
+/// followed in a loop rather than by recursion, and every promise along it
+/// remembers the final value.
+pub fn force_value(value: &Cons) -> Result<Cons, EvaluatorError> {
+    let Cons::Value(ConsValue::Promise(promise)) = value else {
+        return Ok(value.clone());
+    };
+    let mut chain = vec![promise.clone()];
+    let mut current = promise.clone();
+    let result = loop {
+        let Some(pending) = current.pending() else {
+            break current.value().unwrap_or(Cons::Value(ConsValue::NIL));
+        };
+        let result = lisp_eval(&pending.expr, &mut pending.env.fork())?;
+        // Forcing the expression may have forced this promise too.
+        if let Some(value) = current.value() {
+            break value;
+        }
+        match result {
+            Cons::Value(ConsValue::Promise(next)) if pending.chained => {
+                chain.push(next.clone());
+                current = next;
+            }
+            result => break result,
+        }
+    };
+    Ok(chain
+        .iter()
+        .rev()
+        .fold(result, |value, promise| promise.resolve(value)))
+}
+
+pub fn force(args: &[Cons]) -> Result<Cons, EvaluatorError> {
+    arity("force", args, 1, 1)?;
+    force_value(&args[0])
+}
+
+/// `(make-promise obj)`: a promise already forced to `obj`, or `obj` itself
+/// if it is a promise.
+pub fn make_promise(args: &[Cons]) -> Result<Cons, EvaluatorError> {
+    arity("make-promise", args, 1, 1)?;
+    match &args[0] {
+        Cons::Value(ConsValue::Promise(_)) => Ok(args[0].clone()),
+        value => Ok(Cons::Value(ConsValue::Promise(Promise::forced(
+            value.clone(),
+        )))),
+    }
+}
@@ -0,0 +1,72 @@
+use crate::{
+    errors::EvaluatorError,
+    types::{scope::LexicalVarStorage, vector::Vector, Cons, ConsValue},
+};
+
+use super::{arity, int_arg, invalid_argument};
+
+/// The elements of a list or vector argument.
+fn elements(name: &str, arg: &Cons) -> Result<Vec<Cons>, EvaluatorError> {
+    match arg {
+        Cons::Cell(..) | Cons::Value(ConsValue::NIL) => Ok(arg.clone().into_iter().collect()),
+        Cons::Value(ConsValue::Vector(v)) => Ok(v.to_vec()),
+        _ => Err(invalid_argument(name)),
+    }
+}
+
+/// `(random-seed! n)`: restarts the interpreter's random sequence, so that the
+/// same seed always gives the same numbers.
+pub fn seed(args: &[Cons], stg: &LexicalVarStorage) -> Result<Cons, EvaluatorError> {
+    arity("random-seed!", args, 1, 1)?;
+    stg.random().seed(int_arg("random-seed!", &args[0])? as u64);
+    Ok(Cons::Value(ConsValue::NIL))
+}
+
+/// `(random-integer n)`: an integer from 0 up to but not including `n`.
+pub fn integer(args: &[Cons], stg: &LexicalVarStorage) -> Result<Cons, EvaluatorError> {
+    arity("random-integer", args, 1, 1)?;
+    let bound = int_arg("random-integer", &args[0])?;
+    if bound <= 0 {
+        return Err(EvaluatorError::InvalidArgument(format!(
+            "random-integer needs a positive bound, got {}",
+            bound
+        )));
+    }
+    Ok(Cons::Value(ConsValue::Int(
+        stg.random().below(bound as u64) as i64,
+    )))
+}
+
+/// `(random-real)`: a float from 0 up to but not including 1.
+pub fn real(args: &[Cons], stg: &LexicalVarStorage) -> Result<Cons, EvaluatorError> {
+    arity("random-real", args, 0, 0)?;
+    Ok(Cons::Value(ConsValue::Float(stg.random().real())))
+}
+
+pub fn choice(args: &[Cons], stg: &LexicalVarStorage) -> Result<Cons, EvaluatorError> {
+    arity("random-choice", args, 1, 1)?;
+    let elems = elements("random-choice", &args[0])?;
+    if elems.is_empty() {
+        return Err(EvaluatorError::InvalidArgument(
+            "random-choice needs a non-empty list or vector".to_string(),
+        ));
+    }
+    let index = stg.random().below(elems.len() as u64) as usize;
+    Ok(elems[index].clone())
+}
+
+/// `(shuffle seq)`: a new list or vector of the elements of `seq` in random
+/// order.
+pub fn shuffle(args: &[Cons], stg: &LexicalVarStorage) -> Result<Cons, EvaluatorError> {
+    arity("shuffle", args, 1, 1)?;
+    let mut elems = elements("shuffle", &args[0])?;
+    // Fisher-Yates, so every order is equally likely.
+    for i in (1..elems.len()).rev() {
+        let j = stg.random().below(i as u64 + 1) as usize;
+        elems.swap(i, j);
+    }
+    match &args[0] {
+        Cons::Value(ConsValue::Vector(_)) => Ok(Cons::Value(ConsValue::Vector(Vector::new(elems)))),
+        _ => Ok(elems.into_iter().collect()),
+    }
+}
@@ -0,0 +1,137 @@
+//! Streams are lazy lists: either the empty list or a pair of an element and
+//! a promise of the rest of the stream.
+
+use std::sync::Arc;
+
+use crate::{
+    errors::EvaluatorError,
+    types::{promise::Promise, scope::LexicalVarStorage, Cons, ConsValue},
+};
+
+use super::{arity, call, index_arg, invalid_argument, procedure_arg, promise::force_value};
+
+/// Splits a non-empty stream into its first element and the promise of the
+/// rest.
+pub(crate) fn stream_pair(name: &str, arg: &Cons) -> Result<(Cons, Cons), EvaluatorError> {
+    match arg {
+        Cons::Cell(car, cdr) if matches!(**cdr, Cons::Value(ConsValue::Promise(_))) => {
+            Ok((Cons::clone(car), Cons::clone(cdr)))
+        }
+        _ => Err(invalid_argument(name)),
+    }
+}
+
+/// Checks that `arg` is a stream, returning its first element and the promise
+/// of the rest unless it is empty.
+pub(crate) fn stream_arg(name: &str, arg: &Cons) -> Result<Option<(Cons, Cons)>, EvaluatorError> {
+    match arg {
+        Cons::Value(ConsValue::NIL) => Ok(None),
+        _ => stream_pair(name, arg).map(Some),
+    }
+}
+
+/// Returns a promise of `(name arg ... (force 'rest))`, which carries on a
+/// stream operation over the promised rest of a stream once it is forced.
+/// Values that are not literals, like procedures, are passed quoted.
+pub(crate) fn lazy_call(name: &str, args: Vec<Cons>, rest: Cons, env: LexicalVarStorage) -> Cons {
+    let force_rest = Cons::from_iter([
+        Cons::Value(ConsValue::symbol("force")),
+        Cons::Quoted(Arc::new(rest)),
+    ]);
+    let expr = std::iter::once(Cons::Value(ConsValue::symbol(name)))
+        .chain(args)
+        .chain([force_rest])
+        .collect();
+    Cons::Value(ConsValue::Promise(Promise::delayed(expr, env)))
+}
+
+pub fn car(args: &[Cons]) -> Result<Cons, EvaluatorError> {
+    arity("stream-car", args, 1, 1)?;
+    Ok(stream_pair("stream-car", &args[0])?.0)
+}
+
+pub fn cdr(args: &[Cons]) -> Result<Cons, EvaluatorError> {
+    arity("stream-cdr", args, 1, 1)?;
+    force_value(&stream_pair("stream-cdr", &args[0])?.1)
+}
+
+/// `(stream-take n stream)`: a stream of the first `n` elements of `stream`,
+/// or all of them if it is shorter.
+pub fn take(args: &[Cons], stg: &LexicalVarStorage) -> Result<Cons, EvaluatorError> {
+    arity("stream-take", args, 2, 2)?;
+    let n = index_arg("stream-take", &args[0])?;
+    let (first, rest) = match stream_arg("stream-take", &args[1])? {
+        Some(pair) if n > 0 => pair,
+        _ => return Ok(Cons::Value(ConsValue::NIL)),
+    };
+    // The last element taken ends the stream without forcing what follows.
+    let rest = if n == 1 {
+        Cons::Value(ConsValue::Promise(Promise::forced(Cons::Value(
+            ConsValue::NIL,
+        ))))
+    } else {
+        lazy_call(
+            "stream-take",
+            vec![Cons::Value(ConsValue::Int(n as i64 - 1))],
+            rest,
+            stg.detached(),
+        )
+    };
+    Ok(Cons::Cell(Arc::new(first), Arc::new(rest)))
+}
+
+/// `(stream->list stream [n])`: a list of the elements of `stream`, or of at
+/// most its first `n`. Without `n` the stream must be finite.
+pub fn to_list(args: &[Cons]) -> Result<Cons, EvaluatorError> {
+    arity("stream->list", args, 1, 2)?;
+    let limit = match args.get(1) {
+        Some(n) => Some(index_arg("stream->list", n)?),
+        None => None,
+    };
+    let mut elems = Vec::new();
+    // The rest is only forced once another element is wanted.
+    let mut stream = args[0].clone();
+    while limit != Some(elems.len()) {
+        let Some((first, rest)) = stream_arg("stream->list", &force_value(&stream)?)? else {
+            break;
+        };
+        elems.push(first);
+        stream = rest;
+    }
+    Ok(elems.into_iter().collect())
+}
+
+/// `(stream-map proc stream)`: only the first element is mapped straight
+/// away; the rest are mapped as the result is forced.
+pub fn map(args: &[Cons], stg: &mut LexicalVarStorage) -> Result<Cons, EvaluatorError> {
+    arity("stream-map", args, 2, 2)?;
+    let proc = procedure_arg("stream-map", &args[0])?;
+    let Some((first, rest)) = stream_arg("stream-map", &args[1])? else {
+        return Ok(Cons::Value(ConsValue::NIL));
+    };
+    let first = call(&proc, vec![first], stg)?;
+    let proc = Cons::Quoted(Arc::new(args[0].clone()));
+    Ok(Cons::Cell(
+        Arc::new(first),
+        Arc::new(lazy_call("stream-map", vec![proc], rest, stg.fork())),
+    ))
+}
+
+/// `(stream-filter pred stream)`: forces the stream up to its first element
+/// that satisfies pred.
+pub fn filter(args: &[Cons], stg: &mut LexicalVarStorage) -> Result<Cons, EvaluatorError> {
+    arity("stream-filter", args, 2, 2)?;
+    let pred = procedure_arg("stream-filter", &args[0])?;
+    let mut s = args[1].clone();
+    while let Some((first, rest)) = stream_arg("stream-filter", &s)? {
+        if call(&pred, vec![first.clone()], stg)? != Cons::Value(ConsValue::Boolean(false)) {
+            let pred = Cons::Quoted(Arc::new(args[0].clone()));
+            return Ok(Cons::Cell(
+                Arc::new(first),
+                Arc::new(lazy_call("stream-filter", vec![pred], rest, stg.fork())),
+            ));
+        }
+        s = force_value(&rest)?;
+    }
+    Ok(Cons::Value(ConsValue::NIL))
+}
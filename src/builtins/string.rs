@@ -0,0 +1,292 @@
+use std::cmp::Ordering;
+
+use num_bigint::BigInt;
+use num_rational::BigRational;
+use num_traits::{Signed, Zero};
+
+use crate::{
+    errors::EvaluatorError,
+    types::{Cons, ConsValue},
+};
+
+use super::{arity, index_arg, int_arg, invalid_argument, order, string_arg};
+
+fn string(s: String) -> Cons {
+    Cons::Value(ConsValue::String(s))
+}
+
+pub fn append(args: &[Cons]) -> Result<Cons, EvaluatorError> {
+    let mut result = String::new();
+    for arg in args {
+        result.push_str(string_arg("string-append", arg)?);
+    }
+    Ok(string(result))
+}
+
+/// Lengths and indices count Unicode scalar values, not bytes.
+pub fn length(args: &[Cons]) -> Result<Cons, EvaluatorError> {
+    arity("string-length", args, 1, 1)?;
+    let s = string_arg("string-length", &args[0])?;
+    Ok(Cons::Value(ConsValue::Int(s.chars().count() as i64)))
+}
+
+pub fn substring(args: &[Cons]) -> Result<Cons, EvaluatorError> {
+    arity("substring", args, 2, 3)?;
+    let s = string_arg("substring", &args[0])?;
+    let len = s.chars().count();
+    let start = index_arg("substring", &args[1])?;
+    let end = match args.get(2) {
+        Some(end) => index_arg("substring", end)?,
+        None => len,
+    };
+    if start > end || end > len {
+        return Err(EvaluatorError::InvalidArgument(format!(
+            "Range {}..{} out of bounds for string of length {}",
+            start, end, len
+        )));
+    }
+    Ok(string(s.chars().skip(start).take(end - start).collect()))
+}
+
+/// Splits on `sep`, or on runs of whitespace when no separator is given.
+pub fn split(args: &[Cons]) -> Result<Cons, EvaluatorError> {
+    arity("string-split", args, 1, 2)?;
+    let s = string_arg("string-split", &args[0])?;
+    let parts: Vec<&str> = match args.get(1) {
+        Some(sep) => {
+            let sep = string_arg("string-split", sep)?;
+            if sep.is_empty() {
+                return Err(EvaluatorError::InvalidArgument(
+                    "Empty separator for string-split".to_string(),
+                ));
+            }
+            s.split(sep).collect()
+        }
+        None => s.split_whitespace().collect(),
+    };
+    Ok(parts.into_iter().map(|p| string(p.to_string())).collect())
+}
+
+/// Joins a list of strings with `sep`, which defaults to a single space.
+pub fn join(args: &[Cons]) -> Result<Cons, EvaluatorError> {
+    arity("string-join", args, 1, 2)?;
+    let sep = match args.get(1) {
+        Some(sep) => string_arg("string-join", sep)?,
+        None => " ",
+    };
+    let parts = args[0]
+        .clone()
+        .into_iter()
+        .map(|part| string_arg("string-join", &part).map(str::to_string))
+        .collect::<Result<Vec<String>, EvaluatorError>>()?;
+    Ok(string(parts.join(sep)))
+}
+
+pub fn trim(args: &[Cons]) -> Result<Cons, EvaluatorError> {
+    arity("string-trim", args, 1, 1)?;
+    Ok(string(
+        string_arg("string-trim", &args[0])?.trim().to_string(),
+    ))
+}
+
+pub fn upcase(args: &[Cons]) -> Result<Cons, EvaluatorError> {
+    arity("string-upcase", args, 1, 1)?;
+    Ok(string(
+        string_arg("string-upcase", &args[0])?.to_uppercase(),
+    ))
+}
+
+pub fn downcase(args: &[Cons]) -> Result<Cons, EvaluatorError> {
+    arity("string-downcase", args, 1, 1)?;
+    Ok(string(
+        string_arg("string-downcase", &args[0])?.to_lowercase(),
+    ))
+}
+
+/// Returns the character index of the first occurrence of the needle, or #f.
+pub fn contains(args: &[Cons]) -> Result<Cons, EvaluatorError> {
+    arity("string-contains", args, 2, 2)?;
+    let haystack = string_arg("string-contains", &args[0])?;
+    let needle = string_arg("string-contains", &args[1])?;
+    Ok(match haystack.find(needle) {
+        Some(byte_index) => {
+            Cons::Value(ConsValue::Int(haystack[..byte_index].chars().count() as i64))
+        }
+        None => Cons::Value(ConsValue::Boolean(false)),
+    })
+}
+
+pub fn replace(args: &[Cons]) -> Result<Cons, EvaluatorError> {
+    arity("string-replace", args, 3, 3)?;
+    let s = string_arg("string-replace", &args[0])?;
+    let from = string_arg("string-replace", &args[1])?;
+    let to = string_arg("string-replace", &args[2])?;
+    if from.is_empty() {
+        return Err(EvaluatorError::InvalidArgument(
+            "Empty pattern for string-replace".to_string(),
+        ));
+    }
+    Ok(string(s.replace(from, to)))
+}
+
+/// Returns the characters of a string as a list.
+pub fn to_list(args: &[Cons]) -> Result<Cons, EvaluatorError> {
+    arity("string->list", args, 1, 1)?;
+    Ok(string_arg("string->list", &args[0])?
+        .chars()
+        .map(|c| Cons::Value(ConsValue::Char(c)))
+        .collect())
+}
+
+/// Joins a list of characters; strings are also accepted and appended whole.
+pub fn from_list(args: &[Cons]) -> Result<Cons, EvaluatorError> {
+    arity("list->string", args, 1, 1)?;
+    let mut result = String::new();
+    for c in args[0].clone() {
+        match c {
+            Cons::Value(ConsValue::Char(c)) => result.push(c),
+            _ => result.push_str(string_arg("list->string", &c)?),
+        }
+    }
+    Ok(string(result))
+}
+
+pub fn char_at(args: &[Cons]) -> Result<Cons, EvaluatorError> {
+    arity("string-ref", args, 2, 2)?;
+    let s = string_arg("string-ref", &args[0])?;
+    let index = index_arg("string-ref", &args[1])?;
+    s.chars()
+        .nth(index)
+        .map(|c| Cons::Value(ConsValue::Char(c)))
+        .ok_or_else(|| {
+            EvaluatorError::InvalidArgument(format!(
+                "Index {} out of range for string of length {}",
+                index,
+                s.chars().count()
+            ))
+        })
+}
+
+fn radix_arg(name: &str, args: &[Cons], index: usize) -> Result<u32, EvaluatorError> {
+    match args.get(index) {
+        None => Ok(10),
+        Some(radix) => match int_arg(name, radix)? {
+            r @ 2..=36 => Ok(r as u32),
+            r => Err(EvaluatorError::InvalidArgument(format!(
+                "Radix {} out of range for {}",
+                r, name
+            ))),
+        },
+    }
+}
+
+/// Parses an optionally signed run of digits in `radix`. `BigInt` itself
+/// also accepts `_` separators, which are not part of a numeral.
+fn parse_integer(s: &str, radix: u32) -> Option<BigInt> {
+    let digits = s.strip_prefix(['+', '-']).unwrap_or(s);
+    if digits.is_empty() || !digits.chars().all(|c| c.is_digit(radix)) {
+        return None;
+    }
+    BigInt::parse_bytes(s.as_bytes(), radix)
+}
+
+/// Parses an integer in the given radix, or a decimal float. Returns #f for
+/// anything that is not a number.
+pub fn to_number(args: &[Cons]) -> Result<Cons, EvaluatorError> {
+    arity("string->number", args, 1, 2)?;
+    let s = string_arg("string->number", &args[0])?.trim();
+    let radix = radix_arg("string->number", args, 1)?;
+    if let Some(i) = parse_integer(s, radix) {
+        return Ok(Cons::Value(ConsValue::integer(i)));
+    }
+    if let Some((numer, denom)) = s.split_once('/') {
+        return Ok(
+            match (parse_integer(numer, radix), parse_integer(denom, radix)) {
+                (Some(numer), Some(denom)) if !denom.is_zero() && !denom.is_negative() => {
+                    Cons::Value(ConsValue::rational(BigRational::new(numer, denom)))
+                }
+                _ => Cons::Value(ConsValue::Boolean(false)),
+            },
+        );
+    }
+    // Rust also accepts names like "inf" and "NaN", which are not numerals.
+    let is_decimal = s.chars().any(|c| c.is_ascii_digit())
+        && s.chars()
+            .all(|c| c.is_ascii_digit() || matches!(c, '+' | '-' | '.' | 'e' | 'E'));
+    match s.parse::<f64>() {
+        Ok(f) if radix == 10 && is_decimal => Ok(Cons::Value(ConsValue::Float(f))),
+        _ => Ok(Cons::Value(ConsValue::Boolean(false))),
+    }
+}
+
+fn format_radix(i: i64, radix: u32) -> String {
+    let mut n = i.unsigned_abs();
+    let mut digits = vec![];
+    loop {
+        digits.push(std::char::from_digit((n % radix as u64) as u32, radix).unwrap());
+        n /= radix as u64;
+        if n == 0 {
+            break;
+        }
+    }
+    if i < 0 {
+        digits.push('-');
+    }
+    digits.iter().rev().collect()
+}
+
+pub fn from_number(args: &[Cons]) -> Result<Cons, EvaluatorError> {
+    arity("number->string", args, 1, 2)?;
+    let radix = radix_arg("number->string", args, 1)?;
+    match &args[0] {
+        Cons::Value(ConsValue::Int(i)) => Ok(string(format_radix(*i, radix))),
+        Cons::Value(ConsValue::BigInt(i)) => Ok(string(i.to_str_radix(radix))),
+        Cons::Value(ConsValue::Rational(r)) => Ok(string(format!(
+            "{}/{}",
+            r.numer().to_str_radix(radix),
+            r.denom().to_str_radix(radix)
+        ))),
+        Cons::Value(ConsValue::Float(_)) if radix == 10 => Ok(string(args[0].to_string())),
+        Cons::Value(ConsValue::Float(_)) => Err(EvaluatorError::InvalidArgument(
+            "number->string only supports radix 10 for floats".to_string(),
+        )),
+        _ => Err(invalid_argument("number->string")),
+    }
+}
+
+pub fn to_symbol(args: &[Cons]) -> Result<Cons, EvaluatorError> {
+    arity("string->symbol", args, 1, 1)?;
+    let s = string_arg("string->symbol", &args[0])?;
+    Ok(Cons::Value(ConsValue::symbol(s)))
+}
+
+pub fn from_symbol(args: &[Cons]) -> Result<Cons, EvaluatorError> {
+    arity("symbol->string", args, 1, 1)?;
+    match &args[0] {
+        Cons::Value(ConsValue::Symbol(s)) => Ok(string(s.to_string())),
+        _ => Err(invalid_argument("symbol->string")),
+    }
+}
+
+/// Checks that every adjacent pair of string arguments is ordered as one of
+/// `accepted`.
+pub fn compare(name: &str, args: &[Cons], accepted: &[Ordering]) -> Result<Cons, EvaluatorError> {
+    let strings = args
+        .iter()
+        .map(|arg| string_arg(name, arg).map(str::to_string))
+        .collect::<Result<Vec<String>, EvaluatorError>>()?;
+    Ok(order::chain(&strings, accepted))
+}
+
+/// Like `compare`, ignoring case.
+pub fn compare_ci(
+    name: &str,
+    args: &[Cons],
+    accepted: &[Ordering],
+) -> Result<Cons, EvaluatorError> {
+    let strings = args
+        .iter()
+        .map(|arg| string_arg(name, arg).map(str::to_lowercase))
+        .collect::<Result<Vec<String>, EvaluatorError>>()?;
+    Ok(order::chain(&strings, accepted))
+}
@@ -0,0 +1,34 @@
+use crate::{
+    errors::EvaluatorError,
+    types::{symbol::Symbol, Cons, ConsValue},
+};
+
+use super::{arity, invalid_argument, string_arg};
+
+/// `(gensym [prefix])`: a fresh uninterned symbol, named by `prefix` (a
+/// string or symbol, "g" by default) and a counter.
+pub fn gensym(args: &[Cons]) -> Result<Cons, EvaluatorError> {
+    arity("gensym", args, 0, 1)?;
+    let prefix = match args.first() {
+        None => "g",
+        Some(Cons::Value(ConsValue::String(s))) => s.as_str(),
+        Some(Cons::Value(ConsValue::Symbol(s))) => s.as_str(),
+        Some(_) => return Err(invalid_argument("gensym")),
+    };
+    Ok(Cons::Value(ConsValue::Symbol(Symbol::gensym(prefix))))
+}
+
+pub fn uninterned(args: &[Cons]) -> Result<Cons, EvaluatorError> {
+    arity("string->uninterned-symbol", args, 1, 1)?;
+    Ok(Cons::Value(ConsValue::Symbol(Symbol::uninterned(
+        string_arg("string->uninterned-symbol", &args[0])?,
+    ))))
+}
+
+pub fn is_interned(args: &[Cons]) -> Result<Cons, EvaluatorError> {
+    arity("symbol-interned?", args, 1, 1)?;
+    match &args[0] {
+        Cons::Value(ConsValue::Symbol(s)) => Ok(Cons::Value(ConsValue::Boolean(s.is_interned()))),
+        _ => Err(invalid_argument("symbol-interned?")),
+    }
+}
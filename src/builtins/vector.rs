@@ -0,0 +1,118 @@
+use crate::{
+    errors::EvaluatorError,
+    types::{scope::LexicalVarStorage, vector::Vector, Cons, ConsValue},
+};
+
+use super::{arity, call, index_arg, invalid_argument, procedure_arg};
+
+fn vector(elems: Vec<Cons>) -> Cons {
+    Cons::Value(ConsValue::Vector(Vector::new(elems)))
+}
+
+pub(crate) fn vector_arg<'a>(name: &str, arg: &'a Cons) -> Result<&'a Vector, EvaluatorError> {
+    match arg {
+        Cons::Value(ConsValue::Vector(v)) => Ok(v),
+        _ => Err(invalid_argument(name)),
+    }
+}
+
+fn out_of_range(name: &str, index: usize, len: usize) -> EvaluatorError {
+    EvaluatorError::InvalidArgument(format!(
+        "Index {} out of range for {} on a vector of length {}",
+        index, name, len
+    ))
+}
+
+/// `(make-vector n [fill])`: elements default to the empty list.
+pub fn make(args: &[Cons]) -> Result<Cons, EvaluatorError> {
+    arity("make-vector", args, 1, 2)?;
+    let len = index_arg("make-vector", &args[0])?;
+    let fill = args.get(1).cloned().unwrap_or(Cons::Value(ConsValue::NIL));
+    Ok(vector(vec![fill; len]))
+}
+
+pub fn from_args(args: &[Cons]) -> Result<Cons, EvaluatorError> {
+    Ok(vector(args.to_vec()))
+}
+
+pub fn get(args: &[Cons]) -> Result<Cons, EvaluatorError> {
+    arity("vector-ref", args, 2, 2)?;
+    let v = vector_arg("vector-ref", &args[0])?;
+    let index = index_arg("vector-ref", &args[1])?;
+    v.get(index)
+        .ok_or_else(|| out_of_range("vector-ref", index, v.len()))
+}
+
+pub fn set(args: &[Cons]) -> Result<Cons, EvaluatorError> {
+    arity("vector-set!", args, 3, 3)?;
+    let v = vector_arg("vector-set!", &args[0])?;
+    let index = index_arg("vector-set!", &args[1])?;
+    if !v.set(index, args[2].clone()) {
+        return Err(out_of_range("vector-set!", index, v.len()));
+    }
+    Ok(Cons::Value(ConsValue::NIL))
+}
+
+pub fn length(args: &[Cons]) -> Result<Cons, EvaluatorError> {
+    arity("vector-length", args, 1, 1)?;
+    let len = vector_arg("vector-length", &args[0])?.len();
+    Ok(Cons::Value(ConsValue::Int(len as i64)))
+}
+
+pub fn fill(args: &[Cons]) -> Result<Cons, EvaluatorError> {
+    arity("vector-fill!", args, 2, 2)?;
+    vector_arg("vector-fill!", &args[0])?.fill(&args[1]);
+    Ok(Cons::Value(ConsValue::NIL))
+}
+
+pub fn to_list(args: &[Cons]) -> Result<Cons, EvaluatorError> {
+    arity("vector->list", args, 1, 1)?;
+    Ok(vector_arg("vector->list", &args[0])?
+        .to_vec()
+        .into_iter()
+        .collect())
+}
+
+pub fn from_list(args: &[Cons]) -> Result<Cons, EvaluatorError> {
+    arity("list->vector", args, 1, 1)?;
+    match &args[0] {
+        Cons::Cell(..) | Cons::Value(ConsValue::NIL) => {
+            Ok(vector(args[0].clone().into_iter().collect()))
+        }
+        _ => Err(invalid_argument("list->vector")),
+    }
+}
+
+/// `(subvector vec start [end])`: a new vector with the elements from `start`
+/// up to `end`, like `substring`.
+pub fn subvector(args: &[Cons]) -> Result<Cons, EvaluatorError> {
+    arity("subvector", args, 2, 3)?;
+    let elems = vector_arg("subvector", &args[0])?.to_vec();
+    let start = index_arg("subvector", &args[1])?;
+    let end = match args.get(2) {
+        Some(end) => index_arg("subvector", end)?,
+        None => elems.len(),
+    };
+    if start > end || end > elems.len() {
+        return Err(EvaluatorError::InvalidArgument(format!(
+            "Range {}..{} out of bounds for vector of length {}",
+            start,
+            end,
+            elems.len()
+        )));
+    }
+    Ok(vector(elems[start..end].to_vec()))
+}
+
+/// `(vector-map proc vec)`: a new vector of the results of calling proc on
+/// each element.
+pub fn map(args: &[Cons], stg: &mut LexicalVarStorage) -> Result<Cons, EvaluatorError> {
+    arity("vector-map", args, 2, 2)?;
+    let proc = procedure_arg("vector-map", &args[0])?;
+    let results = vector_arg("vector-map", &args[1])?
+        .to_vec()
+        .into_iter()
+        .map(|elem| call(&proc, vec![elem], stg))
+        .collect::<Result<Vec<Cons>, EvaluatorError>>()?;
+    Ok(vector(results))
+}
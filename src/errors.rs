@@ -1,3 +1,5 @@
+use crate::types::{continuation::Continuation, Cons};
+
 #[derive(Clone, Debug, PartialEq)]
 pub struct ConsCellCreateError(pub &'static str);
 
@@ -15,4 +17,16 @@ pub enum EvaluatorError {
     BadFunctionDefinition(String),
     InvalidArgument(String),
     ReturnedNonCons(String),
+    LoadError(String),
+    /// A file system builtin failed, or file system access is disabled.
+    IoError(String),
+    ModuleError(String),
+    DivisionByZero(String),
+    /// Not a failure: raised by `(exit [code])` to unwind evaluation back to
+    /// the embedder, which decides what ending the program means.
+    Exit(i32),
+    /// Not a failure either: raised by calling a continuation to unwind
+    /// evaluation back to the `call/cc` that captured it, which returns the
+    /// value.
+    Escape(Continuation, Cons),
 }
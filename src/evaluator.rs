@@ -1,34 +1,42 @@
+use std::sync::Arc;
+
 use crate::{
     errors::EvaluatorError,
     types::{
-        function::{BuiltinFunction, BuiltinMacro, MacroReturn, UserFunction},
+        bytevector::Bytevector, continuation::Continuation, procedure::Procedure, vector::Vector,
+        Cons, ConsValue,
+    },
+    types::{
+        function::{
+            BuiltinFunction, BuiltinMacro, MacroReturn, UserFunction, BUILTINS_CONST_BY_SYMBOL,
+        },
         scope::LexicalVarStorage,
     },
-    types::{Cons, ConsValue},
 };
 
 pub fn lisp_eval(expr: &Cons, stg: &mut LexicalVarStorage) -> Result<Cons, EvaluatorError> {
-    match lisp_eval_int(expr, stg) {
-        Ok(EvalReturnType::CONS(c)) => Ok(c),
-        Ok(EvalReturnType::FUNC(f)) => Err(EvaluatorError::ReturnedNonCons(format!(
-            "Function {:?} returned non-cons value",
-            f
-        ))),
-        Ok(EvalReturnType::MACRO(m)) => Err(EvaluatorError::ReturnedNonCons(format!(
+    into_cons(lisp_eval_int(expr, stg)?)
+}
+
+pub(crate) fn into_cons(result: EvalReturnType) -> Result<Cons, EvaluatorError> {
+    match result {
+        EvalReturnType::CONS(c) => Ok(c),
+        EvalReturnType::FUNC(f) => Ok(Cons::Value(ConsValue::Procedure(Procedure::Builtin(f)))),
+        EvalReturnType::MACRO(m) => Err(EvaluatorError::ReturnedNonCons(format!(
             "Macro {:?} returned non-cons value",
             m
         ))),
-        Ok(EvalReturnType::USER(u)) => Ok(u.to_cons()),
-        Err(e) => Err(e),
+        EvalReturnType::USER(u) => Ok(Cons::Value(ConsValue::Procedure(Procedure::User(u)))),
     }
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
+#[allow(clippy::upper_case_acronyms, clippy::large_enum_variant)]
 pub(crate) enum EvalReturnType {
     CONS(Cons),
     FUNC(BuiltinFunction),
     MACRO(BuiltinMacro),
-    USER(UserFunction),
+    USER(Arc<UserFunction>),
 }
 
 pub(crate) fn lisp_eval_int(
@@ -52,10 +60,16 @@ pub(crate) fn lisp_eval_int(
                 }
                 // Then look for user-defined functions.
                 else if let Some(user_func) = stg.get_func(s) {
-                    Ok(EvalReturnType::USER(user_func.clone()))
+                    Ok(EvalReturnType::USER(Arc::new(user_func.clone())))
                 }
                 // Then look for variables.
-                else {
+                else if let Some(value) = stg.get(s) {
+                    Ok(EvalReturnType::CONS(value.clone()))
+                }
+                // Finally fall back to builtin constants.
+                else if let Some(value) = BUILTINS_CONST_BY_SYMBOL.get(s) {
+                    Ok(EvalReturnType::CONS(Cons::Value(value.clone())))
+                } else {
                     Ok(EvalReturnType::CONS(stg[s].clone()))
                 }
             }
@@ -63,28 +77,79 @@ pub(crate) fn lisp_eval_int(
                 s.clone(),
             )))),
             ConsValue::Boolean(b) => Ok(EvalReturnType::CONS(Cons::Value(ConsValue::Boolean(*b)))),
+            ConsValue::Char(c) => Ok(EvalReturnType::CONS(Cons::Value(ConsValue::Char(*c)))),
             ConsValue::Int(i) => Ok(EvalReturnType::CONS(Cons::Value(ConsValue::Int(*i)))),
+            ConsValue::BigInt(i) => Ok(EvalReturnType::CONS(Cons::Value(ConsValue::BigInt(
+                i.clone(),
+            )))),
+            ConsValue::Rational(r) => Ok(EvalReturnType::CONS(Cons::Value(ConsValue::Rational(
+                r.clone(),
+            )))),
             ConsValue::Float(fl) => Ok(EvalReturnType::CONS(Cons::Value(ConsValue::Float(*fl)))),
             ConsValue::Comment(s) => Ok(EvalReturnType::CONS(Cons::Value(ConsValue::Comment(
                 s.clone(),
             )))),
+            ConsValue::Port(p) => Ok(EvalReturnType::CONS(Cons::Value(ConsValue::Port(
+                p.clone(),
+            )))),
+            ConsValue::HashTable(t) => Ok(EvalReturnType::CONS(Cons::Value(ConsValue::HashTable(
+                t.clone(),
+            )))),
+            // Literals are part of the program, so each evaluation hands out a
+            // fresh copy that can be mutated without changing the source.
+            ConsValue::Vector(v) => Ok(EvalReturnType::CONS(Cons::Value(ConsValue::Vector(
+                Vector::new(v.to_vec()),
+            )))),
+            ConsValue::Bytevector(b) => Ok(EvalReturnType::CONS(Cons::Value(
+                ConsValue::Bytevector(Bytevector::new(b.to_vec())),
+            ))),
+            ConsValue::Promise(p) => Ok(EvalReturnType::CONS(Cons::Value(ConsValue::Promise(
+                p.clone(),
+            )))),
+            ConsValue::Continuation(k) => Ok(EvalReturnType::CONS(Cons::Value(
+                ConsValue::Continuation(k.clone()),
+            ))),
+            ConsValue::Generator(g) => Ok(EvalReturnType::CONS(Cons::Value(ConsValue::Generator(
+                g.clone(),
+            )))),
+            ConsValue::Yielder(y) => Ok(EvalReturnType::CONS(Cons::Value(ConsValue::Yielder(
+                y.clone(),
+            )))),
+            ConsValue::Parameter(p) => Ok(EvalReturnType::CONS(Cons::Value(ConsValue::Parameter(
+                p.clone(),
+            )))),
+            ConsValue::Procedure(p) => Ok(EvalReturnType::CONS(Cons::Value(ConsValue::Procedure(
+                p.clone(),
+            )))),
+            ConsValue::Date(d) => Ok(EvalReturnType::CONS(Cons::Value(ConsValue::Date(*d)))),
+            ConsValue::Duration(d) => {
+                Ok(EvalReturnType::CONS(Cons::Value(ConsValue::Duration(*d))))
+            }
         },
         Cons::Cell(car, _cdr) => match lisp_eval_int(car, stg)? {
+            // Procedures, continuations, generators and parameters are
+            // values that can be called.
+            proc @ EvalReturnType::CONS(Cons::Value(
+                ConsValue::Procedure(_)
+                | ConsValue::Continuation(_)
+                | ConsValue::Generator(_)
+                | ConsValue::Yielder(_)
+                | ConsValue::Parameter(_),
+            )) => {
+                let evaled_args = eval_args(expr, stg)?;
+                call_procedure(proc, evaled_args, stg)
+            }
             EvalReturnType::CONS(c) => Ok(EvalReturnType::CONS(c)),
             EvalReturnType::MACRO(m) => match m.call(&expr.cdr(), stg)? {
                 MacroReturn::Value(c) => Ok(EvalReturnType::CONS(c)),
-                MacroReturn::Function(f) => Ok(EvalReturnType::USER(f)),
+                MacroReturn::Function(f) => Ok(EvalReturnType::USER(Arc::new(f))),
                 MacroReturn::None => Ok(EvalReturnType::CONS(Cons::Value(ConsValue::NIL))),
             },
             EvalReturnType::FUNC(f) => {
                 // All builtin functions eval their args before they start.
-                let evaled_args: Result<Vec<Cons>, EvaluatorError> = expr
-                    .cdr()
-                    .into_iter()
-                    .map(|c| lisp_eval(&c, &mut stg.fork()))
-                    .collect();
+                let evaled_args = eval_args(expr, stg)?;
                 log::debug!("Evaluating function: {:?}", f);
-                Ok(EvalReturnType::CONS(f.call(evaled_args?)?))
+                Ok(EvalReturnType::CONS(f.call(evaled_args, stg)?))
             }
             EvalReturnType::USER(f) => {
                 log::debug!("Calling user function: {:?}", f);
@@ -94,3 +159,91 @@ pub(crate) fn lisp_eval_int(
         },
     }
 }
+
+/// Evaluates the arguments of the call `expr`, each in a fork of `stg`. Kept
+/// out of `lisp_eval_int` so that its frame, which every level of nesting
+/// pays for, does not hold the forked storages.
+fn eval_args(expr: &Cons, stg: &LexicalVarStorage) -> Result<Vec<Cons>, EvaluatorError> {
+    expr.cdr()
+        .into_iter()
+        .map(|c| lisp_eval(&c, &mut stg.fork()))
+        .collect()
+}
+
+/// Calls a continuation, unwinding to the `call/cc` that captured it.
+fn escape(k: &Continuation, args: Vec<Cons>) -> Result<EvalReturnType, EvaluatorError> {
+    if !k.is_active() {
+        return Err(EvaluatorError::UncallableType(
+            "Continuations can only be called before their call/cc returns".to_string(),
+        ));
+    }
+    let value = match <[Cons; 1]>::try_from(args) {
+        Ok([value]) => value,
+        Err(args) if args.is_empty() => Cons::Value(ConsValue::NIL),
+        Err(args) => {
+            return Err(EvaluatorError::InvalidArgument(format!(
+                "Wrong number of arguments for a continuation: {}",
+                args.len()
+            )));
+        }
+    };
+    Err(EvaluatorError::Escape(k.clone(), value))
+}
+
+/// Calls a procedure with arguments that are already evaluated.
+pub(crate) fn call_procedure(
+    proc: EvalReturnType,
+    args: Vec<Cons>,
+    stg: &mut LexicalVarStorage,
+) -> Result<EvalReturnType, EvaluatorError> {
+    match proc {
+        EvalReturnType::FUNC(f)
+        | EvalReturnType::CONS(Cons::Value(ConsValue::Procedure(Procedure::Builtin(f)))) => {
+            Ok(EvalReturnType::CONS(f.call(args, stg)?))
+        }
+        // User functions evaluate their arguments, so quote the values to
+        // pass them through unchanged.
+        EvalReturnType::USER(f)
+        | EvalReturnType::CONS(Cons::Value(ConsValue::Procedure(Procedure::User(f)))) => f.call(
+            args.into_iter()
+                .map(|c| Cons::Quoted(Arc::new(c)))
+                .collect(),
+            &mut stg.fork(),
+        ),
+        EvalReturnType::MACRO(m) => Err(EvaluatorError::UncallableType(format!(
+            "Macro {:?} cannot be applied",
+            m
+        ))),
+        EvalReturnType::CONS(Cons::Value(ConsValue::Continuation(k))) => escape(&k, args),
+        EvalReturnType::CONS(Cons::Value(ConsValue::Parameter(p))) => match args.as_slice() {
+            [] => Ok(EvalReturnType::CONS(stg.parameter(&p))),
+            _ => Err(EvaluatorError::InvalidArgument(format!(
+                "Wrong number of arguments for a parameter: {}",
+                args.len()
+            ))),
+        },
+        EvalReturnType::CONS(Cons::Value(ConsValue::Generator(g))) => match args.as_slice() {
+            [] => Ok(EvalReturnType::CONS(g.next()?)),
+            _ => Err(EvaluatorError::InvalidArgument(format!(
+                "Wrong number of arguments for a generator: {}",
+                args.len()
+            ))),
+        },
+        EvalReturnType::CONS(Cons::Value(ConsValue::Yielder(y))) => {
+            match <[Cons; 1]>::try_from(args) {
+                Ok([value]) => {
+                    y.yield_value(value)?;
+                    Ok(EvalReturnType::CONS(Cons::Value(ConsValue::NIL)))
+                }
+                Err(args) => Err(EvaluatorError::InvalidArgument(format!(
+                    "Wrong number of arguments for yield: {}",
+                    args.len()
+                ))),
+            }
+        }
+        EvalReturnType::CONS(c) => Err(EvaluatorError::NotAFunction(format!(
+            "{} is not a procedure",
+            c
+        ))),
+    }
+}
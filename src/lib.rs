@@ -1,11 +1,58 @@
+pub mod builtins;
 pub mod errors;
 pub mod evaluator;
+pub mod loader;
 pub mod logger;
 pub mod types;
 
 use lalrpop_util::lalrpop_mod;
 lalrpop_mod!(pub slyther);
 
+/// Helpers shared by the test modules below.
+#[cfg(test)]
+mod test_util {
+    use crate::{
+        errors::EvaluatorError,
+        loader::eval_source,
+        types::{Cons, ConsValue, scope::LexicalVarStorage},
+    };
+
+    /// Evaluates `src` in a fresh storage.
+    pub fn eval(src: &str) -> Result<Cons, EvaluatorError> {
+        eval_source(src, &mut LexicalVarStorage::new())
+    }
+
+    /// Evaluates `src` in a fresh storage and prints the result.
+    pub fn eval_string(src: &str) -> Result<String, EvaluatorError> {
+        eval(src).map(|c| c.to_string())
+    }
+
+    /// Like `eval_string`, for results that are expected to succeed.
+    pub fn display(src: &str) -> String {
+        eval(src).unwrap().to_string()
+    }
+
+    pub fn int(i: i64) -> Result<Cons, EvaluatorError> {
+        Ok(Cons::Value(ConsValue::Int(i)))
+    }
+
+    pub fn float(f: f64) -> Result<Cons, EvaluatorError> {
+        Ok(Cons::Value(ConsValue::Float(f)))
+    }
+
+    pub fn boolean(b: bool) -> Result<Cons, EvaluatorError> {
+        Ok(Cons::Value(ConsValue::Boolean(b)))
+    }
+
+    pub fn character(c: char) -> Result<Cons, EvaluatorError> {
+        Ok(Cons::Value(ConsValue::Char(c)))
+    }
+
+    pub fn string(s: &str) -> Result<Cons, EvaluatorError> {
+        Ok(Cons::Value(ConsValue::String(s.to_string())))
+    }
+}
+
 #[cfg(test)]
 mod test_atom_opers {
     use std::sync::Arc;
@@ -13,15 +60,15 @@ mod test_atom_opers {
     #[test]
     fn test_nil() {
         let nil = crate::types::Cons::Value(crate::types::ConsValue::NIL);
-        assert_eq!(nil.is_nil(), true);
-        assert_eq!(nil.is_quoted(), false);
+        assert!(nil.is_nil());
+        assert!(!nil.is_quoted());
     }
 
     #[test]
     fn test_nil_false() {
         let nil = crate::types::Cons::Value(crate::types::ConsValue::Int(123));
-        assert_eq!(nil.is_nil(), false);
-        assert_eq!(nil.is_quoted(), false);
+        assert!(!nil.is_nil());
+        assert!(!nil.is_quoted());
     }
 
     #[test]
@@ -29,27 +76,27 @@ mod test_atom_opers {
         let nil = crate::types::Cons::Quoted(Arc::new(crate::types::Cons::Value(
             crate::types::ConsValue::NIL,
         )));
-        assert_eq!(nil.is_nil(), false);
-        assert_eq!(nil.is_quoted(), true);
+        assert!(!nil.is_nil());
+        assert!(nil.is_quoted());
     }
 
     #[test]
     fn test_is_quoted_false() {
         let nil = crate::types::Cons::Value(crate::types::ConsValue::Int(123));
-        assert_eq!(nil.is_nil(), false);
-        assert_eq!(nil.is_quoted(), false);
+        assert!(!nil.is_nil());
+        assert!(!nil.is_quoted());
     }
 
     #[test]
     fn test_is_nil_value() {
         let nil = crate::types::ConsValue::NIL;
-        assert_eq!(nil.is_nil(), true);
+        assert!(nil.is_nil());
     }
 
     #[test]
     fn test_is_not_nil_value() {
         let nil = crate::types::ConsValue::Int(123);
-        assert_eq!(nil.is_nil(), false);
+        assert!(!nil.is_nil());
     }
 }
 
@@ -117,7 +164,7 @@ mod test_parse_atom {
         assert_eq!(exprs.len(), 1);
         assert_eq!(
             exprs[0].as_ref(),
-            &crate::types::Cons::Value(crate::types::ConsValue::Symbol("abc".to_string()))
+            &crate::types::Cons::Value(crate::types::ConsValue::symbol("abc"))
         );
     }
 
@@ -155,8 +202,8 @@ mod test_parse_atom {
         let exprs = exprs.unwrap();
         assert_eq!(exprs.len(), 1);
         let list = exprs[0].as_ref();
-        assert_eq!(list.is_quoted(), false);
-        assert_eq!(list.is_nil(), false);
+        assert!(!list.is_quoted());
+        assert!(!list.is_nil());
         assert_eq!(
             list,
             &crate::types::Cons::from_iter(vec![
@@ -176,8 +223,8 @@ mod test_parse_atom {
         let exprs = exprs.unwrap();
         assert_eq!(exprs.len(), 1);
         let list = exprs[0].as_ref();
-        assert_eq!(list.is_quoted(), false);
-        assert_eq!(list.is_nil(), false);
+        assert!(!list.is_quoted());
+        assert!(!list.is_nil());
         assert_eq!(
             list,
             &crate::types::Cons::from_iter(vec![
@@ -220,7 +267,40 @@ mod test_parse_cons {
         assert_eq!(exprs.len(), 1);
         assert_eq!(
             exprs[0].as_ref(),
-            &crate::types::Cons::Value(crate::types::ConsValue::Int(123))
+            &crate::types::Cons::from_iter(vec![crate::types::Cons::Value(
+                crate::types::ConsValue::Int(123)
+            )])
+        );
+    }
+
+    #[test]
+    fn test_symbol_call_cons() {
+        let parser = crate::slyther::SExpressionsParser::new();
+        let exprs = parser.parse("(abc)");
+        assert!(exprs.is_ok());
+        let exprs = exprs.unwrap();
+        assert_eq!(exprs.len(), 1);
+        assert_eq!(
+            exprs[0].as_ref(),
+            &crate::types::Cons::from_iter(vec![crate::types::Cons::Value(
+                crate::types::ConsValue::symbol("abc")
+            )])
+        );
+    }
+
+    #[test]
+    fn test_nested_single_cons() {
+        let parser = crate::slyther::SExpressionsParser::new();
+        let exprs = parser.parse("((a b))");
+        assert!(exprs.is_ok());
+        let exprs = exprs.unwrap();
+        assert_eq!(exprs.len(), 1);
+        assert_eq!(
+            exprs[0].as_ref(),
+            &crate::types::Cons::from_iter(vec![crate::types::Cons::from_iter(vec![
+                crate::types::Cons::Value(crate::types::ConsValue::symbol("a")),
+                crate::types::Cons::Value(crate::types::ConsValue::symbol("b")),
+            ])])
         );
     }
 
@@ -252,9 +332,9 @@ mod test_parse_cons {
         assert_eq!(exprs.len(), 1);
         assert_eq!(
             exprs[0].as_ref(),
-            &crate::types::Cons::Quoted(Arc::new(crate::types::Cons::Value(
-                crate::types::ConsValue::Int(123)
-            )))
+            &crate::types::Cons::Quoted(Arc::new(crate::types::Cons::from_iter(vec![
+                crate::types::Cons::Value(crate::types::ConsValue::Int(123))
+            ])))
         );
     }
 
@@ -268,7 +348,9 @@ mod test_parse_cons {
         assert_eq!(
             exprs[0].as_ref(),
             &crate::types::Cons::Quoted(Arc::new(crate::types::Cons::Quoted(Arc::new(
-                crate::types::Cons::Value(crate::types::ConsValue::Int(123))
+                crate::types::Cons::from_iter(vec![crate::types::Cons::Value(
+                    crate::types::ConsValue::Int(123)
+                )])
             ))))
         );
     }
@@ -351,33 +433,61 @@ mod test_lexvar_stg {
     use crate::types::Cons;
     use crate::types::ConsValue;
     use crate::types::scope::LexicalVarStorage;
+    use crate::types::symbol::Symbol;
 
     #[test]
     fn test_lexvar_stg() {
         let mut stg = LexicalVarStorage::new();
-        assert_eq!(stg.get("foo"), None);
-        assert_eq!(stg.get("bar"), None);
-        stg.put("foo", Cons::Value(ConsValue::Int(123)));
-        assert_eq!(stg.get("foo"), Some(&Cons::Value(ConsValue::Int(123))));
-        assert_eq!(stg.get("bar"), None);
-        stg.put("bar", Cons::Value(ConsValue::Int(456)));
-        assert_eq!(stg.get("foo"), Some(&Cons::Value(ConsValue::Int(123))));
-        assert_eq!(stg.get("bar"), Some(&Cons::Value(ConsValue::Int(456))));
+        assert_eq!(stg.get(&Symbol::new("foo")), None);
+        assert_eq!(stg.get(&Symbol::new("bar")), None);
+        stg.put(&Symbol::new("foo"), Cons::Value(ConsValue::Int(123)));
+        assert_eq!(
+            stg.get(&Symbol::new("foo")),
+            Some(&Cons::Value(ConsValue::Int(123)))
+        );
+        assert_eq!(stg.get(&Symbol::new("bar")), None);
+        stg.put(&Symbol::new("bar"), Cons::Value(ConsValue::Int(456)));
+        assert_eq!(
+            stg.get(&Symbol::new("foo")),
+            Some(&Cons::Value(ConsValue::Int(123)))
+        );
+        assert_eq!(
+            stg.get(&Symbol::new("bar")),
+            Some(&Cons::Value(ConsValue::Int(456)))
+        );
     }
 
     #[test]
     fn test_lexvar_frk() {
         let mut stg = LexicalVarStorage::new();
-        stg.put("foo", Cons::Value(ConsValue::Int(123)));
-        stg.put("bar", Cons::Value(ConsValue::Int(456)));
+        stg.put(&Symbol::new("foo"), Cons::Value(ConsValue::Int(123)));
+        stg.put(&Symbol::new("bar"), Cons::Value(ConsValue::Int(456)));
         let mut stg2 = stg.fork();
-        assert_eq!(stg2.get("foo"), Some(&Cons::Value(ConsValue::Int(123))));
-        assert_eq!(stg2.get("bar"), Some(&Cons::Value(ConsValue::Int(456))));
-        stg2.put("foo", Cons::Value(ConsValue::Int(789)));
-        assert_eq!(stg2.get("foo"), Some(&Cons::Value(ConsValue::Int(789))));
-        assert_eq!(stg2.get("bar"), Some(&Cons::Value(ConsValue::Int(456))));
-        assert_eq!(stg.get("foo"), Some(&Cons::Value(ConsValue::Int(123))));
-        assert_eq!(stg.get("bar"), Some(&Cons::Value(ConsValue::Int(456))));
+        assert_eq!(
+            stg2.get(&Symbol::new("foo")),
+            Some(&Cons::Value(ConsValue::Int(123)))
+        );
+        assert_eq!(
+            stg2.get(&Symbol::new("bar")),
+            Some(&Cons::Value(ConsValue::Int(456)))
+        );
+        stg2.put(&Symbol::new("foo"), Cons::Value(ConsValue::Int(789)));
+        assert_eq!(
+            stg2.get(&Symbol::new("foo")),
+            Some(&Cons::Value(ConsValue::Int(789)))
+        );
+        assert_eq!(
+            stg2.get(&Symbol::new("bar")),
+            Some(&Cons::Value(ConsValue::Int(456)))
+        );
+        assert_eq!(
+            stg.get(&Symbol::new("foo")),
+            Some(&Cons::Value(ConsValue::Int(123)))
+        );
+        assert_eq!(
+            stg.get(&Symbol::new("bar")),
+            Some(&Cons::Value(ConsValue::Int(456)))
+        );
     }
 }
 
@@ -511,6 +621,8 @@ mod test_eval_lists {
 
 #[cfg(test)]
 mod test_func_built {
+    use num_rational::BigRational;
+
     use crate::{
         evaluator::lisp_eval,
         types::{Cons, ConsValue, scope::LexicalVarStorage},
@@ -520,7 +632,7 @@ mod test_func_built {
     fn test_func_add() {
         let mut stg = crate::types::scope::LexicalVarStorage::new();
         let expr = Cons::from_iter(vec![
-            Cons::Value(ConsValue::Symbol("+".to_string())),
+            Cons::Value(ConsValue::symbol("+")),
             Cons::Value(ConsValue::Int(123)),
             Cons::Value(ConsValue::Int(456)),
         ]);
@@ -533,7 +645,7 @@ mod test_func_built {
     #[test]
     fn test_func_add_no_arg() {
         let mut stg = LexicalVarStorage::new();
-        let expr = Cons::from_iter(vec![Cons::Value(ConsValue::Symbol("+".to_string()))]);
+        let expr = Cons::from_iter(vec![Cons::Value(ConsValue::symbol("+"))]);
         let result = lisp_eval(&expr, &mut stg);
         assert!(result.is_ok());
         let result = result.unwrap();
@@ -544,7 +656,7 @@ mod test_func_built {
     fn test_func_add_one_arg() {
         let mut stg = LexicalVarStorage::new();
         let expr = Cons::from_iter(vec![
-            Cons::Value(ConsValue::Symbol("+".to_string())),
+            Cons::Value(ConsValue::symbol("+")),
             Cons::Value(ConsValue::Int(123)),
         ]);
         let result = lisp_eval(&expr, &mut stg);
@@ -557,7 +669,7 @@ mod test_func_built {
     fn test_func_sub() {
         let mut stg = LexicalVarStorage::new();
         let expr = Cons::from_iter(vec![
-            Cons::Value(ConsValue::Symbol("-".to_string())),
+            Cons::Value(ConsValue::symbol("-")),
             Cons::Value(ConsValue::Int(123)),
             Cons::Value(ConsValue::Int(456)),
         ]);
@@ -570,7 +682,7 @@ mod test_func_built {
     #[test]
     fn test_func_sub_no_arg() {
         let mut stg = LexicalVarStorage::new();
-        let expr = Cons::from_iter(vec![Cons::Value(ConsValue::Symbol("-".to_string()))]);
+        let expr = Cons::from_iter(vec![Cons::Value(ConsValue::symbol("-"))]);
         let result = lisp_eval(&expr, &mut stg);
         assert!(result.is_ok());
         let result = result.unwrap();
@@ -581,7 +693,7 @@ mod test_func_built {
     fn test_func_sub_one_arg() {
         let mut stg = LexicalVarStorage::new();
         let expr = Cons::from_iter(vec![
-            Cons::Value(ConsValue::Symbol("-".to_string())),
+            Cons::Value(ConsValue::symbol("-")),
             Cons::Value(ConsValue::Int(123)),
         ]);
         let result = lisp_eval(&expr, &mut stg);
@@ -594,7 +706,7 @@ mod test_func_built {
     fn test_func_mult() {
         let mut stg = LexicalVarStorage::new();
         let expr = Cons::from_iter(vec![
-            Cons::Value(ConsValue::Symbol("*".to_string())),
+            Cons::Value(ConsValue::symbol("*")),
             Cons::Value(ConsValue::Int(123)),
             Cons::Value(ConsValue::Int(456)),
         ]);
@@ -607,7 +719,7 @@ mod test_func_built {
     #[test]
     fn test_func_mult_no_arg() {
         let mut stg = LexicalVarStorage::new();
-        let expr = Cons::from_iter(vec![Cons::Value(ConsValue::Symbol("*".to_string()))]);
+        let expr = Cons::from_iter(vec![Cons::Value(ConsValue::symbol("*"))]);
         let result = lisp_eval(&expr, &mut stg);
         assert!(result.is_ok());
         let result = result.unwrap();
@@ -618,7 +730,7 @@ mod test_func_built {
     fn test_func_mult_one_arg() {
         let mut stg = LexicalVarStorage::new();
         let expr = Cons::from_iter(vec![
-            Cons::Value(ConsValue::Symbol("*".to_string())),
+            Cons::Value(ConsValue::symbol("*")),
             Cons::Value(ConsValue::Int(123)),
         ]);
         let result = lisp_eval(&expr, &mut stg);
@@ -631,20 +743,23 @@ mod test_func_built {
     fn test_func_div() {
         let mut stg = LexicalVarStorage::new();
         let expr = Cons::from_iter(vec![
-            Cons::Value(ConsValue::Symbol("/".to_string())),
+            Cons::Value(ConsValue::symbol("/")),
             Cons::Value(ConsValue::Int(123)),
             Cons::Value(ConsValue::Int(456)),
         ]);
         let result = lisp_eval(&expr, &mut stg);
         assert!(result.is_ok());
         let result = result.unwrap();
-        assert_eq!(result, Cons::Value(ConsValue::Float(0.26973684210526316)));
+        assert_eq!(
+            result,
+            Cons::Value(ConsValue::rational(BigRational::new(41.into(), 152.into())))
+        );
     }
 
     #[test]
     fn test_func_div_no_arg() {
         let mut stg = LexicalVarStorage::new();
-        let expr = Cons::from_iter(vec![Cons::Value(ConsValue::Symbol("/".to_string()))]);
+        let expr = Cons::from_iter(vec![Cons::Value(ConsValue::symbol("/"))]);
         let result = lisp_eval(&expr, &mut stg);
         assert!(result.is_ok());
         let result = result.unwrap();
@@ -655,20 +770,23 @@ mod test_func_built {
     fn test_func_div_one_arg() {
         let mut stg = LexicalVarStorage::new();
         let expr = Cons::from_iter(vec![
-            Cons::Value(ConsValue::Symbol("/".to_string())),
+            Cons::Value(ConsValue::symbol("/")),
             Cons::Value(ConsValue::Int(123)),
         ]);
         let result = lisp_eval(&expr, &mut stg);
         assert!(result.is_ok());
         let result = result.unwrap();
-        assert_eq!(result, Cons::Value(ConsValue::Float(0.008130081300813009)));
+        assert_eq!(
+            result,
+            Cons::Value(ConsValue::rational(BigRational::new(1.into(), 123.into())))
+        );
     }
 
     #[test]
     fn test_func_floordiv() {
         let mut stg = LexicalVarStorage::new();
         let expr = Cons::from_iter(vec![
-            Cons::Value(ConsValue::Symbol("floordiv".to_string())),
+            Cons::Value(ConsValue::symbol("floordiv")),
             Cons::Value(ConsValue::Int(123)),
             Cons::Value(ConsValue::Int(456)),
         ]);
@@ -682,7 +800,7 @@ mod test_func_built {
     fn test_func_mod() {
         let mut stg = LexicalVarStorage::new();
         let expr = Cons::from_iter(vec![
-            Cons::Value(ConsValue::Symbol("%".to_string())),
+            Cons::Value(ConsValue::symbol("%")),
             Cons::Value(ConsValue::Int(123)),
             Cons::Value(ConsValue::Int(456)),
         ]);
@@ -696,7 +814,7 @@ mod test_func_built {
     fn test_func_pow() {
         let mut stg = LexicalVarStorage::new();
         let expr = Cons::from_iter(vec![
-            Cons::Value(ConsValue::Symbol("^".to_string())),
+            Cons::Value(ConsValue::symbol("^")),
             Cons::Value(ConsValue::Int(2)),
             Cons::Value(ConsValue::Int(2)),
         ]);
@@ -706,10 +824,96 @@ mod test_func_built {
         assert_eq!(result, Cons::Value(ConsValue::Int(4)));
     }
 
+    #[test]
+    fn test_func_add_float_contagion() {
+        let mut stg = LexicalVarStorage::new();
+        let expr = Cons::from_iter(vec![
+            Cons::Value(ConsValue::symbol("+")),
+            Cons::Value(ConsValue::Float(1.5)),
+            Cons::Value(ConsValue::Float(1.5)),
+        ]);
+        let result = lisp_eval(&expr, &mut stg);
+        assert!(result.is_ok());
+        let result = result.unwrap();
+        assert_eq!(result, Cons::Value(ConsValue::Float(3.0)));
+    }
+
+    #[test]
+    fn test_func_mult_float_contagion() {
+        let mut stg = LexicalVarStorage::new();
+        let expr = Cons::from_iter(vec![
+            Cons::Value(ConsValue::symbol("*")),
+            Cons::Value(ConsValue::Int(2)),
+            Cons::Value(ConsValue::Float(1.5)),
+        ]);
+        let result = lisp_eval(&expr, &mut stg);
+        assert!(result.is_ok());
+        let result = result.unwrap();
+        assert_eq!(result, Cons::Value(ConsValue::Float(3.0)));
+    }
+
+    #[test]
+    fn test_func_div_exact() {
+        let mut stg = LexicalVarStorage::new();
+        let expr = Cons::from_iter(vec![
+            Cons::Value(ConsValue::symbol("/")),
+            Cons::Value(ConsValue::Int(4)),
+            Cons::Value(ConsValue::Int(2)),
+        ]);
+        let result = lisp_eval(&expr, &mut stg);
+        assert!(result.is_ok());
+        let result = result.unwrap();
+        assert_eq!(result, Cons::Value(ConsValue::Int(2)));
+    }
+
+    #[test]
+    fn test_func_div_float() {
+        let mut stg = LexicalVarStorage::new();
+        let expr = Cons::from_iter(vec![
+            Cons::Value(ConsValue::symbol("/")),
+            Cons::Value(ConsValue::Float(4.0)),
+            Cons::Value(ConsValue::Int(2)),
+        ]);
+        let result = lisp_eval(&expr, &mut stg);
+        assert!(result.is_ok());
+        let result = result.unwrap();
+        assert_eq!(result, Cons::Value(ConsValue::Float(2.0)));
+    }
+
+    #[test]
+    fn test_func_floordiv_float() {
+        let mut stg = LexicalVarStorage::new();
+        let expr = Cons::from_iter(vec![
+            Cons::Value(ConsValue::symbol("floordiv")),
+            Cons::Value(ConsValue::Float(7.5)),
+            Cons::Value(ConsValue::Int(2)),
+        ]);
+        let result = lisp_eval(&expr, &mut stg);
+        assert!(result.is_ok());
+        let result = result.unwrap();
+        assert_eq!(result, Cons::Value(ConsValue::Float(3.0)));
+    }
+
+    #[test]
+    fn test_func_is_int_is_float() {
+        let mut stg = LexicalVarStorage::new();
+        for (name, arg, expected) in [
+            ("is-int", ConsValue::Int(3), true),
+            ("is-int", ConsValue::Float(3.0), false),
+            ("is-float", ConsValue::Float(3.0), true),
+            ("is-float", ConsValue::Int(3), false),
+        ] {
+            let expr =
+                Cons::from_iter(vec![Cons::Value(ConsValue::symbol(name)), Cons::Value(arg)]);
+            let result = lisp_eval(&expr, &mut stg);
+            assert_eq!(result, Ok(Cons::Value(ConsValue::Boolean(expected))));
+        }
+    }
+
     #[test]
     fn test_func_pow_no_arg() {
         let mut stg = LexicalVarStorage::new();
-        let expr = Cons::from_iter(vec![Cons::Value(ConsValue::Symbol("^".to_string()))]);
+        let expr = Cons::from_iter(vec![Cons::Value(ConsValue::symbol("^"))]);
         let result = lisp_eval(&expr, &mut stg);
         assert!(result.is_ok());
         let result = result.unwrap();
@@ -720,7 +924,7 @@ mod test_func_built {
     fn test_func_pow_one_arg() {
         let mut stg = LexicalVarStorage::new();
         let expr = Cons::from_iter(vec![
-            Cons::Value(ConsValue::Symbol("^".to_string())),
+            Cons::Value(ConsValue::symbol("^")),
             Cons::Value(ConsValue::Int(2)),
         ]);
         let result = lisp_eval(&expr, &mut stg);
@@ -733,7 +937,7 @@ mod test_func_built {
     fn test_func_eq() {
         let mut stg = LexicalVarStorage::new();
         let expr = Cons::from_iter(vec![
-            Cons::Value(ConsValue::Symbol("=".to_string())),
+            Cons::Value(ConsValue::symbol("=")),
             Cons::Value(ConsValue::Int(123)),
             Cons::Value(ConsValue::Int(456)),
         ]);
@@ -743,7 +947,7 @@ mod test_func_built {
         assert_eq!(result, Cons::Value(ConsValue::Boolean(false)));
 
         let expr = Cons::from_iter(vec![
-            Cons::Value(ConsValue::Symbol("=".to_string())),
+            Cons::Value(ConsValue::symbol("=")),
             Cons::Value(ConsValue::Int(123)),
             Cons::Value(ConsValue::Int(123)),
         ]);
@@ -756,7 +960,7 @@ mod test_func_built {
     #[test]
     fn test_func_eq_no_arg() {
         let mut stg = LexicalVarStorage::new();
-        let expr = Cons::from_iter(vec![Cons::Value(ConsValue::Symbol("=".to_string()))]);
+        let expr = Cons::from_iter(vec![Cons::Value(ConsValue::symbol("="))]);
         let result = lisp_eval(&expr, &mut stg);
         assert!(result.is_ok());
         let result = result.unwrap();
@@ -767,7 +971,7 @@ mod test_func_built {
     fn test_func_eq_one_arg() {
         let mut stg = LexicalVarStorage::new();
         let expr = Cons::from_iter(vec![
-            Cons::Value(ConsValue::Symbol("=".to_string())),
+            Cons::Value(ConsValue::symbol("=")),
             Cons::Value(ConsValue::Int(123)),
         ]);
         let result = lisp_eval(&expr, &mut stg);
@@ -780,7 +984,7 @@ mod test_func_built {
     fn test_func_neq() {
         let mut stg = LexicalVarStorage::new();
         let expr = Cons::from_iter(vec![
-            Cons::Value(ConsValue::Symbol("!=".to_string())),
+            Cons::Value(ConsValue::symbol("!=")),
             Cons::Value(ConsValue::Int(123)),
             Cons::Value(ConsValue::Int(456)),
         ]);
@@ -790,7 +994,7 @@ mod test_func_built {
         assert_eq!(result, Cons::Value(ConsValue::Boolean(true)));
 
         let expr = Cons::from_iter(vec![
-            Cons::Value(ConsValue::Symbol("!=".to_string())),
+            Cons::Value(ConsValue::symbol("!=")),
             Cons::Value(ConsValue::Int(123)),
             Cons::Value(ConsValue::Int(123)),
         ]);
@@ -803,7 +1007,7 @@ mod test_func_built {
     #[test]
     fn test_func_neq_no_arg() {
         let mut stg = LexicalVarStorage::new();
-        let expr = Cons::from_iter(vec![Cons::Value(ConsValue::Symbol("!=".to_string()))]);
+        let expr = Cons::from_iter(vec![Cons::Value(ConsValue::symbol("!="))]);
         let result = lisp_eval(&expr, &mut stg);
         assert!(result.is_ok());
         let result = result.unwrap();
@@ -814,7 +1018,7 @@ mod test_func_built {
     fn test_func_neq_one_arg() {
         let mut stg = LexicalVarStorage::new();
         let expr = Cons::from_iter(vec![
-            Cons::Value(ConsValue::Symbol("!=".to_string())),
+            Cons::Value(ConsValue::symbol("!=")),
             Cons::Value(ConsValue::Int(123)),
         ]);
         let result = lisp_eval(&expr, &mut stg);
@@ -827,7 +1031,7 @@ mod test_func_built {
     fn test_func_lt() {
         let mut stg = LexicalVarStorage::new();
         let expr = Cons::from_iter(vec![
-            Cons::Value(ConsValue::Symbol("<".to_string())),
+            Cons::Value(ConsValue::symbol("<")),
             Cons::Value(ConsValue::Int(123)),
             Cons::Value(ConsValue::Int(456)),
         ]);
@@ -837,7 +1041,7 @@ mod test_func_built {
         assert_eq!(result, Cons::Value(ConsValue::Boolean(true)));
 
         let expr = Cons::from_iter(vec![
-            Cons::Value(ConsValue::Symbol("<".to_string())),
+            Cons::Value(ConsValue::symbol("<")),
             Cons::Value(ConsValue::Int(123)),
             Cons::Value(ConsValue::Int(123)),
         ]);
@@ -847,7 +1051,7 @@ mod test_func_built {
         assert_eq!(result, Cons::Value(ConsValue::Boolean(false)));
 
         let expr = Cons::from_iter(vec![
-            Cons::Value(ConsValue::Symbol("<".to_string())),
+            Cons::Value(ConsValue::symbol("<")),
             Cons::Value(ConsValue::Int(456)),
             Cons::Value(ConsValue::Int(123)),
         ]);
@@ -861,7 +1065,7 @@ mod test_func_built {
     fn test_func_lt_list() {
         let mut stg = LexicalVarStorage::new();
         let expr = Cons::from_iter(vec![
-            Cons::Value(ConsValue::Symbol("<".to_string())),
+            Cons::Value(ConsValue::symbol("<")),
             Cons::Value(ConsValue::Int(123)),
             Cons::Value(ConsValue::Int(456)),
             Cons::Value(ConsValue::Int(789)),
@@ -873,7 +1077,7 @@ mod test_func_built {
         assert_eq!(result, Cons::Value(ConsValue::Boolean(true)));
 
         let expr = Cons::from_iter(vec![
-            Cons::Value(ConsValue::Symbol("<".to_string())),
+            Cons::Value(ConsValue::symbol("<")),
             Cons::Value(ConsValue::Int(123)),
             Cons::Value(ConsValue::Int(123)),
             Cons::Value(ConsValue::Int(789)),
@@ -886,7 +1090,7 @@ mod test_func_built {
         assert_eq!(result, Cons::Value(ConsValue::Boolean(false)));
 
         let expr = Cons::from_iter(vec![
-            Cons::Value(ConsValue::Symbol("<".to_string())),
+            Cons::Value(ConsValue::symbol("<")),
             Cons::Value(ConsValue::Int(123)),
             Cons::Value(ConsValue::Int(131415)),
             Cons::Value(ConsValue::Int(456)),
@@ -903,7 +1107,7 @@ mod test_func_built {
     fn test_func_gt() {
         let mut stg = LexicalVarStorage::new();
         let expr = Cons::from_iter(vec![
-            Cons::Value(ConsValue::Symbol(">".to_string())),
+            Cons::Value(ConsValue::symbol(">")),
             Cons::Value(ConsValue::Int(123)),
             Cons::Value(ConsValue::Int(456)),
         ]);
@@ -913,7 +1117,7 @@ mod test_func_built {
         assert_eq!(result, Cons::Value(ConsValue::Boolean(false)));
 
         let expr = Cons::from_iter(vec![
-            Cons::Value(ConsValue::Symbol(">".to_string())),
+            Cons::Value(ConsValue::symbol(">")),
             Cons::Value(ConsValue::Int(123)),
             Cons::Value(ConsValue::Int(123)),
         ]);
@@ -923,7 +1127,7 @@ mod test_func_built {
         assert_eq!(result, Cons::Value(ConsValue::Boolean(false)));
 
         let expr = Cons::from_iter(vec![
-            Cons::Value(ConsValue::Symbol(">".to_string())),
+            Cons::Value(ConsValue::symbol(">")),
             Cons::Value(ConsValue::Int(456)),
             Cons::Value(ConsValue::Int(123)),
         ]);
@@ -937,7 +1141,7 @@ mod test_func_built {
     fn test_func_gt_list() {
         let mut stg = LexicalVarStorage::new();
         let expr = Cons::from_iter(vec![
-            Cons::Value(ConsValue::Symbol(">".to_string())),
+            Cons::Value(ConsValue::symbol(">")),
             Cons::Value(ConsValue::Int(101112)),
             Cons::Value(ConsValue::Int(789)),
             Cons::Value(ConsValue::Int(456)),
@@ -949,7 +1153,7 @@ mod test_func_built {
         assert_eq!(result, Cons::Value(ConsValue::Boolean(true)));
 
         let expr = Cons::from_iter(vec![
-            Cons::Value(ConsValue::Symbol(">".to_string())),
+            Cons::Value(ConsValue::symbol(">")),
             Cons::Value(ConsValue::Int(131415)),
             Cons::Value(ConsValue::Int(101112)),
             Cons::Value(ConsValue::Int(789)),
@@ -962,7 +1166,7 @@ mod test_func_built {
         assert_eq!(result, Cons::Value(ConsValue::Boolean(true)));
 
         let expr = Cons::from_iter(vec![
-            Cons::Value(ConsValue::Symbol(">".to_string())),
+            Cons::Value(ConsValue::symbol(">")),
             Cons::Value(ConsValue::Int(131415)),
             Cons::Value(ConsValue::Int(101112)),
             Cons::Value(ConsValue::Int(789)),
@@ -979,7 +1183,7 @@ mod test_func_built {
     fn test_func_lte() {
         let mut stg = LexicalVarStorage::new();
         let expr = Cons::from_iter(vec![
-            Cons::Value(ConsValue::Symbol("<=".to_string())),
+            Cons::Value(ConsValue::symbol("<=")),
             Cons::Value(ConsValue::Int(123)),
             Cons::Value(ConsValue::Int(456)),
         ]);
@@ -989,7 +1193,7 @@ mod test_func_built {
         assert_eq!(result, Cons::Value(ConsValue::Boolean(true)));
 
         let expr = Cons::from_iter(vec![
-            Cons::Value(ConsValue::Symbol("<=".to_string())),
+            Cons::Value(ConsValue::symbol("<=")),
             Cons::Value(ConsValue::Int(123)),
             Cons::Value(ConsValue::Int(123)),
         ]);
@@ -999,7 +1203,7 @@ mod test_func_built {
         assert_eq!(result, Cons::Value(ConsValue::Boolean(true)));
 
         let expr = Cons::from_iter(vec![
-            Cons::Value(ConsValue::Symbol("<=".to_string())),
+            Cons::Value(ConsValue::symbol("<=")),
             Cons::Value(ConsValue::Int(456)),
             Cons::Value(ConsValue::Int(123)),
         ]);
@@ -1013,7 +1217,7 @@ mod test_func_built {
     fn test_func_lte_list() {
         let mut stg = LexicalVarStorage::new();
         let expr = Cons::from_iter(vec![
-            Cons::Value(ConsValue::Symbol("<=".to_string())),
+            Cons::Value(ConsValue::symbol("<=")),
             Cons::Value(ConsValue::Int(123)),
             Cons::Value(ConsValue::Int(456)),
             Cons::Value(ConsValue::Int(789)),
@@ -1025,7 +1229,7 @@ mod test_func_built {
         assert_eq!(result, Cons::Value(ConsValue::Boolean(true)));
 
         let expr = Cons::from_iter(vec![
-            Cons::Value(ConsValue::Symbol("<=".to_string())),
+            Cons::Value(ConsValue::symbol("<=")),
             Cons::Value(ConsValue::Int(123)),
             Cons::Value(ConsValue::Int(123)),
             Cons::Value(ConsValue::Int(789)),
@@ -1038,7 +1242,7 @@ mod test_func_built {
         assert_eq!(result, Cons::Value(ConsValue::Boolean(true)));
 
         let expr = Cons::from_iter(vec![
-            Cons::Value(ConsValue::Symbol("<=".to_string())),
+            Cons::Value(ConsValue::symbol("<=")),
             Cons::Value(ConsValue::Int(123)),
             Cons::Value(ConsValue::Int(131415)),
             Cons::Value(ConsValue::Int(456)),
@@ -1055,7 +1259,7 @@ mod test_func_built {
     fn test_func_gte() {
         let mut stg = LexicalVarStorage::new();
         let expr = Cons::from_iter(vec![
-            Cons::Value(ConsValue::Symbol(">=".to_string())),
+            Cons::Value(ConsValue::symbol(">=")),
             Cons::Value(ConsValue::Int(123)),
             Cons::Value(ConsValue::Int(456)),
         ]);
@@ -1065,7 +1269,7 @@ mod test_func_built {
         assert_eq!(result, Cons::Value(ConsValue::Boolean(false)));
 
         let expr = Cons::from_iter(vec![
-            Cons::Value(ConsValue::Symbol(">=".to_string())),
+            Cons::Value(ConsValue::symbol(">=")),
             Cons::Value(ConsValue::Int(123)),
             Cons::Value(ConsValue::Int(123)),
         ]);
@@ -1075,7 +1279,7 @@ mod test_func_built {
         assert_eq!(result, Cons::Value(ConsValue::Boolean(true)));
 
         let expr = Cons::from_iter(vec![
-            Cons::Value(ConsValue::Symbol(">=".to_string())),
+            Cons::Value(ConsValue::symbol(">=")),
             Cons::Value(ConsValue::Int(456)),
             Cons::Value(ConsValue::Int(123)),
         ]);
@@ -1089,7 +1293,7 @@ mod test_func_built {
     fn test_func_gte_list() {
         let mut stg = LexicalVarStorage::new();
         let expr = Cons::from_iter(vec![
-            Cons::Value(ConsValue::Symbol(">=".to_string())),
+            Cons::Value(ConsValue::symbol(">=")),
             Cons::Value(ConsValue::Int(101112)),
             Cons::Value(ConsValue::Int(789)),
             Cons::Value(ConsValue::Int(456)),
@@ -1101,7 +1305,7 @@ mod test_func_built {
         assert_eq!(result, Cons::Value(ConsValue::Boolean(true)));
 
         let expr = Cons::from_iter(vec![
-            Cons::Value(ConsValue::Symbol(">=".to_string())),
+            Cons::Value(ConsValue::symbol(">=")),
             Cons::Value(ConsValue::Int(131415)),
             Cons::Value(ConsValue::Int(101112)),
             Cons::Value(ConsValue::Int(789)),
@@ -1114,7 +1318,7 @@ mod test_func_built {
         assert_eq!(result, Cons::Value(ConsValue::Boolean(true)));
 
         let expr = Cons::from_iter(vec![
-            Cons::Value(ConsValue::Symbol(">=".to_string())),
+            Cons::Value(ConsValue::symbol(">=")),
             Cons::Value(ConsValue::Int(131415)),
             Cons::Value(ConsValue::Int(101112)),
             Cons::Value(ConsValue::Int(789)),
@@ -1139,8 +1343,8 @@ mod test_mcro_built {
     fn test_set_define_var_atom() {
         let mut stg = LexicalVarStorage::new();
         let expr = Cons::from_iter(vec![
-            Cons::Value(ConsValue::Symbol("define".to_string())),
-            Cons::Value(ConsValue::Symbol("a".to_string())),
+            Cons::Value(ConsValue::symbol("define")),
+            Cons::Value(ConsValue::symbol("a")),
             Cons::Value(ConsValue::Int(123)),
         ]);
         let result = lisp_eval(&expr, &mut stg);
@@ -1148,7 +1352,7 @@ mod test_mcro_built {
         let result = result.unwrap();
         assert_eq!(result, Cons::Value(ConsValue::NIL));
 
-        let expr = Cons::Value(ConsValue::Symbol("a".to_string()));
+        let expr = Cons::Value(ConsValue::symbol("a"));
         let result = lisp_eval(&expr, &mut stg);
         assert!(result.is_ok());
         let result = result.unwrap();
@@ -1159,10 +1363,10 @@ mod test_mcro_built {
     fn test_set_define_var_eval() {
         let mut stg = LexicalVarStorage::new();
         let expr = Cons::from_iter(vec![
-            Cons::Value(ConsValue::Symbol("define".to_string())),
-            Cons::Value(ConsValue::Symbol("a".to_string())),
+            Cons::Value(ConsValue::symbol("define")),
+            Cons::Value(ConsValue::symbol("a")),
             Cons::from_iter(vec![
-                Cons::Value(ConsValue::Symbol("+".to_string())),
+                Cons::Value(ConsValue::symbol("+")),
                 Cons::Value(ConsValue::Int(123)),
                 Cons::Value(ConsValue::Int(456)),
             ]),
@@ -1172,7 +1376,7 @@ mod test_mcro_built {
         let result = result.unwrap();
         assert_eq!(result, Cons::Value(ConsValue::NIL));
 
-        let expr = Cons::Value(ConsValue::Symbol("a".to_string()));
+        let expr = Cons::Value(ConsValue::symbol("a"));
         let result = lisp_eval(&expr, &mut stg);
         assert!(result.is_ok());
         let result = result.unwrap();
@@ -1183,16 +1387,16 @@ mod test_mcro_built {
     fn test_define_real_func() {
         let mut stg = LexicalVarStorage::new();
         let expr = Cons::from_iter(vec![
-            Cons::Value(ConsValue::Symbol("define".to_string())),
+            Cons::Value(ConsValue::symbol("define")),
             Cons::from_iter(vec![
-                Cons::Value(ConsValue::Symbol("add".to_string())),
-                Cons::Value(ConsValue::Symbol("a".to_string())),
-                Cons::Value(ConsValue::Symbol("b".to_string())),
+                Cons::Value(ConsValue::symbol("add")),
+                Cons::Value(ConsValue::symbol("a")),
+                Cons::Value(ConsValue::symbol("b")),
             ]),
             Cons::from_iter(vec![
-                Cons::Value(ConsValue::Symbol("+".to_string())),
-                Cons::Value(ConsValue::Symbol("a".to_string())),
-                Cons::Value(ConsValue::Symbol("b".to_string())),
+                Cons::Value(ConsValue::symbol("+")),
+                Cons::Value(ConsValue::symbol("a")),
+                Cons::Value(ConsValue::symbol("b")),
             ]),
         ]);
         let result = lisp_eval(&expr, &mut stg);
@@ -1201,7 +1405,7 @@ mod test_mcro_built {
         assert_eq!(result, Cons::Value(ConsValue::NIL));
 
         let expr = Cons::from_iter(vec![
-            Cons::Value(ConsValue::Symbol("add".to_string())),
+            Cons::Value(ConsValue::symbol("add")),
             Cons::Value(ConsValue::Int(123)),
             Cons::Value(ConsValue::Int(456)),
         ]);
@@ -1215,11 +1419,11 @@ mod test_mcro_built {
     fn test_define_empty_func() {
         let mut stg = LexicalVarStorage::new();
         let expr = Cons::from_iter(vec![
-            Cons::Value(ConsValue::Symbol("define".to_string())),
+            Cons::Value(ConsValue::symbol("define")),
             Cons::from_iter(vec![
-                Cons::Value(ConsValue::Symbol("add".to_string())),
-                Cons::Value(ConsValue::Symbol("a".to_string())),
-                Cons::Value(ConsValue::Symbol("b".to_string())),
+                Cons::Value(ConsValue::symbol("add")),
+                Cons::Value(ConsValue::symbol("a")),
+                Cons::Value(ConsValue::symbol("b")),
             ]),
         ]);
         let result = lisp_eval(&expr, &mut stg);
@@ -1228,7 +1432,7 @@ mod test_mcro_built {
         assert_eq!(result, Cons::Value(ConsValue::NIL));
 
         let expr = Cons::from_iter(vec![
-            Cons::Value(ConsValue::Symbol("add".to_string())),
+            Cons::Value(ConsValue::symbol("add")),
             Cons::Value(ConsValue::Int(123)),
             Cons::Value(ConsValue::Int(456)),
         ]);
@@ -1242,21 +1446,21 @@ mod test_mcro_built {
     fn test_define_multi_line_func() {
         let mut stg = LexicalVarStorage::new();
         let expr = Cons::from_iter(vec![
-            Cons::Value(ConsValue::Symbol("define".to_string())),
+            Cons::Value(ConsValue::symbol("define")),
             Cons::from_iter(vec![
-                Cons::Value(ConsValue::Symbol("add-then-sub".to_string())),
-                Cons::Value(ConsValue::Symbol("a".to_string())),
-                Cons::Value(ConsValue::Symbol("b".to_string())),
+                Cons::Value(ConsValue::symbol("add-then-sub")),
+                Cons::Value(ConsValue::symbol("a")),
+                Cons::Value(ConsValue::symbol("b")),
             ]),
             Cons::from_iter(vec![
-                Cons::Value(ConsValue::Symbol("+".to_string())),
-                Cons::Value(ConsValue::Symbol("a".to_string())),
-                Cons::Value(ConsValue::Symbol("b".to_string())),
+                Cons::Value(ConsValue::symbol("+")),
+                Cons::Value(ConsValue::symbol("a")),
+                Cons::Value(ConsValue::symbol("b")),
             ]),
             Cons::from_iter(vec![
-                Cons::Value(ConsValue::Symbol("-".to_string())),
-                Cons::Value(ConsValue::Symbol("a".to_string())),
-                Cons::Value(ConsValue::Symbol("b".to_string())),
+                Cons::Value(ConsValue::symbol("-")),
+                Cons::Value(ConsValue::symbol("a")),
+                Cons::Value(ConsValue::symbol("b")),
             ]),
         ]);
         let result = lisp_eval(&expr, &mut stg);
@@ -1265,7 +1469,7 @@ mod test_mcro_built {
         assert_eq!(result, Cons::Value(ConsValue::NIL));
 
         let expr = Cons::from_iter(vec![
-            Cons::Value(ConsValue::Symbol("add-then-sub".to_string())),
+            Cons::Value(ConsValue::symbol("add-then-sub")),
             Cons::Value(ConsValue::Int(123)),
             Cons::Value(ConsValue::Int(456)),
         ]);
@@ -1281,16 +1485,16 @@ mod test_mcro_built {
 
         // Create a function that overrides the + function
         let expr = Cons::from_iter(vec![
-            Cons::Value(ConsValue::Symbol("define".to_string())),
+            Cons::Value(ConsValue::symbol("define")),
             Cons::from_iter(vec![
-                Cons::Value(ConsValue::Symbol("add".to_string())),
-                Cons::Value(ConsValue::Symbol("a".to_string())),
-                Cons::Value(ConsValue::Symbol("b".to_string())),
+                Cons::Value(ConsValue::symbol("add")),
+                Cons::Value(ConsValue::symbol("a")),
+                Cons::Value(ConsValue::symbol("b")),
             ]),
             Cons::from_iter(vec![
-                Cons::Value(ConsValue::Symbol("+".to_string())),
-                Cons::Value(ConsValue::Symbol("a".to_string())),
-                Cons::Value(ConsValue::Symbol("b".to_string())),
+                Cons::Value(ConsValue::symbol("+")),
+                Cons::Value(ConsValue::symbol("a")),
+                Cons::Value(ConsValue::symbol("b")),
             ]),
         ]);
         let result = lisp_eval(&expr, &mut stg);
@@ -1300,7 +1504,7 @@ mod test_mcro_built {
 
         // Invoke the new add function
         let expr = Cons::from_iter(vec![
-            Cons::Value(ConsValue::Symbol("add".to_string())),
+            Cons::Value(ConsValue::symbol("add")),
             Cons::Value(ConsValue::Int(123)),
             Cons::Value(ConsValue::Int(456)),
         ]);
@@ -1312,20 +1516,20 @@ mod test_mcro_built {
         // Create a function that uses the new add function
         // As part of its definition
         let expr = Cons::from_iter(vec![
-            Cons::Value(ConsValue::Symbol("define".to_string())),
+            Cons::Value(ConsValue::symbol("define")),
             Cons::from_iter(vec![
-                Cons::Value(ConsValue::Symbol("addadd".to_string())),
-                Cons::Value(ConsValue::Symbol("a".to_string())),
-                Cons::Value(ConsValue::Symbol("b".to_string())),
+                Cons::Value(ConsValue::symbol("addadd")),
+                Cons::Value(ConsValue::symbol("a")),
+                Cons::Value(ConsValue::symbol("b")),
             ]),
             Cons::from_iter(vec![
-                Cons::Value(ConsValue::Symbol("add".to_string())),
-                Cons::Value(ConsValue::Symbol("a".to_string())),
-                Cons::Value(ConsValue::Symbol("b".to_string())),
+                Cons::Value(ConsValue::symbol("add")),
+                Cons::Value(ConsValue::symbol("a")),
+                Cons::Value(ConsValue::symbol("b")),
                 Cons::from_iter(vec![
-                    Cons::Value(ConsValue::Symbol("add".to_string())),
-                    Cons::Value(ConsValue::Symbol("a".to_string())),
-                    Cons::Value(ConsValue::Symbol("b".to_string())),
+                    Cons::Value(ConsValue::symbol("add")),
+                    Cons::Value(ConsValue::symbol("a")),
+                    Cons::Value(ConsValue::symbol("b")),
                 ]),
             ]),
         ]);
@@ -1336,7 +1540,7 @@ mod test_mcro_built {
 
         // Invoke the new addadd function
         let expr = Cons::from_iter(vec![
-            Cons::Value(ConsValue::Symbol("addadd".to_string())),
+            Cons::Value(ConsValue::symbol("addadd")),
             Cons::Value(ConsValue::Int(123)),
             Cons::Value(ConsValue::Int(456)),
         ]);
@@ -1351,18 +1555,18 @@ mod test_mcro_built {
     // fn test_lambda_define() {
     //     let mut stg = LexicalVarStorage::new();
     //     let expr = Cons::from_iter(vec![
-    //         Cons::Value(ConsValue::Symbol("define".to_string())),
-    //         Cons::Value(ConsValue::Symbol("add".to_string())),
+    //         Cons::Value(ConsValue::symbol("define")),
+    //         Cons::Value(ConsValue::symbol("add")),
     //         Cons::from_iter(vec![
-    //             Cons::Value(ConsValue::Symbol("lambda".to_string())),
+    //             Cons::Value(ConsValue::symbol("lambda")),
     //             Cons::from_iter(vec![
-    //                 Cons::Value(ConsValue::Symbol("a".to_string())),
-    //                 Cons::Value(ConsValue::Symbol("b".to_string())),
+    //                 Cons::Value(ConsValue::symbol("a")),
+    //                 Cons::Value(ConsValue::symbol("b")),
     //             ]),
     //             Cons::from_iter(vec![
-    //                 Cons::Value(ConsValue::Symbol("+".to_string())),
-    //                 Cons::Value(ConsValue::Symbol("a".to_string())),
-    //                 Cons::Value(ConsValue::Symbol("b".to_string())),
+    //                 Cons::Value(ConsValue::symbol("+")),
+    //                 Cons::Value(ConsValue::symbol("a")),
+    //                 Cons::Value(ConsValue::symbol("b")),
     //             ]),
     //         ]),
     //     ]);
@@ -1375,7 +1579,7 @@ mod test_mcro_built {
 
     //     // Invoke the new add function
     //     let expr = Cons::from_iter(vec![
-    //         Cons::Value(ConsValue::Symbol("add".to_string())),
+    //         Cons::Value(ConsValue::symbol("add")),
     //         Cons::Value(ConsValue::Int(123)),
     //         Cons::Value(ConsValue::Int(456)),
     //     ]);
@@ -1385,3 +1589,2226 @@ mod test_mcro_built {
     //     assert_eq!(result, Cons::Value(ConsValue::Int(579)));
     // }
 }
+
+#[cfg(test)]
+mod test_modules {
+    use crate::{
+        errors::EvaluatorError,
+        loader::eval_source,
+        types::{Cons, ConsValue, scope::LexicalVarStorage, symbol::Symbol},
+    };
+    use std::{fs, path::PathBuf};
+
+    fn module_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("boxr-test-modules-{}", name));
+        fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    #[test]
+    fn test_module_exports() {
+        let mut stg = LexicalVarStorage::new();
+        let src = "(module math (export add-one) (define (helper x) (+ x 1)) (define (add-one x) (helper x)))
+                   (import math)
+                   (add-one 41)";
+        assert_eq!(
+            eval_source(src, &mut stg),
+            Ok(Cons::Value(ConsValue::Int(42)))
+        );
+        // Only the exported name became visible.
+        assert!(stg.get_func(&Symbol::new("add-one")).is_some());
+        assert!(stg.get_func(&Symbol::new("helper")).is_none());
+    }
+
+    #[test]
+    fn test_module_prefix_import() {
+        let mut stg = LexicalVarStorage::new();
+        let src = "(module a (export helper value) (define (helper x) (+ x 1)) (define value 10))
+                   (module b (export helper) (define (helper x) (* x 2)))
+                   (import (prefix a a:) (prefix b b:))
+                   (+ (a:helper a:value) (b:helper 5))";
+        assert_eq!(
+            eval_source(src, &mut stg),
+            Ok(Cons::Value(ConsValue::Int(21)))
+        );
+    }
+
+    #[test]
+    fn test_module_undefined_export() {
+        let mut stg = LexicalVarStorage::new();
+        let src = "(module broken (export missing) (define present 1))";
+        assert!(matches!(
+            eval_source(src, &mut stg),
+            Err(EvaluatorError::ModuleError(_))
+        ));
+    }
+
+    #[test]
+    fn test_import_from_load_path() {
+        let dir = module_dir("load-path");
+        fs::write(
+            dir.join("greeter.scm"),
+            "(module greeter (export greeting) (define greeting \"hello\"))",
+        )
+        .unwrap();
+        let mut stg = LexicalVarStorage::new();
+        stg.add_load_path(dir);
+        assert_eq!(
+            eval_source("(import greeter) greeting", &mut stg),
+            Ok(Cons::Value(ConsValue::String("hello".to_string())))
+        );
+    }
+
+    #[test]
+    fn test_load_from_load_path() {
+        let dir = module_dir("load");
+        fs::write(dir.join("defs.scm"), "(define loaded 7)").unwrap();
+        let mut stg = LexicalVarStorage::new();
+        stg.add_load_path(dir);
+        assert_eq!(
+            eval_source("(load \"defs\") loaded", &mut stg),
+            Ok(Cons::Value(ConsValue::Int(7)))
+        );
+    }
+
+    #[test]
+    fn test_import_missing_module() {
+        let mut stg = LexicalVarStorage::new();
+        assert!(matches!(
+            eval_source("(import no-such-module)", &mut stg),
+            Err(EvaluatorError::ModuleError(_))
+        ));
+    }
+}
+
+#[cfg(test)]
+mod test_help {
+    use crate::{
+        errors::EvaluatorError,
+        loader::eval_source,
+        types::{
+            Cons, ConsValue,
+            doc::help_text,
+            function::{BUILTINS_FUNC_MAP, BUILTINS_MACRO_MAP},
+            port::Port,
+            scope::LexicalVarStorage,
+            symbol::Symbol,
+        },
+    };
+
+    #[test]
+    fn test_builtins_documented() {
+        for f in BUILTINS_FUNC_MAP.values() {
+            assert!(!f.doc().signature.is_empty(), "{:?}", f);
+            assert!(!f.doc().description.is_empty(), "{:?}", f);
+        }
+        for m in BUILTINS_MACRO_MAP.values() {
+            assert!(!m.doc().signature.is_empty(), "{:?}", m);
+            assert!(!m.doc().description.is_empty(), "{:?}", m);
+        }
+    }
+
+    #[test]
+    fn test_help_builtin() {
+        let stg = LexicalVarStorage::new();
+        assert_eq!(
+            help_text("+", &stg),
+            Some("(+ num ...)\n    Returns the sum of its arguments.".to_string())
+        );
+        assert_eq!(help_text("no-such-name", &stg), None);
+    }
+
+    #[test]
+    fn test_docstring() {
+        let mut stg = LexicalVarStorage::new();
+        let src = "(define (add-one x) \"Adds one to x.\" (+ x 1))
+                   (add-one 1)";
+        assert_eq!(
+            eval_source(src, &mut stg),
+            Ok(Cons::Value(ConsValue::Int(2)))
+        );
+        assert_eq!(
+            help_text("add-one", &stg),
+            Some("(add-one x)\n    Adds one to x.".to_string())
+        );
+    }
+
+    #[test]
+    fn test_string_only_body_is_not_docstring() {
+        let mut stg = LexicalVarStorage::new();
+        let src = "(define (greet x) \"hello\")
+                   (greet 1)";
+        assert_eq!(
+            eval_source(src, &mut stg),
+            Ok(Cons::Value(ConsValue::String("hello".to_string())))
+        );
+        assert_eq!(stg.get_func(&Symbol::new("greet")).unwrap().doc(), None);
+    }
+
+    #[test]
+    fn test_help_writes_to_output() {
+        let port = Port::string();
+        let mut stg = LexicalVarStorage::new();
+        stg.set_output(port.clone());
+        assert_eq!(
+            eval_source("(help '+)", &mut stg),
+            Ok(Cons::Value(ConsValue::NIL))
+        );
+        assert_eq!(
+            port.contents(),
+            Some("(+ num ...)\n    Returns the sum of its arguments.\n".to_string())
+        );
+    }
+
+    #[test]
+    fn test_help_undefined() {
+        let mut stg = LexicalVarStorage::new();
+        assert_eq!(
+            eval_source("(help 'no-such-name)", &mut stg),
+            Err(EvaluatorError::UndefinedSymbol("no-such-name".to_string()))
+        );
+    }
+
+    #[test]
+    fn test_apropos() {
+        let mut stg = LexicalVarStorage::new();
+        let src = "(define is-thing 1)
+                   (apropos \"is-\")";
+        let result = eval_source(src, &mut stg).unwrap();
+        let names: Vec<Cons> = result.into_iter().collect();
+        assert!(names.contains(&Cons::Value(ConsValue::symbol("is-list"))));
+        assert!(names.contains(&Cons::Value(ConsValue::symbol("is-thing"))));
+        assert!(!names.contains(&Cons::Value(ConsValue::symbol("car"))));
+        let mut sorted = names.clone();
+        sorted.sort_by_key(|c| c.to_string());
+        assert_eq!(names, sorted);
+    }
+}
+
+#[cfg(test)]
+mod test_exit {
+    use crate::{
+        errors::EvaluatorError,
+        loader::eval_source,
+        types::{Cons, ConsValue, scope::LexicalVarStorage, symbol::Symbol},
+    };
+
+    #[test]
+    fn test_exit_code() {
+        let mut stg = LexicalVarStorage::new();
+        assert_eq!(
+            eval_source("(exit 3)", &mut stg),
+            Err(EvaluatorError::Exit(3))
+        );
+        assert_eq!(
+            eval_source("(exit)", &mut stg),
+            Err(EvaluatorError::Exit(0))
+        );
+        assert_eq!(
+            eval_source("(exit #f)", &mut stg),
+            Err(EvaluatorError::Exit(1))
+        );
+    }
+
+    #[test]
+    fn test_exit_unwinds() {
+        let mut stg = LexicalVarStorage::new();
+        let src = "(define (stop x) (exit x) (define after 1))
+                   (+ 1 (stop 7))
+                   (define later 2)";
+        assert_eq!(eval_source(src, &mut stg), Err(EvaluatorError::Exit(7)));
+        assert_eq!(stg.get(&Symbol::new("after")), None);
+        assert_eq!(stg.get(&Symbol::new("later")), None);
+    }
+
+    #[test]
+    fn test_exit_bad_code() {
+        let mut stg = LexicalVarStorage::new();
+        assert!(matches!(
+            eval_source("(exit \"no\")", &mut stg),
+            Err(EvaluatorError::InvalidArgument(_))
+        ));
+        assert!(matches!(
+            eval_source("(exit 99999999999)", &mut stg),
+            Err(EvaluatorError::InvalidArgument(_))
+        ));
+    }
+
+    #[test]
+    fn test_zero_arg_call() {
+        let mut stg = LexicalVarStorage::new();
+        let src = "(define (answer) 42)
+                   (answer)";
+        assert_eq!(
+            eval_source(src, &mut stg),
+            Ok(Cons::Value(ConsValue::Int(42)))
+        );
+    }
+}
+
+#[cfg(test)]
+mod test_strings {
+    use crate::{
+        errors::EvaluatorError,
+        test_util::{eval, string},
+        types::{Cons, ConsValue},
+    };
+
+    #[test]
+    fn test_string_append_and_length() {
+        assert_eq!(
+            eval("(string-append \"foo\" \"bar\" \"baz\")"),
+            string("foobarbaz")
+        );
+        assert_eq!(
+            eval("(string-length \"héllo\")"),
+            Ok(Cons::Value(ConsValue::Int(5)))
+        );
+    }
+
+    #[test]
+    fn test_substring() {
+        assert_eq!(eval("(substring \"héllo\" 1 3)"), string("él"));
+        assert_eq!(eval("(substring \"hello\" 2)"), string("llo"));
+        assert!(matches!(
+            eval("(substring \"hello\" 2 9)"),
+            Err(EvaluatorError::InvalidArgument(_))
+        ));
+    }
+
+    #[test]
+    fn test_split_join_trim() {
+        assert_eq!(
+            eval("(string-split \"a,b,,c\" \",\")"),
+            Ok(Cons::from_iter(
+                ["a", "b", "", "c"].map(|s| string(s).unwrap())
+            ))
+        );
+        assert_eq!(
+            eval("(string-join (string-split \"  one  two \") \"-\")"),
+            string("one-two")
+        );
+        assert_eq!(eval("(string-trim \"  x y \")"), string("x y"));
+    }
+
+    #[test]
+    fn test_case_search_replace() {
+        assert_eq!(eval("(string-upcase \"abc\")"), string("ABC"));
+        assert_eq!(eval("(string-downcase \"ABC\")"), string("abc"));
+        assert_eq!(
+            eval("(string-contains \"héllo\" \"llo\")"),
+            Ok(Cons::Value(ConsValue::Int(2)))
+        );
+        assert_eq!(
+            eval("(string-contains \"hello\" \"z\")"),
+            Ok(Cons::Value(ConsValue::Boolean(false)))
+        );
+        assert_eq!(
+            eval("(string-replace \"a-b-c\" \"-\" \"+\")"),
+            string("a+b+c")
+        );
+    }
+
+    #[test]
+    fn test_list_conversion() {
+        assert_eq!(
+            eval("(string->list \"ab\")"),
+            Ok(Cons::from_iter(vec![
+                Cons::Value(ConsValue::Char('a')),
+                Cons::Value(ConsValue::Char('b'))
+            ]))
+        );
+        assert_eq!(
+            eval("(list->string (string->list \"héllo\"))"),
+            string("héllo")
+        );
+    }
+
+    #[test]
+    fn test_number_conversion() {
+        assert_eq!(
+            eval("(string->number \"42\")"),
+            Ok(Cons::Value(ConsValue::Int(42)))
+        );
+        assert_eq!(
+            eval("(string->number \"ff\" 16)"),
+            Ok(Cons::Value(ConsValue::Int(255)))
+        );
+        assert_eq!(
+            eval("(string->number \"2.5\")"),
+            Ok(Cons::Value(ConsValue::Float(2.5)))
+        );
+        assert_eq!(
+            eval("(string->number \"nan\")"),
+            Ok(Cons::Value(ConsValue::Boolean(false)))
+        );
+        assert_eq!(
+            eval("(string->number \"1_000\")"),
+            Ok(Cons::Value(ConsValue::Boolean(false)))
+        );
+        assert_eq!(
+            eval("(string->number \"1_0/2\")"),
+            Ok(Cons::Value(ConsValue::Boolean(false)))
+        );
+        assert_eq!(
+            eval("(string->number \"-0000000000000000000000012\")"),
+            Ok(Cons::Value(ConsValue::Int(-12)))
+        );
+        assert_eq!(eval("(number->string 255 16)"), string("ff"));
+        assert_eq!(eval("(number->string -5 2)"), string("-101"));
+        assert_eq!(eval("(number->string 1.5)"), string("1.5"));
+        assert!(matches!(
+            eval("(number->string 10 1)"),
+            Err(EvaluatorError::InvalidArgument(_))
+        ));
+    }
+
+    #[test]
+    fn test_symbol_conversion() {
+        assert_eq!(
+            eval("(string->symbol \"abc\")"),
+            Ok(Cons::Value(ConsValue::symbol("abc")))
+        );
+        assert_eq!(eval("(symbol->string 'abc)"), string("abc"));
+    }
+
+    #[test]
+    fn test_string_comparison() {
+        let t = Ok(Cons::Value(ConsValue::Boolean(true)));
+        let f = Ok(Cons::Value(ConsValue::Boolean(false)));
+        assert_eq!(eval("(string<? \"apple\" \"banana\" \"cherry\")"), t);
+        assert_eq!(eval("(string<? \"apple\" \"apple\")"), f);
+        assert_eq!(eval("(string<=? \"apple\" \"apple\")"), t);
+        assert_eq!(eval("(string>? \"b\" \"a\")"), t);
+        assert_eq!(eval("(string>=? \"a\" \"b\")"), f);
+        assert_eq!(eval("(string=? \"a\" \"a\" \"a\")"), t);
+        assert!(matches!(
+            eval("(string<? \"a\" 1)"),
+            Err(EvaluatorError::InvalidArgument(_))
+        ));
+    }
+}
+
+#[cfg(test)]
+mod test_format {
+    use crate::{
+        errors::EvaluatorError,
+        loader::eval_source,
+        test_util::{eval, string},
+        types::{Cons, ConsValue, port::Port, scope::LexicalVarStorage},
+    };
+
+    #[test]
+    fn test_format_display_write() {
+        assert_eq!(
+            eval("(format #f \"~a and ~s\" \"str\" \"str\")"),
+            string("str and \"str\"")
+        );
+        assert_eq!(
+            eval("(format #f \"~a\" '(1 \"two\" 3.5))"),
+            string("(1 two 3.5)")
+        );
+        assert_eq!(
+            eval("(format #f \"[~6a][~6@a]\" 'ab 'cd)"),
+            string("[ab    ][    cd]")
+        );
+    }
+
+    #[test]
+    fn test_format_integers() {
+        assert_eq!(
+            eval("(format #f \"~d|~5d|~5,'0d\" 42 42 -42)"),
+            string("42|   42|-0042")
+        );
+        assert_eq!(
+            eval("(format #f \"~5,'0d|~5d|~6,'0x\" -5 -5 -255)"),
+            string("-0005|   -5|-000ff")
+        );
+        assert_eq!(
+            eval("(format #f \"~x ~b ~o\" 255 5 8)"),
+            string("ff 101 10")
+        );
+        assert!(matches!(
+            eval("(format #f \"~d\" 1.5)"),
+            Err(EvaluatorError::InvalidArgument(_))
+        ));
+    }
+
+    #[test]
+    fn test_format_floats() {
+        assert_eq!(
+            eval("(format #f \"~,2f|~8,3f|~f\" 3.14159 2 0.5)"),
+            string("3.14|   2.000|0.5")
+        );
+    }
+
+    #[test]
+    fn test_format_escapes() {
+        assert_eq!(eval("(format #f \"100~~~%done\")"), string("100~\ndone"));
+    }
+
+    #[test]
+    fn test_format_argument_errors() {
+        assert!(matches!(
+            eval("(format #f \"~a ~a\" 1)"),
+            Err(EvaluatorError::InvalidArgument(_))
+        ));
+        assert!(matches!(
+            eval("(format #f \"~a\" 1 2)"),
+            Err(EvaluatorError::InvalidArgument(_))
+        ));
+        assert!(matches!(
+            eval("(format #f \"~q\" 1)"),
+            Err(EvaluatorError::InvalidArgument(_))
+        ));
+    }
+
+    #[test]
+    fn test_format_to_output() {
+        let port = Port::string();
+        let mut stg = LexicalVarStorage::new();
+        stg.set_output(port.clone());
+        assert_eq!(
+            eval_source("(format #t \"x=~d~%\" 1) (println \"y\" 2)", &mut stg),
+            Ok(Cons::Value(ConsValue::NIL))
+        );
+        assert_eq!(port.contents(), Some("x=1\ny 2\n".to_string()));
+    }
+
+    #[test]
+    fn test_format_to_port() {
+        let mut stg = LexicalVarStorage::new();
+        let src = "(define out (open-output-string))
+                   (format out \"~a-\" 1)
+                   (format out \"~a\" 2)
+                   (get-output-string out)";
+        assert_eq!(eval_source(src, &mut stg), string("1-2"));
+    }
+}
+
+#[cfg(test)]
+mod test_math {
+    use crate::{
+        errors::EvaluatorError,
+        test_util::{eval, float, int},
+    };
+
+    #[test]
+    fn test_math_abs_min_max() {
+        assert_eq!(eval("(abs -5)"), int(5));
+        assert_eq!(eval("(abs -2.5)"), float(2.5));
+        assert_eq!(eval("(min 3 1 2)"), int(1));
+        assert_eq!(eval("(max 3 1 2)"), int(3));
+        assert_eq!(eval("(max 1 2.5)"), float(2.5));
+        assert_eq!(eval("(max 3 2.5)"), float(3.0));
+        assert!(matches!(
+            eval("(min)"),
+            Err(EvaluatorError::InvalidArgument(_))
+        ));
+    }
+
+    #[test]
+    fn test_math_rounding() {
+        assert_eq!(eval("(floor 2.7)"), float(2.0));
+        assert_eq!(eval("(floor -2.5)"), float(-3.0));
+        assert_eq!(eval("(ceiling 2.1)"), float(3.0));
+        assert_eq!(eval("(round 2.5)"), float(2.0));
+        assert_eq!(eval("(round 3.5)"), float(4.0));
+        assert_eq!(eval("(truncate -2.7)"), float(-2.0));
+        assert_eq!(eval("(floor 7)"), int(7));
+    }
+
+    #[test]
+    fn test_math_transcendental() {
+        assert_eq!(eval("(sqrt 16)"), int(4));
+        assert_eq!(eval("(sqrt 16.0)"), float(4.0));
+        assert_eq!(eval("(sqrt 2)"), float(2f64.sqrt()));
+        assert_eq!(eval("(exp 0)"), float(1.0));
+        assert_eq!(eval("(log 1)"), float(0.0));
+        assert_eq!(eval("(log 8 2)"), float(3.0));
+        assert_eq!(eval("(sin 0)"), float(0.0));
+        assert_eq!(eval("(cos 0)"), float(1.0));
+        assert_eq!(eval("(atan 1 1)"), float(std::f64::consts::FRAC_PI_4));
+        assert!(matches!(
+            eval("(sqrt \"4\")"),
+            Err(EvaluatorError::InvalidArgument(_))
+        ));
+    }
+
+    #[test]
+    fn test_math_constants() {
+        assert_eq!(eval("pi"), float(std::f64::consts::PI));
+        assert_eq!(eval("e"), float(std::f64::consts::E));
+        assert_eq!(eval("(define e 2) e"), int(2));
+    }
+
+    #[test]
+    fn test_math_exactness() {
+        assert_eq!(eval("(exact->inexact 3)"), float(3.0));
+        assert_eq!(eval("(inexact->exact 3.0)"), int(3));
+        assert_eq!(eval("(inexact->exact 3.5)").unwrap().to_string(), "7/2");
+    }
+}
+
+#[cfg(test)]
+mod test_integer {
+    use crate::{
+        errors::EvaluatorError,
+        test_util::{display, eval, int},
+        types::{Cons, ConsValue},
+    };
+
+    #[test]
+    fn test_integer_division_signs() {
+        assert_eq!(eval("(quotient 17 5)"), int(3));
+        assert_eq!(eval("(quotient -17 5)"), int(-3));
+        assert_eq!(eval("(remainder 17 -5)"), int(2));
+        assert_eq!(eval("(remainder -17 5)"), int(-2));
+        assert_eq!(eval("(modulo -17 5)"), int(3));
+        assert_eq!(eval("(modulo 17 -5)"), int(-3));
+        assert_eq!(eval("(modulo -15 5)"), int(0));
+    }
+
+    #[test]
+    fn test_integer_large_values() {
+        assert_eq!(
+            eval("(remainder 9007199254740993 2)"),
+            int(1),
+            "must not round through f64"
+        );
+        assert_eq!(eval("(% 9007199254740993 10)"), int(3));
+        assert_eq!(
+            eval("(remainder (* 16807 1622650073) 2147483647)"),
+            int(984943658)
+        );
+    }
+
+    #[test]
+    fn test_integer_division_by_zero() {
+        assert!(matches!(
+            eval("(quotient 1 0)"),
+            Err(EvaluatorError::DivisionByZero(_))
+        ));
+        assert!(matches!(
+            eval("(modulo 1 0)"),
+            Err(EvaluatorError::DivisionByZero(_))
+        ));
+        assert!(matches!(
+            eval("(% 1 0)"),
+            Err(EvaluatorError::DivisionByZero(_))
+        ));
+        assert!(matches!(
+            eval("(% 1.5 0.0)"),
+            Err(EvaluatorError::DivisionByZero(_))
+        ));
+    }
+
+    #[test]
+    fn test_integer_gcd_lcm_expt() {
+        assert_eq!(eval("(gcd 12 -18)"), int(6));
+        assert_eq!(eval("(gcd)"), int(0));
+        assert_eq!(eval("(lcm 4 6)"), int(12));
+        assert_eq!(eval("(lcm)"), int(1));
+        assert_eq!(eval("(expt 3 39)"), int(4052555153018976267));
+        assert_eq!(eval("(expt 2 0)"), int(1));
+        assert_eq!(display("(expt 1/2 2)"), "1/4");
+        assert_eq!(display("(expt 2 -1)"), "1/2");
+        assert_eq!(display("(expt 4 0.5)"), "2.0");
+        assert_eq!(
+            eval("(number->string (expt 10 20))"),
+            Ok(Cons::Value(ConsValue::String(
+                "100000000000000000000".to_string()
+            )))
+        );
+        assert_eq!(
+            eval("(exact-integer-sqrt 17)"),
+            Ok(Cons::from_iter(vec![
+                Cons::Value(ConsValue::Int(4)),
+                Cons::Value(ConsValue::Int(1)),
+            ]))
+        );
+    }
+
+    #[test]
+    fn test_integer_bitwise() {
+        assert_eq!(eval("(bit-and 12 10)"), int(8));
+        assert_eq!(eval("(bit-or 12 10)"), int(14));
+        assert_eq!(eval("(bit-xor 12 10)"), int(6));
+        assert_eq!(eval("(bit-not 0)"), int(-1));
+        assert_eq!(eval("(arithmetic-shift 1 10)"), int(1024));
+        assert_eq!(eval("(arithmetic-shift -8 -1)"), int(-4));
+        assert_eq!(eval("(bit-count 255)"), int(8));
+        assert_eq!(
+            eval("(arithmetic-shift (arithmetic-shift 1 63) -62)"),
+            int(2)
+        );
+    }
+
+    #[test]
+    fn test_integer_result_limit() {
+        assert!(matches!(
+            eval("(arithmetic-shift 1 100000000000)"),
+            Err(EvaluatorError::InvalidArgument(_))
+        ));
+        assert!(matches!(
+            eval("(expt 10 100000000)"),
+            Err(EvaluatorError::InvalidArgument(_))
+        ));
+        assert_eq!(eval("(arithmetic-shift 0 100000000000)"), int(0));
+        assert_eq!(eval("(arithmetic-shift -5 -100000000000)"), int(-1));
+        assert_eq!(eval("(expt -1 100000000001)"), int(-1));
+        assert!(matches!(
+            eval("(^ 2 100000000)"),
+            Err(EvaluatorError::InvalidArgument(_))
+        ));
+        assert!(matches!(
+            eval("(^ 1/3 -100000000)"),
+            Err(EvaluatorError::InvalidArgument(_))
+        ));
+        assert_eq!(eval("(^ -1 100000000001)"), int(-1));
+        assert!(matches!(
+            eval("(^ 0 -100000000001)"),
+            Err(EvaluatorError::DivisionByZero(_))
+        ));
+    }
+}
+
+#[cfg(test)]
+mod test_bignum {
+    use crate::{
+        test_util::{display, eval},
+        types::{Cons, ConsValue},
+    };
+
+    #[test]
+    fn test_bignum_promotion() {
+        assert_eq!(display("(^ 2 100)"), "1267650600228229401496703205376");
+        assert_eq!(display("(+ 9223372036854775807 1)"), "9223372036854775808");
+        assert_eq!(
+            display("(* -9223372036854775808 -1)"),
+            "9223372036854775808"
+        );
+        assert_eq!(display("(- -9223372036854775808)"), "9223372036854775808");
+    }
+
+    #[test]
+    fn test_bignum_demotion() {
+        assert_eq!(
+            eval("(- (+ 9223372036854775807 1) 1)"),
+            Ok(Cons::Value(ConsValue::Int(i64::MAX)))
+        );
+        assert_eq!(
+            eval("(/ (^ 2 100) (^ 2 98))"),
+            Ok(Cons::Value(ConsValue::Int(4)))
+        );
+    }
+
+    #[test]
+    fn test_bignum_exact_small_values() {
+        assert_eq!(
+            eval("(+ 9007199254740993 0)"),
+            Ok(Cons::Value(ConsValue::Int(9007199254740993)))
+        );
+        assert_eq!(
+            eval("(* 3037000499 3037000499)"),
+            Ok(Cons::Value(ConsValue::Int(9223372030926249001)))
+        );
+    }
+
+    #[test]
+    fn test_bignum_factorial() {
+        let factors: Vec<String> = (1..=50).map(|i| i.to_string()).collect();
+        assert_eq!(
+            display(&format!("(* {})", factors.join(" "))),
+            "30414093201713378043612608166064768844377641568960512000000000000"
+        );
+    }
+
+    #[test]
+    fn test_bignum_literals() {
+        assert_eq!(
+            display("123456789012345678901234567890"),
+            "123456789012345678901234567890"
+        );
+        assert_eq!(
+            eval("(= 100000000000000000000 (* 10000000000 10000000000))"),
+            Ok(Cons::Value(ConsValue::Boolean(true)))
+        );
+        assert_eq!(
+            eval("(< 100000000000000000000 100000000000000000001)"),
+            Ok(Cons::Value(ConsValue::Boolean(true)))
+        );
+        assert_eq!(
+            eval("(remainder 100000000000000000001 7)"),
+            Ok(Cons::Value(ConsValue::Int(3)))
+        );
+    }
+}
+
+#[cfg(test)]
+mod test_rational {
+    use crate::{
+        errors::EvaluatorError,
+        test_util::{display, eval},
+        types::{Cons, ConsValue},
+    };
+
+    #[test]
+    fn test_rational_division() {
+        assert_eq!(display("(/ 1 3)"), "1/3");
+        assert_eq!(display("(/ 6 4)"), "3/2");
+        assert_eq!(display("(/ -6 4)"), "-3/2");
+        assert_eq!(display("(/ 6 -4)"), "-3/2");
+        assert_eq!(display("(/ 4)"), "1/4");
+        assert_eq!(eval("(* 100 (/ 1 3))").unwrap().to_string(), "100/3");
+        assert_eq!(eval("(* 3 (/ 1 3))"), Ok(Cons::Value(ConsValue::Int(1))));
+        assert!(matches!(
+            eval("(/ 1 0)"),
+            Err(EvaluatorError::DivisionByZero(_))
+        ));
+    }
+
+    #[test]
+    fn test_rational_reader() {
+        assert_eq!(display("1/3"), "1/3");
+        assert_eq!(display("-2/4"), "-1/2");
+        assert_eq!(eval("4/2"), Ok(Cons::Value(ConsValue::Int(2))));
+        assert!(eval("1/0").is_err());
+        assert_eq!(display("(string->number \"6/8\")"), "3/4");
+        assert_eq!(
+            eval("(number->string 3/4)"),
+            Ok(Cons::Value(ConsValue::String("3/4".to_string())))
+        );
+    }
+
+    #[test]
+    fn test_rational_arithmetic() {
+        assert_eq!(display("(+ 1/3 1/6)"), "1/2");
+        assert_eq!(display("(- 1/2 1/3)"), "1/6");
+        assert_eq!(display("(^ 2/3 2)"), "4/9");
+        assert_eq!(display("(^ 2 -2)"), "1/4");
+        assert_eq!(display("(+ 1/2 0.25)"), "0.75");
+        assert_eq!(display("(abs -1/2)"), "1/2");
+        assert_eq!(display("(max 1/3 1/4)"), "1/3");
+    }
+
+    #[test]
+    fn test_rational_comparison() {
+        let t = Ok(Cons::Value(ConsValue::Boolean(true)));
+        assert_eq!(eval("(< 1/3 1/2 1)"), t);
+        assert_eq!(eval("(>= 1/2 0.5)"), t);
+        assert_eq!(eval("(= 1/2 2/4)"), t);
+    }
+
+    #[test]
+    fn test_rational_rounding() {
+        assert_eq!(display("(floor 7/2)"), "3");
+        assert_eq!(display("(ceiling 7/2)"), "4");
+        assert_eq!(display("(round 5/2)"), "2");
+        assert_eq!(display("(round 7/2)"), "4");
+        assert_eq!(display("(truncate -7/2)"), "-3");
+    }
+
+    #[test]
+    fn test_rational_accessors() {
+        assert_eq!(display("(numerator 6/4)"), "3");
+        assert_eq!(display("(denominator 6/4)"), "2");
+        assert_eq!(display("(denominator -1/2)"), "2");
+        assert_eq!(display("(numerator 5)"), "5");
+        assert_eq!(display("(denominator 5)"), "1");
+        assert_eq!(display("(denominator 0.5)"), "2.0");
+    }
+}
+
+#[cfg(test)]
+mod test_equality {
+    use crate::{
+        errors::EvaluatorError,
+        test_util::{boolean, eval},
+    };
+
+    #[test]
+    fn test_equality_numeric() {
+        assert_eq!(eval("(= 1 1.0)"), boolean(true));
+        assert_eq!(eval("(= 1/2 0.5 2/4)"), boolean(true));
+        assert_eq!(eval("(= 1 2)"), boolean(false));
+        assert_eq!(eval("(!= 1 1.0)"), boolean(false));
+        assert!(matches!(
+            eval("(= \"a\" \"a\")"),
+            Err(EvaluatorError::InvalidArgument(_))
+        ));
+    }
+
+    #[test]
+    fn test_equality_eq_identity() {
+        assert_eq!(eval("(define xs '(1 2)) (eq? xs xs)"), boolean(true));
+        assert_eq!(eval("(eq? '(1 2) '(1 2))"), boolean(false));
+        assert_eq!(eval("(eq? 'a 'a)"), boolean(true));
+        assert_eq!(eval("(eq? '() '())"), boolean(true));
+    }
+
+    #[test]
+    fn test_equality_eqv_exactness() {
+        assert_eq!(eval("(eqv? 2 2)"), boolean(true));
+        assert_eq!(eval("(eqv? 2 2.0)"), boolean(false));
+        assert_eq!(
+            eval("(eqv? 100000000000000000000 (* 10000000000 10000000000))"),
+            boolean(true)
+        );
+        assert_eq!(eval("(eqv? 0.0 (- 0.0))"), boolean(false));
+        assert_eq!(eval("(eqv? \"ab\" \"ab\")"), boolean(true));
+    }
+
+    #[test]
+    fn test_equality_equal_structure() {
+        assert_eq!(eval("(equal? '(1 (2 3)) '(1 (2 3)))"), boolean(true));
+        assert_eq!(eval("(equal? '(1 2) '(1 2.0))"), boolean(false));
+        assert_eq!(eval("(equal? '(1 2) '(1 2 3))"), boolean(false));
+        assert!(matches!(
+            eval("(equal? 1)"),
+            Err(EvaluatorError::InvalidArgument(_))
+        ));
+    }
+}
+
+#[cfg(test)]
+mod test_order {
+    use crate::{
+        errors::EvaluatorError,
+        test_util::{boolean, eval, int},
+        types::{Cons, ConsValue},
+    };
+
+    #[test]
+    fn test_order_strings_and_symbols() {
+        assert_eq!(eval("(< \"a\" \"b\" \"c\")"), boolean(true));
+        assert_eq!(eval("(>= \"b\" \"b\" \"a\")"), boolean(true));
+        assert_eq!(eval("(< 'apple 'banana)"), boolean(true));
+        assert_eq!(eval("(> 'apple 'banana)"), boolean(false));
+        assert!(matches!(
+            eval("(< \"a\" 1)"),
+            Err(EvaluatorError::InvalidArgument(_))
+        ));
+    }
+
+    #[test]
+    fn test_order_string_ci() {
+        assert_eq!(eval("(string-ci=? \"Hello\" \"hELLO\")"), boolean(true));
+        assert_eq!(eval("(string-ci<? \"apple\" \"Banana\")"), boolean(true));
+        assert_eq!(eval("(string<? \"apple\" \"Banana\")"), boolean(false));
+        assert_eq!(eval("(string-ci>=? \"B\" \"a\" \"A\")"), boolean(true));
+    }
+
+    #[test]
+    fn test_order_compare() {
+        assert_eq!(eval("(compare 1 2)"), int(-1));
+        assert_eq!(eval("(compare \"b\" \"a\")"), int(1));
+        assert_eq!(eval("(compare 1 \"a\")"), int(-1));
+        assert_eq!(eval("(compare 1 1.0)"), int(-1));
+        assert_eq!(eval("(compare '(1 2) '(1 2))"), int(0));
+    }
+
+    #[test]
+    fn test_order_sort() {
+        assert_eq!(
+            eval("(sort '(3 \"b\" 1 \"a\" 2.5))").map(|c| c.to_string()),
+            Ok("(1 2.5 3 \"a\" \"b\")".to_string())
+        );
+        assert_eq!(eval("(sort '())"), Ok(Cons::Value(ConsValue::NIL)));
+        assert!(matches!(
+            eval("(sort 1)"),
+            Err(EvaluatorError::InvalidArgument(_))
+        ));
+    }
+}
+
+#[cfg(test)]
+mod test_apply {
+    use crate::{
+        errors::EvaluatorError,
+        test_util::{eval, int},
+        types::{Cons, ConsValue},
+    };
+
+    #[test]
+    fn test_apply_builtin() {
+        assert_eq!(eval("(apply + '(1 2 3))"), int(6));
+        assert_eq!(eval("(apply + 1 2 '(3 4))"), int(10));
+        assert_eq!(eval("(apply + '())"), int(0));
+        assert_eq!(
+            eval("(apply string-append \"a\" '(\"b\" \"c\"))"),
+            Ok(Cons::Value(ConsValue::String("abc".to_string())))
+        );
+    }
+
+    #[test]
+    fn test_apply_user_function() {
+        assert_eq!(
+            eval("(define (add3 a b c) (+ a b c)) (apply add3 1 '(2 3))"),
+            int(6)
+        );
+        assert_eq!(eval("(apply (lambda (x y) (- x y)) '(10 4))"), int(6));
+        assert_eq!(
+            eval("(define (pair x y) y) (apply pair '(1 (a b)))").map(|c| c.to_string()),
+            Ok("(a b)".to_string())
+        );
+    }
+
+    #[test]
+    fn test_higher_order_builtins_as_values() {
+        assert_eq!(
+            eval("(apply vector-map (lambda (x) (* x 10)) '(#(1 2)))").map(|c| c.to_string()),
+            Ok("#(10 20)".to_string())
+        );
+        assert_eq!(
+            eval("(apply call/cc (vector->list (vector (lambda (k) (k 42)))))"),
+            int(42)
+        );
+        assert_eq!(eval("(apply apply + '((1 2)))"), int(3));
+        let src = "(define (map-with f s) (stream->list (f (lambda (x) (+ x 1)) s)))
+                   (map-with stream-map (stream-cons 1 (stream-cons 2 '())))";
+        assert_eq!(eval(src).map(|c| c.to_string()), Ok("(2 3)".to_string()));
+        assert_eq!(
+            eval("vector-map").map(|c| c.to_string()),
+            Ok("#<procedure vector-map>".to_string())
+        );
+    }
+
+    #[test]
+    fn test_apply_errors() {
+        assert!(matches!(
+            eval("(apply + 1 2)"),
+            Err(EvaluatorError::InvalidArgument(_))
+        ));
+        assert!(matches!(
+            eval("(apply +)"),
+            Err(EvaluatorError::InvalidArgument(_))
+        ));
+        assert!(matches!(
+            eval("(apply 1 '(2 3))"),
+            Err(EvaluatorError::NotAFunction(_))
+        ));
+        assert!(matches!(
+            eval("(define (add3 a b c) (+ a b c)) (apply add3 '(1 2))"),
+            Err(EvaluatorError::InvalidArgument(_))
+        ));
+    }
+
+    #[test]
+    fn test_deep_nesting() {
+        // The baseline evaluator handled this depth on a main-thread-sized
+        // stack; procedure values must not make each level more expensive.
+        let depth = 400;
+        let src = format!("{}0{}", "(+ 1 ".repeat(depth), ")".repeat(depth));
+        let result = std::thread::Builder::new()
+            .stack_size(8 * 1024 * 1024)
+            .spawn(move || eval(&src))
+            .unwrap()
+            .join()
+            .unwrap();
+        assert_eq!(result, int(depth as i64));
+    }
+}
+
+#[cfg(test)]
+mod test_hash_table {
+    use crate::{
+        errors::EvaluatorError,
+        test_util::{boolean, eval, eval_string, int},
+        types::{Cons, ConsValue},
+    };
+
+    const TABLE: &str = "(define h (make-hash-table)) (hash-set! h 'a 1) (hash-set! h \"b\" 2) ";
+
+    #[test]
+    fn test_hash_table_ref() {
+        assert_eq!(eval(&format!("{}(hash-ref h 'a)", TABLE)), int(1));
+        assert_eq!(eval(&format!("{}(hash-ref h \"b\")", TABLE)), int(2));
+        assert_eq!(eval(&format!("{}(hash-ref h 'c 0)", TABLE)), int(0));
+        assert!(matches!(
+            eval(&format!("{}(hash-ref h 'c)", TABLE)),
+            Err(EvaluatorError::InvalidArgument(_))
+        ));
+        assert!(matches!(
+            eval("(hash-ref '(a 1) 'a)"),
+            Err(EvaluatorError::InvalidArgument(_))
+        ));
+    }
+
+    #[test]
+    fn test_hash_table_update() {
+        assert_eq!(
+            eval(&format!("{}(hash-set! h 'a 10) (hash-ref h 'a)", TABLE)),
+            int(10)
+        );
+        assert_eq!(eval(&format!("{}(hash-count h)", TABLE)), int(2));
+        assert_eq!(
+            eval(&format!(
+                "{}(hash-delete! h 'a) (hash-contains? h 'a)",
+                TABLE
+            )),
+            boolean(false)
+        );
+        assert_eq!(
+            eval(&format!("{}(hash-delete! h 'x) (hash-count h)", TABLE)),
+            int(2)
+        );
+        assert_eq!(
+            eval(&format!("{}(hash-contains? h \"b\")", TABLE)),
+            boolean(true)
+        );
+    }
+
+    #[test]
+    fn test_hash_table_keys_are_equal() {
+        assert_eq!(
+            eval("(define h (make-hash-table)) (hash-set! h '(1 2) 'x) (hash-ref h '(1 2))")
+                .map(|c| c.to_string()),
+            Ok("x".to_string())
+        );
+        // Exactness matters for numbers, like eqv?.
+        assert_eq!(
+            eval("(define h (make-hash-table)) (hash-set! h 1 'x) (hash-contains? h 1.0)"),
+            boolean(false)
+        );
+        assert_eq!(
+            eval("(define h (make-hash-table)) (hash-set! h 0.5 'x) (hash-contains? h 0.5)"),
+            boolean(true)
+        );
+        assert_eq!(
+            eval("(define h (make-hash-table)) (hash-set! h 1/2 'x) (hash-contains? h 2/4)"),
+            boolean(true)
+        );
+    }
+
+    #[test]
+    fn test_hash_table_listing() {
+        assert_eq!(
+            eval_string(&format!("{}(hash-keys h)", TABLE)),
+            Ok("(a \"b\")".to_string())
+        );
+        assert_eq!(
+            eval_string(&format!("{}(hash-values h)", TABLE)),
+            Ok("(1 2)".to_string())
+        );
+        assert_eq!(
+            eval_string(&format!("{}(hash->alist h)", TABLE)),
+            Ok("((a 1) (\"b\" 2))".to_string())
+        );
+        assert_eq!(
+            eval_string(&format!("{}h", TABLE)),
+            Ok("#hash((a 1) (\"b\" 2))".to_string())
+        );
+        assert_eq!(
+            eval_string("(define h (make-hash-table)) (hash-set! h 'self h) h"),
+            Ok("#hash((self #hash(...)))".to_string())
+        );
+    }
+
+    #[test]
+    fn test_hash_table_for_each() {
+        assert_eq!(
+            eval(&format!(
+                "{}(define out (make-hash-table)) \
+                 (hash-for-each h (lambda (k v) (hash-set! out v k))) \
+                 (hash-ref out 2)",
+                TABLE
+            )),
+            Ok(Cons::Value(ConsValue::String("b".to_string())))
+        );
+        assert_eq!(eval("(is-hash-table (make-hash-table))"), boolean(true));
+    }
+}
+
+#[cfg(test)]
+mod test_vector {
+    use crate::{
+        errors::EvaluatorError,
+        test_util::{eval, eval_string, int},
+        types::{Cons, ConsValue},
+    };
+
+    #[test]
+    fn test_vector_literal() {
+        assert_eq!(eval_string("#(1 2 3)"), Ok("#(1 2 3)".to_string()));
+        assert_eq!(eval_string("#()"), Ok("#()".to_string()));
+        // Elements are not evaluated.
+        assert_eq!(eval_string("#(a (+ 1 2))"), Ok("#(a (+ 1 2))".to_string()));
+        assert_eq!(eval("(vector-ref #(1 2 3) 1)"), int(2));
+    }
+
+    #[test]
+    fn test_vector_construction() {
+        assert_eq!(
+            eval_string("(vector 1 (+ 1 1) \"c\")"),
+            Ok("#(1 2 \"c\")".to_string())
+        );
+        assert_eq!(eval_string("(make-vector 3 0)"), Ok("#(0 0 0)".to_string()));
+        assert_eq!(eval("(vector-length (make-vector 5))"), int(5));
+        assert_eq!(
+            eval_string("(list->vector '(1 2))"),
+            Ok("#(1 2)".to_string())
+        );
+        assert_eq!(
+            eval_string("(vector->list #(1 2))"),
+            Ok("(1 2)".to_string())
+        );
+    }
+
+    #[test]
+    fn test_vector_mutation() {
+        assert_eq!(
+            eval_string("(define v (vector 1 2 3)) (vector-set! v 0 'x) v"),
+            Ok("#(x 2 3)".to_string())
+        );
+        assert_eq!(
+            eval_string("(define v (vector 1 2 3)) (vector-fill! v 7) v"),
+            Ok("#(7 7 7)".to_string())
+        );
+    }
+
+    #[test]
+    fn test_vector_literal_is_copied() {
+        // Mutating the value of a literal must not change the program.
+        let src =
+            "(define (fresh) #(0)) (vector-set! (fresh) 0 9) (vector-fill! (fresh) 7) (fresh)";
+        assert_eq!(eval_string(src), Ok("#(0)".to_string()));
+    }
+
+    #[test]
+    fn test_vector_out_of_range() {
+        assert!(matches!(
+            eval("(vector-ref #(1 2 3) 3)"),
+            Err(EvaluatorError::InvalidArgument(_))
+        ));
+        assert!(matches!(
+            eval("(vector-ref #(1 2 3) -1)"),
+            Err(EvaluatorError::InvalidArgument(_))
+        ));
+        assert!(matches!(
+            eval("(vector-set! (vector) 0 1)"),
+            Err(EvaluatorError::InvalidArgument(_))
+        ));
+        assert!(matches!(
+            eval("(subvector #(1 2 3) 2 5)"),
+            Err(EvaluatorError::InvalidArgument(_))
+        ));
+    }
+
+    #[test]
+    fn test_vector_subvector_and_map() {
+        assert_eq!(
+            eval_string("(subvector #(1 2 3 4) 1 3)"),
+            Ok("#(2 3)".to_string())
+        );
+        assert_eq!(
+            eval_string("(subvector #(1 2 3 4) 2)"),
+            Ok("#(3 4)".to_string())
+        );
+        assert_eq!(
+            eval_string("(vector-map (lambda (x) (* x x)) #(1 2 3))"),
+            Ok("#(1 4 9)".to_string())
+        );
+        assert_eq!(
+            eval_string("(vector-map - #(1 2))"),
+            Ok("#(-1 -2)".to_string())
+        );
+    }
+
+    #[test]
+    fn test_vector_equality() {
+        assert_eq!(
+            eval("(equal? #(1 (2)) (vector 1 '(2)))"),
+            Ok(Cons::Value(ConsValue::Boolean(true)))
+        );
+        assert_eq!(
+            eval("(eqv? #(1) #(1))"),
+            Ok(Cons::Value(ConsValue::Boolean(false)))
+        );
+        assert_eq!(eval("(compare #(1 2) #(1 3))"), int(-1));
+    }
+}
+
+#[cfg(test)]
+mod test_char {
+    use crate::{
+        errors::EvaluatorError,
+        test_util::{boolean, character, eval},
+        types::{Cons, ConsValue},
+    };
+
+    #[test]
+    fn test_char_literals() {
+        assert_eq!(eval("#\\a"), character('a'));
+        assert_eq!(eval("#\\space"), character(' '));
+        assert_eq!(eval("#\\newline"), character('\n'));
+        assert_eq!(eval("#\\x41"), character('A'));
+        assert_eq!(eval("#\\x"), character('x'));
+        assert_eq!(eval("#\\("), character('('));
+        assert_eq!(eval("#\\λ"), character('λ'));
+        assert!(eval("#\\bogus").is_err());
+    }
+
+    #[test]
+    fn test_char_display() {
+        let show = |src: &str| eval(src).map(|c| c.to_string());
+        assert_eq!(show("#\\a"), Ok("#\\a".to_string()));
+        assert_eq!(show("#\\x20"), Ok("#\\space".to_string()));
+        assert_eq!(show("(integer->char 1)"), Ok("#\\x1".to_string()));
+        assert_eq!(
+            eval("(list->string '(#\\a #\\b))"),
+            Ok(Cons::Value(ConsValue::String("ab".to_string())))
+        );
+    }
+
+    #[test]
+    fn test_char_conversions() {
+        assert_eq!(
+            eval("(char->integer #\\A)"),
+            Ok(Cons::Value(ConsValue::Int(65)))
+        );
+        assert_eq!(eval("(integer->char 955)"), character('λ'));
+        assert!(matches!(
+            eval("(integer->char 55296)"),
+            Err(EvaluatorError::InvalidArgument(_))
+        ));
+        assert_eq!(eval("(char-upcase #\\a)"), character('A'));
+        assert_eq!(eval("(char-downcase #\\A)"), character('a'));
+        assert_eq!(eval("(char-upcase #\\1)"), character('1'));
+    }
+
+    #[test]
+    fn test_char_predicates() {
+        assert_eq!(eval("(char-alphabetic? #\\a)"), boolean(true));
+        assert_eq!(eval("(char-alphabetic? #\\1)"), boolean(false));
+        assert_eq!(eval("(char-numeric? #\\1)"), boolean(true));
+        assert_eq!(eval("(char-whitespace? #\\tab)"), boolean(true));
+        assert_eq!(eval("(is-char #\\a)"), boolean(true));
+        assert_eq!(eval("(is-char \"a\")"), boolean(false));
+    }
+
+    #[test]
+    fn test_char_comparisons() {
+        assert_eq!(eval("(char<? #\\a #\\b #\\c)"), boolean(true));
+        assert_eq!(eval("(char=? #\\a #\\A)"), boolean(false));
+        assert_eq!(eval("(char-ci=? #\\a #\\A)"), boolean(true));
+        assert_eq!(eval("(< #\\a #\\b)"), boolean(true));
+        assert!(matches!(
+            eval("(char<? #\\a \"b\")"),
+            Err(EvaluatorError::InvalidArgument(_))
+        ));
+    }
+
+    #[test]
+    fn test_string_ref() {
+        assert_eq!(eval("(string-ref \"héllo\" 1)"), character('é'));
+        assert!(matches!(
+            eval("(string-ref \"abc\" 3)"),
+            Err(EvaluatorError::InvalidArgument(_))
+        ));
+    }
+}
+
+#[cfg(test)]
+mod test_bytevector {
+    use crate::{
+        errors::EvaluatorError,
+        test_util::{eval, eval_string, int},
+        types::{Cons, ConsValue},
+    };
+
+    fn invalid(result: Result<Cons, EvaluatorError>) -> bool {
+        matches!(result, Err(EvaluatorError::InvalidArgument(_)))
+    }
+
+    #[test]
+    fn test_bytevector_literal() {
+        assert_eq!(eval_string("#u8(1 2 255)"), Ok("#u8(1 2 255)".to_string()));
+        assert_eq!(eval_string("#u8()"), Ok("#u8()".to_string()));
+        assert!(eval("#u8(256)").is_err());
+        assert!(eval("#u8(a)").is_err());
+        let src = "(define (fresh) #u8(0)) (bytevector-u8-set! (fresh) 0 9) (fresh)";
+        assert_eq!(eval_string(src), Ok("#u8(0)".to_string()));
+    }
+
+    #[test]
+    fn test_bytevector_access() {
+        assert_eq!(eval("(bytevector-u8-ref #u8(1 2 3) 2)"), int(3));
+        assert_eq!(eval("(bytevector-length (make-bytevector 4))"), int(4));
+        assert_eq!(
+            eval_string("(define b (make-bytevector 3 7)) (bytevector-u8-set! b 1 0) b"),
+            Ok("#u8(7 0 7)".to_string())
+        );
+        assert!(invalid(eval("(bytevector-u8-ref #u8(1) 1)")));
+        assert!(invalid(eval("(bytevector-u8-set! #u8(1) 0 256)")));
+        assert!(invalid(eval("(bytevector 1 -1)")));
+    }
+
+    #[test]
+    fn test_bytevector_copy_append() {
+        assert_eq!(
+            eval_string("(bytevector-copy #u8(1 2 3 4) 1 3)"),
+            Ok("#u8(2 3)".to_string())
+        );
+        assert_eq!(
+            eval_string("(bytevector-append #u8(1) (bytevector 2 3) #u8())"),
+            Ok("#u8(1 2 3)".to_string())
+        );
+        assert!(invalid(eval("(bytevector-copy #u8(1 2) 1 3)")));
+    }
+
+    #[test]
+    fn test_bytevector_utf8() {
+        assert_eq!(
+            eval_string("(string->utf8 \"hé\")"),
+            Ok("#u8(104 195 169)".to_string())
+        );
+        assert_eq!(
+            eval("(utf8->string #u8(104 195 169))"),
+            Ok(Cons::Value(ConsValue::String("hé".to_string())))
+        );
+        assert!(invalid(eval("(utf8->string #u8(255))")));
+    }
+
+    #[test]
+    fn test_bytevector_integers() {
+        assert_eq!(eval("(bytevector-uint-ref #u8(1 2) 0 'big 2)"), int(258));
+        assert_eq!(eval("(bytevector-uint-ref #u8(1 2) 0 'little 2)"), int(513));
+        assert_eq!(eval("(bytevector-sint-ref #u8(255 254) 0 'big 2)"), int(-2));
+        assert_eq!(
+            eval("(bytevector-uint-ref #u8(255 254) 0 'big 2)"),
+            int(65534)
+        );
+        assert_eq!(
+            eval_string("(define b (make-bytevector 4)) (bytevector-uint-set! b 0 258 'big 4) b"),
+            Ok("#u8(0 0 1 2)".to_string())
+        );
+        assert_eq!(
+            eval_string("(define b (make-bytevector 2)) (bytevector-sint-set! b 0 -2 'little 2) b"),
+            Ok("#u8(254 255)".to_string())
+        );
+        assert!(invalid(eval(
+            "(bytevector-uint-set! (make-bytevector 1) 0 256 'big 1)"
+        )));
+        assert!(invalid(eval(
+            "(bytevector-sint-set! (make-bytevector 1) 0 128 'big 1)"
+        )));
+        assert!(invalid(eval(
+            "(bytevector-uint-set! (make-bytevector 1) 0 -1 'big 1)"
+        )));
+        assert!(invalid(eval("(bytevector-uint-ref #u8(1) 0 'middle 1)")));
+        assert!(invalid(eval("(bytevector-uint-ref #u8(1) 0 'big 2)")));
+        // Huge sizes are rejected before anything is allocated.
+        assert!(invalid(eval(
+            "(bytevector-uint-set! (make-bytevector 4 0) 0 1 'big 100000000000)"
+        )));
+        assert!(invalid(eval(
+            "(bytevector-sint-ref (make-bytevector 4 0) 1 'big 100000000000)"
+        )));
+    }
+
+    #[test]
+    fn test_bytevector_equality() {
+        assert_eq!(
+            eval("(equal? #u8(1 2) (bytevector 1 2))"),
+            Ok(Cons::Value(ConsValue::Boolean(true)))
+        );
+        assert_eq!(
+            eval("(eqv? #u8(1 2) #u8(1 2))"),
+            Ok(Cons::Value(ConsValue::Boolean(false)))
+        );
+    }
+}
+
+#[cfg(test)]
+mod test_symbol {
+    use crate::{
+        test_util::{boolean, eval},
+        types::{Cons, ConsValue, symbol::Symbol},
+    };
+
+    #[test]
+    fn test_symbol_interning() {
+        assert_eq!(Symbol::new("abc"), Symbol::new("abc"));
+        assert_ne!(Symbol::new("abc"), Symbol::new("abd"));
+        assert_ne!(Symbol::uninterned("abc"), Symbol::new("abc"));
+        assert!(Symbol::new("abc").is_interned());
+        assert!(!Symbol::uninterned("abc").is_interned());
+        assert_eq!(eval("(eq? 'abc (string->symbol \"abc\"))"), boolean(true));
+    }
+
+    #[test]
+    fn test_symbol_uninterned() {
+        assert_eq!(
+            eval("(eq? 'abc (string->uninterned-symbol \"abc\"))"),
+            boolean(false)
+        );
+        assert_eq!(
+            eval("(symbol->string (string->uninterned-symbol \"abc\"))"),
+            Ok(Cons::Value(ConsValue::String("abc".to_string())))
+        );
+        assert_eq!(
+            eval("(symbol-interned? (string->uninterned-symbol \"abc\"))"),
+            boolean(false)
+        );
+        assert_eq!(eval("(symbol-interned? 'abc)"), boolean(true));
+    }
+
+    #[test]
+    fn test_gensym() {
+        assert_eq!(eval("(define g (gensym)) (eq? g g)"), boolean(true));
+        assert_eq!(eval("(eq? (gensym) (gensym))"), boolean(false));
+        assert_eq!(eval("(symbol-interned? (gensym))"), boolean(false));
+        let name = eval("(symbol->string (gensym \"tmp\"))").unwrap();
+        assert!(matches!(name, Cons::Value(ConsValue::String(s)) if s.starts_with("tmp")));
+        // A gensym never names a variable or builtin that was read in.
+        assert_eq!(
+            eval(
+                "(define h (make-hash-table)) (hash-set! h (gensym \"+\") 1) (hash-contains? h '+0)"
+            ),
+            boolean(false)
+        );
+    }
+}
+
+#[cfg(test)]
+mod test_promise {
+    use crate::{
+        test_util::{eval, eval_string, int},
+        types::{Cons, ConsValue},
+    };
+
+    #[test]
+    fn test_force_memoizes() {
+        assert_eq!(eval("(force (delay (+ 1 2)))"), int(3));
+        let src = "(define out (open-output-string))
+                   (define p (delay (format out \"forced \")))
+                   (force p)
+                   (force p)
+                   (get-output-string out)";
+        assert_eq!(eval_string(src), Ok("\"forced \"".to_string()));
+        // The expression is not evaluated until forced.
+        let src = "(define out (open-output-string))
+                   (define p (delay (format out \"forced \")))
+                   (get-output-string out)";
+        assert_eq!(eval_string(src), Ok("\"\"".to_string()));
+    }
+
+    #[test]
+    fn test_make_promise() {
+        assert_eq!(eval("(force (make-promise 5))"), int(5));
+        assert_eq!(
+            eval("(define p (delay 1)) (eq? p (make-promise p))"),
+            Ok(Cons::Value(ConsValue::Boolean(true)))
+        );
+        // Forcing a value that is not a promise returns it.
+        assert_eq!(eval("(force 4)"), int(4));
+        assert_eq!(
+            eval("(is-promise (delay 1))"),
+            Ok(Cons::Value(ConsValue::Boolean(true)))
+        );
+        assert_eq!(
+            eval("(is-promise 1)"),
+            Ok(Cons::Value(ConsValue::Boolean(false)))
+        );
+    }
+
+    #[test]
+    fn test_delay_force() {
+        assert_eq!(eval("(force (delay-force (delay (* 6 7))))"), int(42));
+        assert_eq!(
+            eval("(force (delay-force (delay-force (delay-force (delay 1)))))"),
+            int(1)
+        );
+        let src = "(define out (open-output-string))
+                   (define inner (delay (format out \"inner \")))
+                   (define outer (delay-force inner))
+                   (force outer)
+                   (force inner)
+                   (get-output-string out)";
+        assert_eq!(eval_string(src), Ok("\"inner \"".to_string()));
+    }
+
+    #[test]
+    fn test_stream_basics() {
+        let src = "(define (ints n) (stream-cons n (ints (+ n 1))))
+                   (define nat (ints 0))";
+        assert_eq!(
+            eval_string(&format!("{} (stream->list nat 5)", src)),
+            Ok("(0 1 2 3 4)".to_string())
+        );
+        assert_eq!(
+            eval(&format!(
+                "{} (stream-car (stream-cdr (stream-cdr nat)))",
+                src
+            )),
+            int(2)
+        );
+        assert_eq!(
+            eval_string(&format!("{} (stream->list (stream-take 3 nat))", src)),
+            Ok("(0 1 2)".to_string())
+        );
+        assert_eq!(
+            eval_string("(stream->list (stream-cons 1 (stream-cons 2 '())))"),
+            Ok("(1 2)".to_string())
+        );
+        assert!(eval("(stream-car '())").is_err());
+        assert!(eval("(stream-cdr '(1 2))").is_err());
+    }
+
+    #[test]
+    fn test_stream_map_filter() {
+        let src = "(define (ints n) (stream-cons n (ints (+ n 1))))
+                   (define nat (ints 0))";
+        assert_eq!(
+            eval_string(&format!(
+                "{} (stream->list (stream-map (lambda (x) (* x x)) nat) 4)",
+                src
+            )),
+            Ok("(0 1 4 9)".to_string())
+        );
+        assert_eq!(
+            eval_string(&format!(
+                "{} (stream->list (stream-filter (lambda (x) (= (% x 3) 0)) nat) 4)",
+                src
+            )),
+            Ok("(0 3 6 9)".to_string())
+        );
+        assert_eq!(
+            eval_string(&format!(
+                "{} (stream->list (stream-map - (stream-take 3 nat)))",
+                src
+            )),
+            Ok("(0 -1 -2)".to_string())
+        );
+    }
+
+    #[test]
+    fn test_stream_laziness() {
+        // Only the elements that are asked for are computed.
+        let src = "(define out (open-output-string))
+                   (define (ints n) (stream-cons (format out \"~a \" n) (ints (+ n 1))))
+                   (stream->list (stream-take 3 (ints 0)))
+                   (get-output-string out)";
+        assert_eq!(eval_string(src), Ok("\"0 1 2 \"".to_string()));
+    }
+}
+
+#[cfg(test)]
+mod test_continuation {
+    use crate::{
+        errors::EvaluatorError,
+        loader::eval_source,
+        test_util::{eval, eval_string, int},
+        types::{Cons, ConsValue, scope::LexicalVarStorage},
+    };
+
+    #[test]
+    fn test_call_cc_escape() {
+        assert_eq!(eval("(+ 1 (call/cc (lambda (k) (+ 10 (k 5)))))"), int(6));
+        assert_eq!(
+            eval("(call-with-current-continuation (lambda (k) 3))"),
+            int(3)
+        );
+        assert_eq!(
+            eval_string("(call/cc (lambda (k) (k)))"),
+            Ok("'()".to_string())
+        );
+        // Escaping out of a search part way through.
+        assert_eq!(
+            eval("(call/cc (lambda (found) (vector-map (lambda (x) (found x)) #(7 8))))"),
+            int(7)
+        );
+        assert!(eval("(call/cc (lambda (k) (k 1 2)))").is_err());
+    }
+
+    #[test]
+    fn test_call_cc_nested() {
+        // An inner continuation only unwinds to its own call/cc.
+        let src = "(+ 100 (call/cc (lambda (outer)
+                      (+ 10 (call/cc (lambda (inner) (inner 1)))))))";
+        assert_eq!(eval(src), int(111));
+        let src = "(+ 100 (call/cc (lambda (outer)
+                      (+ 10 (call/cc (lambda (inner) (outer 1)))))))";
+        assert_eq!(eval(src), int(101));
+    }
+
+    #[test]
+    fn test_call_cc_escape_only() {
+        assert_eq!(
+            eval_string("(define k (call/cc (lambda (k) k))) k"),
+            Ok("#<continuation>".to_string())
+        );
+        assert!(matches!(
+            eval("(define k (call/cc (lambda (k) k))) (k 1)"),
+            Err(EvaluatorError::UncallableType(_))
+        ));
+    }
+
+    #[test]
+    fn test_dynamic_wind() {
+        let src = "(define out (open-output-string))
+                   (define result (dynamic-wind
+                     (lambda '() (format out \"before \"))
+                     (lambda '() (format out \"during \") 42)
+                     (lambda '() (format out \"after\"))))
+                   (format #f \"~a ~a\" result (get-output-string out))";
+        assert_eq!(
+            eval_string(src),
+            Ok("\"42 before during after\"".to_string())
+        );
+    }
+
+    #[test]
+    fn test_dynamic_wind_escape() {
+        let src = "(define out (open-output-string))
+                   (define result (call/cc (lambda (k) (dynamic-wind
+                     (lambda '() (format out \"before \"))
+                     (lambda '() (k 9) (format out \"unreached \"))
+                     (lambda '() (format out \"after\"))))))
+                   (format #f \"~a ~a\" result (get-output-string out))";
+        assert_eq!(eval_string(src), Ok("\"9 before after\"".to_string()));
+        // after also runs when thunk fails.
+        let src = "(define out (open-output-string))
+                   (dynamic-wind
+                     (lambda '() (format out \"before \"))
+                     (lambda '() (+ 1 \"a\"))
+                     (lambda '() (format out \"after\")))";
+        let mut stg = LexicalVarStorage::new();
+        assert!(eval_source(src, &mut stg).is_err());
+        assert_eq!(
+            eval_source("(get-output-string out)", &mut stg),
+            Ok(Cons::Value(ConsValue::String("before after".to_string())))
+        );
+    }
+
+    #[test]
+    fn test_define_value() {
+        // define binds the value of its expression rather than calling it.
+        assert_eq!(eval("(define sq (lambda (x) (* x x))) (sq 3)"), int(9));
+    }
+}
+
+#[cfg(test)]
+mod test_generator {
+    use crate::{
+        loader::eval_source,
+        test_util::{eval, eval_string, int},
+        types::{Cons, ConsValue, scope::LexicalVarStorage},
+    };
+
+    const COUNTER: &str = "(define (count-from n yield) (yield n) (count-from (+ n 1) yield))
+                           (define nat (make-generator (lambda (yield) (count-from 0 yield))))";
+
+    #[test]
+    fn test_generator_values() {
+        let src = "(define g (make-generator (lambda (yield) (yield 1) (yield 2))))";
+        assert_eq!(eval(&format!("{} (g)", src)), int(1));
+        assert_eq!(eval(&format!("{} (g) (g)", src)), int(2));
+        assert_eq!(
+            eval_string(&format!("{} (g) (g) (g)", src)),
+            Ok("end-of-generator".to_string())
+        );
+        assert_eq!(
+            eval(&format!("{} (g) (g) (g) (eq? (g) end-of-generator)", src)),
+            Ok(Cons::Value(ConsValue::Boolean(true)))
+        );
+        // A symbol read from source is not the end marker.
+        assert_eq!(
+            eval("(eq? 'end-of-generator end-of-generator)"),
+            Ok(Cons::Value(ConsValue::Boolean(false)))
+        );
+        assert!(eval(&format!("{} (g 1)", src)).is_err());
+    }
+
+    #[test]
+    fn test_generator_deep_nesting() {
+        // Deeper than a default 2 MiB thread stack allows in a debug build.
+        let depth = 150;
+        let body = format!("{}0{}", "(+ 1 ".repeat(depth), ")".repeat(depth));
+        let src = format!("((make-generator (lambda (yield) (yield {}))))", body);
+        assert_eq!(eval(&src), int(depth as i64));
+    }
+
+    #[test]
+    fn test_generator_is_lazy() {
+        let src = "(define out (open-output-string))
+                   (define g (make-generator (lambda (yield)
+                     (format out \"a \") (yield 1) (format out \"b \") (yield 2))))
+                   (g)
+                   (get-output-string out)";
+        assert_eq!(eval_string(src), Ok("\"a \"".to_string()));
+    }
+
+    #[test]
+    fn test_generator_conversions() {
+        assert_eq!(
+            eval_string(&format!("{} (generator->list nat 3)", COUNTER)),
+            Ok("(0 1 2)".to_string())
+        );
+        assert_eq!(
+            eval_string("(generator->list (make-generator (lambda (yield) (yield 1) (yield 2))))"),
+            Ok("(1 2)".to_string())
+        );
+        assert_eq!(
+            eval_string(&format!(
+                "{} (stream->list (stream-map (lambda (x) (* x x)) (generator->stream nat)) 4)",
+                COUNTER
+            )),
+            Ok("(0 1 4 9)".to_string())
+        );
+        assert_eq!(
+            eval(&format!("{} (apply + (generator->list nat 5))", COUNTER)),
+            int(10)
+        );
+        assert_eq!(
+            eval_string(&format!(
+                "{} (vector-map (lambda (x) (nat)) #(a b))",
+                COUNTER
+            )),
+            Ok("#(0 1)".to_string())
+        );
+    }
+
+    #[test]
+    fn test_generator_errors() {
+        // Errors in the body are raised by the call that ran it, after which
+        // the generator has ended.
+        let src = "(define g (make-generator (lambda (yield) (yield (+ 1 \"a\")))))";
+        let mut stg = LexicalVarStorage::new();
+        assert!(eval_source(&format!("{} (g)", src), &mut stg).is_err());
+        assert_eq!(
+            eval_source("(g)", &mut stg).map(|c| c.to_string()),
+            Ok("end-of-generator".to_string())
+        );
+        // Continuations escape out of the generator body.
+        assert_eq!(
+            eval(
+                "(call/cc (lambda (k) (generator->list (make-generator (lambda (yield) (yield 1) (k 9))))))"
+            ),
+            int(9)
+        );
+    }
+}
+
+#[cfg(test)]
+mod test_parameter {
+    use crate::{
+        loader::eval_source,
+        test_util::{eval, int},
+        types::{Cons, ConsValue, scope::LexicalVarStorage},
+    };
+
+    #[test]
+    fn test_parameterize() {
+        let src = "(define width (make-parameter 10))
+                   (define (show) (width))";
+        assert_eq!(eval(&format!("{} (show)", src)), int(10));
+        assert_eq!(
+            eval(&format!("{} (parameterize ((width 20)) (show))", src)),
+            int(20)
+        );
+        assert_eq!(
+            eval(&format!(
+                "{} (parameterize ((width 20)) (show)) (show)",
+                src
+            )),
+            int(10)
+        );
+        assert_eq!(
+            eval(&format!(
+                "{} (parameterize ((width 20)) (parameterize ((width 30)) (show)))",
+                src
+            )),
+            int(30)
+        );
+        assert!(eval(&format!("{} (width 1)", src)).is_err());
+        assert!(eval("(parameterize ((car 1)) 2)").is_err());
+    }
+
+    #[test]
+    fn test_parameter_converter() {
+        let src = "(define doubled (make-parameter 5 (lambda (x) (* x 2))))";
+        assert_eq!(eval(&format!("{} (doubled)", src)), int(10));
+        assert_eq!(
+            eval(&format!("{} (parameterize ((doubled 7)) (doubled))", src)),
+            int(14)
+        );
+    }
+
+    #[test]
+    fn test_parameterize_restores() {
+        let mut stg = LexicalVarStorage::new();
+        eval_source("(define width (make-parameter 10))", &mut stg).unwrap();
+        assert!(eval_source("(parameterize ((width 40)) (+ 1 \"a\"))", &mut stg).is_err());
+        assert_eq!(eval_source("(width)", &mut stg), int(10));
+        assert_eq!(
+            eval_source(
+                "(call/cc (lambda (k) (parameterize ((width 30)) (k (width)))))",
+                &mut stg
+            ),
+            int(30)
+        );
+        assert_eq!(eval_source("(width)", &mut stg), int(10));
+    }
+
+    #[test]
+    fn test_parameterize_in_generator() {
+        // A generator suspended inside parameterize keeps the binding to
+        // itself, and one made inside parameterize keeps seeing it.
+        let src = "(define p (make-parameter 1))
+                   (define g (make-generator (lambda (yield) (parameterize ((p 99)) (yield (p))))))";
+        assert_eq!(eval(&format!("{} (g) (p)", src)), int(1));
+        assert_eq!(eval(&format!("{} (g)", src)), int(99));
+        let src = "(define p (make-parameter 1))
+                   (define g (parameterize ((p 2)) (make-generator (lambda (yield) (yield (p))))))
+                   (g)";
+        assert_eq!(eval(src), int(2));
+    }
+
+    #[test]
+    fn test_parameterize_output_port() {
+        let src = "(define out (open-output-string))
+                   (parameterize ((current-output-port out)) (print \"captured\"))
+                   (get-output-string out)";
+        assert_eq!(
+            eval(src),
+            Ok(Cons::Value(ConsValue::String("captured".to_string())))
+        );
+    }
+}
+
+#[cfg(test)]
+mod test_random {
+    use crate::{
+        loader::eval_source,
+        test_util::{eval, eval_string},
+        types::{Cons, ConsValue, scope::LexicalVarStorage},
+    };
+
+    #[test]
+    fn test_random_seed_sequence() {
+        // A seed must keep giving these exact values, on every platform.
+        let src = "(random-seed! 42)
+                   (format #f \"~a ~a ~a\" (random-integer 1000000) (random-integer 1000000)
+                     (random-integer 1000000))";
+        assert_eq!(
+            eval(src),
+            Ok(Cons::Value(ConsValue::String(
+                "558742 543102 559009".to_string()
+            )))
+        );
+        let src = "(random-seed! 42) (random-integer 1000000) (random-integer 1000000)
+                   (random-integer 1000000) (random-real)";
+        assert_eq!(
+            eval(src),
+            Ok(Cons::Value(ConsValue::Float(0.9246929453253876)))
+        );
+    }
+
+    #[test]
+    fn test_random_reseed() {
+        let mut stg = LexicalVarStorage::new();
+        let first = eval_source("(random-seed! 7) (shuffle '(1 2 3 4 5 6 7 8))", &mut stg);
+        let second = eval_source("(random-seed! 7) (shuffle '(1 2 3 4 5 6 7 8))", &mut stg);
+        assert_eq!(first, second);
+    }
+
+    #[test]
+    fn test_random_ranges() {
+        let mut stg = LexicalVarStorage::new();
+        for _ in 0..100 {
+            let Ok(Cons::Value(ConsValue::Int(i))) = eval_source("(random-integer 3)", &mut stg)
+            else {
+                panic!("random-integer did not return an integer");
+            };
+            assert!((0..3).contains(&i));
+            let Ok(Cons::Value(ConsValue::Float(f))) = eval_source("(random-real)", &mut stg)
+            else {
+                panic!("random-real did not return a float");
+            };
+            assert!((0.0..1.0).contains(&f));
+        }
+        assert!(eval("(random-integer 0)").is_err());
+        assert!(eval("(random-integer -5)").is_err());
+    }
+
+    #[test]
+    fn test_shuffle_and_choice() {
+        assert_eq!(
+            eval_string("(sort (shuffle '(3 1 4 1 5 9 2 6)))"),
+            Ok("(1 1 2 3 4 5 6 9)".to_string())
+        );
+        assert_eq!(
+            eval_string("(vector-length (shuffle #(1 2 3)))"),
+            Ok("3".to_string())
+        );
+        assert_eq!(eval_string("(shuffle '())"), Ok("'()".to_string()));
+        assert_eq!(eval_string("(random-choice '(7 7))"), Ok("7".to_string()));
+        assert_eq!(eval_string("(random-choice #(x))"), Ok("x".to_string()));
+        assert!(eval("(random-choice '())").is_err());
+    }
+}
+
+#[cfg(test)]
+mod test_date {
+    use chrono::{FixedOffset, TimeZone, Utc};
+
+    use crate::{
+        errors::EvaluatorError,
+        loader::eval_source,
+        types::{Cons, clock::Clock, scope::LexicalVarStorage},
+    };
+
+    /// A storage whose clock is stopped at 2024-03-10 12:30:00 UTC, in a
+    /// local time zone two hours east of UTC.
+    fn frozen() -> LexicalVarStorage {
+        let mut stg = LexicalVarStorage::new();
+        stg.set_clock(Clock::frozen(
+            Utc.with_ymd_and_hms(2024, 3, 10, 12, 30, 0).unwrap(),
+            FixedOffset::east_opt(2 * 3600).unwrap(),
+        ));
+        stg
+    }
+
+    fn eval(src: &str) -> Result<Cons, EvaluatorError> {
+        eval_source(src, &mut frozen())
+    }
+
+    fn eval_string(src: &str) -> Result<String, EvaluatorError> {
+        eval(src).map(|c| c.to_string())
+    }
+
+    #[test]
+    fn test_frozen_clock() {
+        assert_eq!(
+            eval_string("(current-time)"),
+            Ok("1710073800000".to_string())
+        );
+        assert_eq!(
+            eval_string("(date->string (current-date))"),
+            Ok("\"2024-03-10T14:30:00+02:00\"".to_string())
+        );
+        assert_eq!(
+            eval_string("(sleep 1500) (current-time)"),
+            Ok("1710073801500".to_string())
+        );
+        assert!(eval("(sleep -1)").is_err());
+    }
+
+    #[test]
+    fn test_make_date_fields() {
+        let src = "(define d (make-date 2024 2 29 23 59 58 -18000))
+                   (format #f \"~a ~a ~a ~a ~a ~a ~a ~a ~a ~a\" (date-year d) (date-month d)
+                     (date-day d) (date-hour d) (date-minute d) (date-second d)
+                     (date-millisecond d) (date-week-day d) (date-year-day d)
+                     (date-zone-offset d))";
+        assert_eq!(
+            eval_string(src),
+            Ok("\"2024 2 29 23 59 58 0 4 60 -18000\"".to_string())
+        );
+        assert_eq!(
+            eval_string("(date->string (make-date 2024 1 2))"),
+            Ok("\"2024-01-02T00:00:00+00:00\"".to_string())
+        );
+        assert!(eval("(make-date 2023 2 29)").is_err());
+        assert!(eval("(make-date 2024 1 1 25 0 0)").is_err());
+        assert!(eval("(make-date 2024 1)").is_err());
+    }
+
+    #[test]
+    fn test_format_and_parse() {
+        assert_eq!(
+            eval_string("(date->string (make-date 2024 7 4 9 5 0) \"%Y-%m-%d %H:%M\")"),
+            Ok("\"2024-07-04 09:05\"".to_string())
+        );
+        assert!(eval("(date->string (make-date 2024 7 4) \"%Q\")").is_err());
+        assert_eq!(
+            eval_string("(date->time (string->date \"2024-03-10T14:30:00+02:00\"))"),
+            Ok("1710073800000".to_string())
+        );
+        assert_eq!(
+            eval_string("(date->string (string->date \"2024-07-04 09:05\" \"%Y-%m-%d %H:%M\"))"),
+            Ok("\"2024-07-04T09:05:00+00:00\"".to_string())
+        );
+        assert_eq!(
+            eval_string("(date->string (string->date \"04/07/2024\" \"%d/%m/%Y\"))"),
+            Ok("\"2024-07-04T00:00:00+00:00\"".to_string())
+        );
+        assert!(eval("(string->date \"yesterday\")").is_err());
+    }
+
+    #[test]
+    fn test_durations() {
+        assert_eq!(
+            eval_string("(duration->milliseconds (make-duration 1 2 3 4 5))"),
+            Ok("93784005".to_string())
+        );
+        assert_eq!(
+            eval_string("(date->string (date-add (make-date 2024 2 28) (make-duration 2)))"),
+            Ok("\"2024-03-01T00:00:00+00:00\"".to_string())
+        );
+        assert_eq!(
+            eval_string(
+                "(duration->milliseconds
+                   (date-difference (make-date 2024 1 1 1 0 0) (make-date 2024 1 1 0 0 0 3600)))"
+            ),
+            Ok("7200000".to_string())
+        );
+        assert_eq!(
+            eval_string("(equal? (make-duration 0 24 0 0) (make-duration 1))"),
+            Ok("true".to_string())
+        );
+        assert!(eval("(make-duration 1 2)").is_err());
+    }
+
+    #[test]
+    fn test_time_zones() {
+        assert_eq!(
+            eval_string("(date->string (date->utc (make-date 2024 1 1 0 0 0 3600)))"),
+            Ok("\"2023-12-31T23:00:00+00:00\"".to_string())
+        );
+        assert_eq!(
+            eval_string("(date->string (date->local (make-date 2024 1 1)))"),
+            Ok("\"2024-01-01T02:00:00+02:00\"".to_string())
+        );
+        assert_eq!(
+            eval_string("(equal? (make-date 2024 1 1) (date->local (make-date 2024 1 1)))"),
+            Ok("true".to_string())
+        );
+        assert_eq!(
+            eval_string("(date->string (time->date 0))"),
+            Ok("\"1970-01-01T00:00:00+00:00\"".to_string())
+        );
+    }
+}
+
+#[cfg(test)]
+mod test_file {
+    use crate::{
+        errors::EvaluatorError,
+        loader::eval_source,
+        test_util::{eval, eval_string},
+        types::{scope::LexicalVarStorage, symbol::Symbol},
+    };
+    use std::{fs, path::PathBuf};
+
+    /// An empty scratch directory for one test.
+    fn test_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("boxr-test-file-{}", name));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    #[test]
+    fn test_write_append_read() {
+        let path = test_dir("write").join("notes.txt");
+        let path = path.display();
+        let src = format!(
+            "(write-file \"{path}\" \"one\ntwo\n\")
+             (append-file \"{path}\" \"three\")
+             (read-file \"{path}\")"
+        );
+        assert_eq!(eval_string(&src), Ok("\"one\ntwo\nthree\"".to_string()));
+        assert_eq!(
+            eval_string(&format!("(read-lines \"{path}\")")),
+            Ok("(\"one\" \"two\" \"three\")".to_string())
+        );
+        assert_eq!(
+            eval_string(&format!("(file-size \"{path}\")")),
+            Ok("13".to_string())
+        );
+        // Writing replaces what was there.
+        let src = format!("(write-file \"{path}\" \"new\") (read-file \"{path}\")");
+        assert_eq!(eval_string(&src), Ok("\"new\"".to_string()));
+    }
+
+    #[test]
+    fn test_directories() {
+        let dir = test_dir("dirs");
+        let dir = dir.display();
+        let src = format!(
+            "(make-directory \"{dir}/a/b\")
+             (write-file \"{dir}/z.txt\" \"\")
+             (write-file \"{dir}/m.txt\" \"\")
+             (rename-file \"{dir}/m.txt\" \"{dir}/a/m.txt\")
+             (delete-file \"{dir}/z.txt\")
+             (format #f \"~a ~a ~a\" (directory-list \"{dir}\") (directory-list \"{dir}/a\")
+               (file-exists? \"{dir}/z.txt\"))"
+        );
+        assert_eq!(eval_string(&src), Ok("\"(a) (b m.txt) false\"".to_string()));
+        let year = eval_string(&format!("(date-year (file-mtime \"{dir}/a/m.txt\"))"));
+        assert!(year.unwrap().parse::<i64>().unwrap() >= 2024);
+    }
+
+    #[test]
+    fn test_file_errors() {
+        let dir = test_dir("errors");
+        let dir = dir.display();
+        for src in [
+            format!("(read-file \"{dir}/missing\")"),
+            format!("(delete-file \"{dir}/missing\")"),
+            format!("(file-size \"{dir}/missing\")"),
+            format!("(directory-list \"{dir}/missing\")"),
+        ] {
+            assert!(
+                matches!(eval(&src), Err(EvaluatorError::IoError(_))),
+                "{}",
+                src
+            );
+        }
+        assert!(matches!(
+            eval("(read-file 42)"),
+            Err(EvaluatorError::InvalidArgument(_))
+        ));
+    }
+
+    #[test]
+    fn test_file_system_disabled() {
+        let dir = test_dir("disabled");
+        let path = dir.join("secret.txt");
+        fs::write(&path, "hidden").unwrap();
+        let mut stg = LexicalVarStorage::new();
+        stg.set_file_system_enabled(false);
+        for src in [
+            format!("(read-file \"{}\")", path.display()),
+            format!("(file-exists? \"{}\")", path.display()),
+            format!("(write-file \"{}\" \"changed\")", path.display()),
+        ] {
+            assert!(matches!(
+                eval_source(&src, &mut stg),
+                Err(EvaluatorError::IoError(_))
+            ));
+        }
+        assert_eq!(fs::read_to_string(&path).unwrap(), "hidden");
+    }
+
+    #[test]
+    fn test_load_disabled() {
+        let dir = test_dir("load-disabled");
+        fs::write(dir.join("inc.scm"), "(define loaded 1)").unwrap();
+        fs::write(
+            dir.join("inc-module.scm"),
+            "(module inc-module (export loaded) (define loaded 1))",
+        )
+        .unwrap();
+        let mut stg = LexicalVarStorage::new();
+        stg.add_load_path(dir.clone());
+        stg.set_file_system_enabled(false);
+        let src = format!("(load \"{}\")", dir.join("inc.scm").display());
+        for src in [src.as_str(), "(load \"inc\")", "(import inc-module)"] {
+            assert!(matches!(
+                eval_source(src, &mut stg),
+                Err(EvaluatorError::IoError(_))
+            ));
+        }
+        assert!(stg.get(&Symbol::new("loaded")).is_none());
+        // Modules defined in the script itself need no file access.
+        let src = "(module local (export x) (define x 5)) (import local) x";
+        assert_eq!(
+            eval_source(src, &mut stg).map(|c| c.to_string()),
+            Ok("5".to_string())
+        );
+    }
+}
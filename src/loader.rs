@@ -0,0 +1,111 @@
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+use lazy_static::lazy_static;
+
+use crate::{
+    errors::EvaluatorError,
+    evaluator::lisp_eval,
+    slyther::SExpressionsParser,
+    types::{module::Module, scope::LexicalVarStorage, Cons, ConsValue},
+};
+
+lazy_static! {
+    static ref PARSER: SExpressionsParser = SExpressionsParser::new();
+}
+
+/// Finds the file `load` or `import` refers to by `name`. The name is tried
+/// as given and with a `.scm` extension, first as-is when absolute and then
+/// relative to each load path in order.
+pub fn resolve_path(name: &str, load_paths: &[PathBuf]) -> Option<PathBuf> {
+    let candidates = [PathBuf::from(name), PathBuf::from(format!("{}.scm", name))];
+    for candidate in candidates {
+        if candidate.is_absolute() {
+            if candidate.is_file() {
+                return Some(candidate);
+            }
+            continue;
+        }
+        for dir in load_paths {
+            let path = dir.join(&candidate);
+            if path.is_file() {
+                return Some(path);
+            }
+        }
+    }
+    None
+}
+
+/// Parses and evaluates every expression in `source`, returning the value of
+/// the last one.
+pub fn eval_source(source: &str, stg: &mut LexicalVarStorage) -> Result<Cons, EvaluatorError> {
+    if source.trim().is_empty() {
+        return Ok(Cons::Value(ConsValue::NIL));
+    }
+    let exprs = PARSER
+        .parse(source)
+        .map_err(|e| EvaluatorError::LoadError(format!("Parse error: {}", e)))?;
+    let mut result = Cons::Value(ConsValue::NIL);
+    for expr in exprs {
+        result = lisp_eval(&expr, stg)?;
+    }
+    Ok(result)
+}
+
+/// Fails with an `IoError` naming `name` when the embedder has turned off file
+/// system access for `stg`.
+pub fn check_file_system(name: &str, stg: &LexicalVarStorage) -> Result<(), EvaluatorError> {
+    if stg.file_system_enabled() {
+        Ok(())
+    } else {
+        Err(EvaluatorError::IoError(format!(
+            "File system access is disabled for {}",
+            name
+        )))
+    }
+}
+
+pub fn load_file(path: &Path, stg: &mut LexicalVarStorage) -> Result<Cons, EvaluatorError> {
+    check_file_system("load", stg)?;
+    log::debug!("Loading file: {}", path.display());
+    let source = fs::read_to_string(path)
+        .map_err(|e| EvaluatorError::LoadError(format!("{}: {}", path.display(), e)))?;
+    eval_source(&source, stg)
+}
+
+/// Returns the module called `name`, loading it through the load path search
+/// if it has not been defined yet.
+pub fn require_module(name: &str, stg: &LexicalVarStorage) -> Result<Module, EvaluatorError> {
+    if let Some(module) = stg.modules().lock().unwrap().get(name) {
+        return Ok(module.clone());
+    }
+
+    check_file_system("import", stg)?;
+    let load_paths = stg.modules().lock().unwrap().load_paths().to_vec();
+    let path = resolve_path(name, &load_paths)
+        .ok_or_else(|| EvaluatorError::ModuleError(format!("Could not find module {}", name)))?;
+    if !stg.modules().lock().unwrap().begin_loading(name) {
+        return Err(EvaluatorError::ModuleError(format!(
+            "Cyclic import of module {}",
+            name
+        )));
+    }
+    let loaded = load_file(&path, &mut stg.detached());
+    stg.modules().lock().unwrap().finish_loading(name);
+    loaded?;
+
+    stg.modules()
+        .lock()
+        .unwrap()
+        .get(name)
+        .cloned()
+        .ok_or_else(|| {
+            EvaluatorError::ModuleError(format!(
+                "{} does not define module {}",
+                path.display(),
+                name
+            ))
+        })
+}
@@ -1,15 +1,22 @@
 use boxr::{
-    evaluator::lisp_eval, logger, slyther::SExpressionsParser, types::scope::LexicalVarStorage,
+    errors::EvaluatorError, evaluator::lisp_eval, logger, slyther::SExpressionsParser,
+    types::scope::LexicalVarStorage,
 };
 use clap::Parser;
 use log::LevelFilter;
-use std::fs;
+use std::{fs, path::Path, process};
 
 #[derive(Parser, Debug)]
 #[command(author, version, about, long_about)]
 struct Args {
     #[arg(short, long)]
     file: String,
+    /// Seed for the random number generator, for reproducible runs.
+    #[arg(long)]
+    seed: Option<i64>,
+    /// Stop the script from reading and writing files.
+    #[arg(long)]
+    no_file_system: bool,
 }
 
 fn main() {
@@ -21,6 +28,14 @@ fn main() {
     let file: String = fs::read_to_string(&file_name).unwrap();
 
     let mut global_stg = LexicalVarStorage::new();
+    if let Some(seed) = args.seed {
+        global_stg.random().seed(seed as u64);
+    }
+    global_stg.set_file_system_enabled(!args.no_file_system);
+    // Let scripts load and import files that sit next to them.
+    if let Some(dir) = Path::new(&file_name).parent() {
+        global_stg.add_load_path(dir.to_path_buf());
+    }
     let parser = SExpressionsParser::new();
     let exprs = parser.parse(&file).unwrap();
 
@@ -28,6 +43,7 @@ fn main() {
         log::debug!("{:?}", expr.as_ref());
         match lisp_eval(&expr, &mut global_stg) {
             Ok(_) => {}
+            Err(EvaluatorError::Exit(code)) => process::exit(code),
             Err(e) => log::error!("{:?}", e),
         };
     }
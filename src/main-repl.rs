@@ -1,4 +1,7 @@
+use std::fmt::{self, Display, Formatter};
 use std::path::Path;
+use std::process;
+use std::sync::OnceLock;
 
 use boxr::errors::EvaluatorError;
 use boxr::types::Cons;
@@ -16,36 +19,79 @@ lazy_static! {
 
 struct BoxrContext(LexicalVarStorage);
 
-fn eval(args: ArgMatches, context: &mut BoxrContext) -> reedline_repl_rs::Result<Option<String>> {
+#[derive(Debug)]
+enum ReplError {
+    Repl(reedline_repl_rs::Error),
+    /// The session evaluated `(exit [code])`.
+    Exit(i32),
+}
+
+impl Display for ReplError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            ReplError::Repl(e) => write!(f, "{}", e),
+            ReplError::Exit(code) => write!(f, "Exited with status {}", code),
+        }
+    }
+}
+
+impl From<reedline_repl_rs::Error> for ReplError {
+    fn from(e: reedline_repl_rs::Error) -> Self {
+        ReplError::Repl(e)
+    }
+}
+
+type ReplResult<T> = Result<T, ReplError>;
+
+/// Status requested by `(exit [code])`, applied once the REPL has shut down.
+static EXIT_CODE: OnceLock<i32> = OnceLock::new();
+
+fn on_error(
+    error: ReplError,
+    _repl: &Repl<BoxrContext, ReplError>,
+) -> reedline_repl_rs::Result<()> {
+    match error {
+        ReplError::Repl(e) => {
+            eprintln!("{}", e);
+            Ok(())
+        }
+        ReplError::Exit(code) => {
+            EXIT_CODE.get_or_init(|| code);
+            // Failing the error handler is the only way to stop `Repl::run`,
+            // which then drops the line editor and saves the history.
+            Err(reedline_repl_rs::Error::UnknownCommand("exit".to_string()))
+        }
+    }
+}
+
+fn eval(args: ArgMatches, context: &mut BoxrContext) -> ReplResult<Option<String>> {
     let body: String = args
         .get_many("body")
         .unwrap()
-        .into_iter()
-        .map(|s: &String| format!(" {}", s.to_string()))
+        .map(|s: &String| format!(" {}", s))
         .collect();
     let ast = PARSER.parse(body.as_str()).unwrap();
     let result = ast
         .iter()
         .map(|s| lisp_eval(s, &mut context.0))
         .collect::<Result<Vec<Cons>, EvaluatorError>>();
+    if let Err(EvaluatorError::Exit(code)) = result {
+        return Err(ReplError::Exit(code));
+    }
     Ok(Some(format!("{}", result.unwrap().iter().last().unwrap())))
 }
 
-fn lex(args: ArgMatches, _context: &mut BoxrContext) -> reedline_repl_rs::Result<Option<String>> {
+fn lex(args: ArgMatches, _context: &mut BoxrContext) -> ReplResult<Option<String>> {
     let body: String = args
         .get_many("body")
         .unwrap()
-        .into_iter()
-        .map(|s: &String| format!(" {}", s.to_string()))
+        .map(|s: &String| format!(" {}", s))
         .collect();
     let ast = PARSER.parse(body.as_str()).unwrap();
     Ok(Some(format!("{:?}", ast)))
 }
 
-fn print_lvs(
-    _args: ArgMatches,
-    context: &mut BoxrContext,
-) -> reedline_repl_rs::Result<Option<String>> {
+fn print_lvs(_args: ArgMatches, context: &mut BoxrContext) -> ReplResult<Option<String>> {
     Ok(Some(format!("{:?}", context.0)))
 }
 
@@ -60,6 +106,7 @@ fn main() -> reedline_repl_rs::Result<()> {
         .with_history(Path::new(".boxr_history").to_path_buf(), 1000)
         .with_stop_on_ctrl_c(true)
         .with_stop_on_ctrl_d(true)
+        .with_error_handler(on_error)
         .with_command(
             Command::new("eval").arg(Arg::new("body").action(ArgAction::Append)),
             eval,
@@ -73,5 +120,9 @@ fn main() -> reedline_repl_rs::Result<()> {
             // Print the lexical variable storage
             print_lvs,
         );
-    repl.run()
+    let result = repl.run();
+    if let Some(code) = EXIT_CODE.get() {
+        process::exit(*code);
+    }
+    result
 }
@@ -0,0 +1,62 @@
+use std::{
+    fmt::{Display, Error, Formatter},
+    sync::{Arc, Mutex},
+};
+
+/// A mutable, fixed-length sequence of bytes. Clones share the same
+/// underlying storage.
+#[derive(Debug, Clone)]
+pub struct Bytevector(Arc<Mutex<Vec<u8>>>);
+
+impl Bytevector {
+    pub fn new(bytes: Vec<u8>) -> Self {
+        Bytevector(Arc::new(Mutex::new(bytes)))
+    }
+
+    pub fn len(&self) -> usize {
+        self.0.lock().unwrap().len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    pub fn get(&self, index: usize) -> Option<u8> {
+        self.0.lock().unwrap().get(index).copied()
+    }
+
+    /// Overwrites the bytes from `index` on with `bytes`, returning false if
+    /// they do not fit.
+    pub fn write(&self, index: usize, bytes: &[u8]) -> bool {
+        let mut storage = self.0.lock().unwrap();
+        match storage.get_mut(index..index.saturating_add(bytes.len())) {
+            Some(dest) => {
+                dest.copy_from_slice(bytes);
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Returns a snapshot of the bytes.
+    pub fn to_vec(&self) -> Vec<u8> {
+        self.0.lock().unwrap().clone()
+    }
+
+    pub fn ptr_eq(&self, other: &Bytevector) -> bool {
+        Arc::ptr_eq(&self.0, &other.0)
+    }
+}
+
+impl PartialEq for Bytevector {
+    fn eq(&self, other: &Self) -> bool {
+        self.ptr_eq(other)
+    }
+}
+
+impl Display for Bytevector {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), Error> {
+        let bytes: Vec<String> = self.to_vec().iter().map(u8::to_string).collect();
+        write!(f, "#u8({})", bytes.join(" "))
+    }
+}
@@ -0,0 +1,74 @@
+use std::{
+    sync::{Arc, Mutex},
+    thread,
+};
+
+use chrono::{DateTime, FixedOffset, Local, Offset, TimeDelta, Utc};
+
+#[derive(Debug)]
+enum ClockKind {
+    System,
+    Frozen {
+        now: DateTime<Utc>,
+        offset: FixedOffset,
+    },
+}
+
+/// Where the date and time builtins get the time from. The system clock is
+/// used unless an embedder, usually a test, freezes it. Clones share the same
+/// clock.
+#[derive(Debug, Clone)]
+pub struct Clock(Arc<Mutex<ClockKind>>);
+
+impl Clock {
+    pub fn system() -> Self {
+        Clock(Arc::new(Mutex::new(ClockKind::System)))
+    }
+
+    /// A clock stopped at `now`, whose local time zone is `offset`. Sleeping
+    /// moves it forward at once instead of waiting, so scripts behave the same
+    /// on every run.
+    pub fn frozen(now: DateTime<Utc>, offset: FixedOffset) -> Self {
+        Clock(Arc::new(Mutex::new(ClockKind::Frozen { now, offset })))
+    }
+
+    pub fn now(&self) -> DateTime<Utc> {
+        match &*self.0.lock().unwrap() {
+            ClockKind::System => Utc::now(),
+            ClockKind::Frozen { now, .. } => *now,
+        }
+    }
+
+    /// The offset of the local time zone at `at`.
+    pub fn local_offset(&self, at: DateTime<Utc>) -> FixedOffset {
+        match &*self.0.lock().unwrap() {
+            ClockKind::System => at.with_timezone(&Local).offset().fix(),
+            ClockKind::Frozen { offset, .. } => *offset,
+        }
+    }
+
+    /// The current time in the local time zone.
+    pub fn local_now(&self) -> DateTime<FixedOffset> {
+        let now = self.now();
+        now.with_timezone(&self.local_offset(now))
+    }
+
+    pub fn sleep(&self, duration: TimeDelta) {
+        let mut kind = self.0.lock().unwrap();
+        if let ClockKind::Frozen { now, .. } = &mut *kind {
+            *now = now
+                .checked_add_signed(duration)
+                .unwrap_or(DateTime::<Utc>::MAX_UTC);
+            return;
+        }
+        // Other threads may read the clock while this one sleeps.
+        drop(kind);
+        thread::sleep(duration.to_std().unwrap_or_default());
+    }
+}
+
+impl Default for Clock {
+    fn default() -> Self {
+        Self::system()
+    }
+}
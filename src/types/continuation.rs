@@ -0,0 +1,49 @@
+use std::{
+    fmt::{Display, Error, Formatter},
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
+};
+
+/// An escape-only continuation captured by `call/cc`. Calling it unwinds
+/// evaluation back to that `call/cc`, which returns the value passed to it,
+/// so it can only be called until the `call/cc` returns. Clones are the same
+/// continuation.
+#[derive(Debug, Clone)]
+pub struct Continuation(Arc<AtomicBool>);
+
+impl Continuation {
+    pub fn new() -> Self {
+        Continuation(Arc::new(AtomicBool::new(true)))
+    }
+
+    /// Whether the `call/cc` that captured this continuation is still running.
+    pub fn is_active(&self) -> bool {
+        self.0.load(Ordering::SeqCst)
+    }
+
+    /// Marks the continuation as no longer callable, once its `call/cc` has
+    /// returned.
+    pub fn expire(&self) {
+        self.0.store(false, Ordering::SeqCst);
+    }
+}
+
+impl Default for Continuation {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl PartialEq for Continuation {
+    fn eq(&self, other: &Self) -> bool {
+        Arc::ptr_eq(&self.0, &other.0)
+    }
+}
+
+impl Display for Continuation {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), Error> {
+        write!(f, "#<continuation>")
+    }
+}
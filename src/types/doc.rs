@@ -0,0 +1,911 @@
+use std::collections::BTreeSet;
+
+use super::{
+    function::{
+        BuiltinFunction, BuiltinMacro, BUILTINS_CONST_MAP, BUILTINS_FUNC_MAP, BUILTINS_MACRO_MAP,
+    },
+    scope::LexicalVarStorage,
+    symbol::Symbol,
+};
+
+/// Documentation shown by `help` for a builtin function or macro.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Doc {
+    pub signature: &'static str,
+    pub description: &'static str,
+}
+
+const fn doc(signature: &'static str, description: &'static str) -> Doc {
+    Doc {
+        signature,
+        description,
+    }
+}
+
+impl BuiltinFunction {
+    /// The name the function is called by, as shown in its signature.
+    pub fn name(&self) -> &'static str {
+        let signature = self.doc().signature;
+        signature[1..].split([' ', ')']).next().unwrap_or_default()
+    }
+
+    pub fn doc(&self) -> Doc {
+        match *self {
+            BuiltinFunction::Add => doc("(+ num ...)", "Returns the sum of its arguments."),
+            BuiltinFunction::Sub => doc(
+                "(- num ...)",
+                "Subtracts the remaining arguments from the first, or negates a single argument.",
+            ),
+            BuiltinFunction::Mul => doc("(* num ...)", "Returns the product of its arguments."),
+            BuiltinFunction::Div => doc(
+                "(/ num ...)",
+                "Divides the first argument by the rest, or inverts a single argument. Integers that do not divide evenly give an exact fraction like 1/3.",
+            ),
+            BuiltinFunction::FloorDiv => doc(
+                "(floordiv num ...)",
+                "Divides the first argument by the rest and truncates the result towards zero.",
+            ),
+            BuiltinFunction::Mod => doc(
+                "(% num ...)",
+                "Takes the remainder of the first argument by each of the rest in turn, exactly for integers.",
+            ),
+            BuiltinFunction::Pow => doc(
+                "(^ num ...)",
+                "Raises the first argument to the power of the rest in turn.",
+            ),
+            BuiltinFunction::Eq => doc(
+                "(= num ...)",
+                "Returns #t if all arguments are numerically equal, so (= 1 1.0) is #t.",
+            ),
+            BuiltinFunction::Neq => doc(
+                "(!= num ...)",
+                "Returns #t if any argument differs numerically from the others.",
+            ),
+            BuiltinFunction::Lt => doc(
+                "(< x ...)",
+                "Returns #t if the arguments are strictly increasing. Numbers compare numerically, characters by code point, and strings and symbols lexicographically. All arguments must be of the same kind.",
+            ),
+            BuiltinFunction::Gt => doc(
+                "(> x ...)",
+                "Returns #t if the arguments are strictly decreasing. Numbers compare numerically, characters by code point, and strings and symbols lexicographically. All arguments must be of the same kind.",
+            ),
+            BuiltinFunction::Lte => doc(
+                "(<= x ...)",
+                "Returns #t if the arguments are non-decreasing. Numbers compare numerically, characters by code point, and strings and symbols lexicographically. All arguments must be of the same kind.",
+            ),
+            BuiltinFunction::Gte => doc(
+                "(>= x ...)",
+                "Returns #t if the arguments are non-increasing. Numbers compare numerically, characters by code point, and strings and symbols lexicographically. All arguments must be of the same kind.",
+            ),
+            BuiltinFunction::Not => doc("(not obj)", "Returns the logical negation of obj."),
+            BuiltinFunction::Print => doc(
+                "(print obj ...)",
+                "Displays each obj, separated by spaces, on the current output port.",
+            ),
+            BuiltinFunction::Println => {
+                doc("(println obj ...)", "Like print, followed by a newline.")
+            }
+            BuiltinFunction::Input => doc(
+                "(input prompt)",
+                "Shows prompt and returns a line read from the input.",
+            ),
+            BuiltinFunction::List => doc("(list obj ...)", "Returns a list of its arguments."),
+            BuiltinFunction::Cons => {
+                doc("(cons car cdr)", "Returns a new cell holding car and cdr.")
+            }
+            BuiltinFunction::Car => doc("(car pair)", "Returns the first element of pair."),
+            BuiltinFunction::Cdr => doc(
+                "(cdr pair)",
+                "Returns everything after the first element of pair.",
+            ),
+            BuiltinFunction::IsList => doc("(is-list obj)", "Returns #t if obj is a list."),
+            BuiltinFunction::IsSymbol => doc("(is-symbol obj)", "Returns #t if obj is a symbol."),
+            BuiltinFunction::IsString => doc("(is-string obj)", "Returns #t if obj is a string."),
+            BuiltinFunction::IsBoolean => {
+                doc("(is-boolean obj)", "Returns #t if obj is a boolean.")
+            }
+            BuiltinFunction::IsInt => doc("(is-int obj)", "Returns #t if obj is an integer."),
+            BuiltinFunction::IsFloat => doc("(is-float obj)", "Returns #t if obj is a float."),
+            BuiltinFunction::IsQuoted => doc("(is-quoted obj)", "Returns #t if obj is quoted."),
+            BuiltinFunction::IsComment => {
+                doc("(is-comment obj)", "Returns #t if obj is a comment.")
+            }
+            BuiltinFunction::IsFunction => {
+                doc("(is-function obj)", "Returns #t if obj is a function.")
+            }
+            BuiltinFunction::IsMacro => doc("(is-macro obj)", "Returns #t if obj is a macro."),
+            BuiltinFunction::IsNil => doc("(is-nil obj)", "Returns #t if obj is the empty list."),
+            BuiltinFunction::IsDefined => doc(
+                "(is-defined sym)",
+                "Returns #t if sym names a builtin or a definition.",
+            ),
+            BuiltinFunction::IsBound => doc(
+                "(is-bound sym)",
+                "Returns #t if sym is bound in the current environment.",
+            ),
+            BuiltinFunction::Quote => doc("(quote obj)", "Returns obj without evaluating it."),
+            BuiltinFunction::Quasiquote => doc(
+                "(quasiquote obj)",
+                "Returns obj without evaluating it, except for unquoted parts.",
+            ),
+            BuiltinFunction::Unquote => doc(
+                "(unquote obj)",
+                "Evaluates obj inside a quasiquoted expression.",
+            ),
+            BuiltinFunction::UnquoteSplicing => doc(
+                "(unquote-splicing list)",
+                "Evaluates list and splices its elements into a quasiquoted expression.",
+            ),
+            BuiltinFunction::Apply => doc(
+                "(apply proc arg ... list)",
+                "Calls proc, a builtin or user function, with the given arguments followed by the elements of list, which must be a proper list.",
+            ),
+            BuiltinFunction::Load => doc(
+                "(load path)",
+                "Evaluates the file at path, searched for along the load path, in the current environment.",
+            ),
+            BuiltinFunction::Exit => doc(
+                "(exit [code])",
+                "Stops evaluation and ends the program with status code: 0 by default or for #t, 1 for #f.",
+            ),
+            BuiltinFunction::Help => doc(
+                "(help 'name)",
+                "Prints the signature and documentation of name.",
+            ),
+            BuiltinFunction::Apropos => doc(
+                "(apropos str)",
+                "Returns the sorted list of bound names that contain str.",
+            ),
+            BuiltinFunction::StringAppend => doc(
+                "(string-append str ...)",
+                "Returns the concatenation of its arguments.",
+            ),
+            BuiltinFunction::Substring => doc(
+                "(substring str start [end])",
+                "Returns the characters of str from start up to end, which defaults to the end of str.",
+            ),
+            BuiltinFunction::StringLength => doc(
+                "(string-length str)",
+                "Returns the number of characters (Unicode scalar values) in str.",
+            ),
+            BuiltinFunction::StringSplit => doc(
+                "(string-split str [sep])",
+                "Returns the list of pieces of str between occurrences of sep, or between runs of whitespace.",
+            ),
+            BuiltinFunction::StringJoin => doc(
+                "(string-join list [sep])",
+                "Concatenates a list of strings with sep, a single space by default, between them.",
+            ),
+            BuiltinFunction::StringTrim => doc(
+                "(string-trim str)",
+                "Returns str without leading and trailing whitespace.",
+            ),
+            BuiltinFunction::StringUpcase => {
+                doc("(string-upcase str)", "Returns str in upper case.")
+            }
+            BuiltinFunction::StringDowncase => {
+                doc("(string-downcase str)", "Returns str in lower case.")
+            }
+            BuiltinFunction::StringContains => doc(
+                "(string-contains str search)",
+                "Returns the index of the first occurrence of search in str, or #f.",
+            ),
+            BuiltinFunction::StringReplace => doc(
+                "(string-replace str from to)",
+                "Returns str with every occurrence of from replaced by to.",
+            ),
+            BuiltinFunction::StringToList => doc(
+                "(string->list str)",
+                "Returns the list of characters in str.",
+            ),
+            BuiltinFunction::ListToString => doc(
+                "(list->string list)",
+                "Returns the string made of a list of characters. Strings in the list are appended whole.",
+            ),
+            BuiltinFunction::StringToNumber => doc(
+                "(string->number str [radix])",
+                "Parses str as a number in radix (10 by default), returning #f if it is not one.",
+            ),
+            BuiltinFunction::NumberToString => doc(
+                "(number->string num [radix])",
+                "Returns num written in radix, which defaults to 10.",
+            ),
+            BuiltinFunction::StringToSymbol => {
+                doc("(string->symbol str)", "Returns the symbol named str.")
+            }
+            BuiltinFunction::SymbolToString => doc(
+                "(symbol->string sym)",
+                "Returns the name of sym as a string.",
+            ),
+            BuiltinFunction::StringEq => doc(
+                "(string=? str ...)",
+                "Returns #t if all arguments are the same string.",
+            ),
+            BuiltinFunction::StringLt => doc(
+                "(string<? str ...)",
+                "Returns #t if the arguments are in strictly increasing lexicographic order.",
+            ),
+            BuiltinFunction::StringGt => doc(
+                "(string>? str ...)",
+                "Returns #t if the arguments are in strictly decreasing lexicographic order.",
+            ),
+            BuiltinFunction::StringLte => doc(
+                "(string<=? str ...)",
+                "Returns #t if the arguments are in non-decreasing lexicographic order.",
+            ),
+            BuiltinFunction::StringGte => doc(
+                "(string>=? str ...)",
+                "Returns #t if the arguments are in non-increasing lexicographic order.",
+            ),
+            BuiltinFunction::Format => doc(
+                "(format dest template arg ...)",
+                "Expands template with args: ~a display, ~s write, ~d ~x ~b ~o integers, ~f floats, ~% newline, ~~ tilde. Directives take a width (~5d), a pad character (~5,'0d), a precision (~8,2f), and @ to right-justify (~10@a). Returns the string when dest is #f, otherwise writes it to the current output (#t) or the port dest.",
+            ),
+            BuiltinFunction::CurrentOutputPort => doc(
+                "(current-output-port)",
+                "Returns the port that print and (format #t ...) write to.",
+            ),
+            BuiltinFunction::OpenOutputString => doc(
+                "(open-output-string)",
+                "Returns a port that collects what is written to it in a string.",
+            ),
+            BuiltinFunction::GetOutputString => doc(
+                "(get-output-string port)",
+                "Returns everything written so far to a port from open-output-string.",
+            ),
+            BuiltinFunction::Abs => doc("(abs num)", "Returns the absolute value of num."),
+            BuiltinFunction::Min => doc("(min num ...)", "Returns the smallest argument."),
+            BuiltinFunction::Max => doc("(max num ...)", "Returns the largest argument."),
+            BuiltinFunction::Floor => doc("(floor num)", "Rounds num down to an integer."),
+            BuiltinFunction::Ceiling => doc("(ceiling num)", "Rounds num up to an integer."),
+            BuiltinFunction::Round => doc(
+                "(round num)",
+                "Rounds num to the nearest integer, halfway cases to even.",
+            ),
+            BuiltinFunction::Truncate => doc("(truncate num)", "Rounds num towards zero."),
+            BuiltinFunction::Sqrt => doc("(sqrt num)", "Returns the square root of num."),
+            BuiltinFunction::Exp => doc("(exp num)", "Returns e raised to the power num."),
+            BuiltinFunction::Log => doc(
+                "(log num [base])",
+                "Returns the natural logarithm of num, or its logarithm in base.",
+            ),
+            BuiltinFunction::Sin => doc("(sin num)", "Returns the sine of num radians."),
+            BuiltinFunction::Cos => doc("(cos num)", "Returns the cosine of num radians."),
+            BuiltinFunction::Tan => doc("(tan num)", "Returns the tangent of num radians."),
+            BuiltinFunction::Asin => doc("(asin num)", "Returns the arcsine of num in radians."),
+            BuiltinFunction::Acos => doc("(acos num)", "Returns the arccosine of num in radians."),
+            BuiltinFunction::Atan => doc(
+                "(atan y [x])",
+                "Returns the arctangent of y, or the angle of the point (x, y), in radians.",
+            ),
+            BuiltinFunction::ExactToInexact => {
+                doc("(exact->inexact num)", "Returns num as a float.")
+            }
+            BuiltinFunction::InexactToExact => doc(
+                "(inexact->exact num)",
+                "Returns the exact value of num, a fraction if it is not whole.",
+            ),
+            BuiltinFunction::Quotient => doc(
+                "(quotient n d)",
+                "Divides the integer n by d, rounding towards zero.",
+            ),
+            BuiltinFunction::Remainder => doc(
+                "(remainder n d)",
+                "Returns the remainder of (quotient n d), which has the sign of n.",
+            ),
+            BuiltinFunction::Modulo => doc(
+                "(modulo n d)",
+                "Returns n modulo d, which has the sign of d.",
+            ),
+            BuiltinFunction::Gcd => doc(
+                "(gcd n ...)",
+                "Returns the greatest common divisor of its integer arguments.",
+            ),
+            BuiltinFunction::Lcm => doc(
+                "(lcm n ...)",
+                "Returns the least common multiple of its integer arguments.",
+            ),
+            BuiltinFunction::Expt => doc(
+                "(expt base exp)",
+                "Raises base to the power exp, exactly for exact arguments and an integer exponent.",
+            ),
+            BuiltinFunction::ExactIntegerSqrt => doc(
+                "(exact-integer-sqrt n)",
+                "Returns a list (s r) where s is the integer square root of n and r is n - s*s.",
+            ),
+            BuiltinFunction::BitAnd => doc(
+                "(bit-and n ...)",
+                "Returns the bitwise and of its integer arguments.",
+            ),
+            BuiltinFunction::BitOr => doc(
+                "(bit-or n ...)",
+                "Returns the bitwise or of its integer arguments.",
+            ),
+            BuiltinFunction::BitXor => doc(
+                "(bit-xor n ...)",
+                "Returns the bitwise exclusive or of its integer arguments.",
+            ),
+            BuiltinFunction::BitNot => doc("(bit-not n)", "Returns the bitwise complement of n."),
+            BuiltinFunction::ArithmeticShift => doc(
+                "(arithmetic-shift n count)",
+                "Shifts n left by count bits, or right if count is negative.",
+            ),
+            BuiltinFunction::BitCount => doc(
+                "(bit-count n)",
+                "Counts the one bits of n, or the zero bits if n is negative.",
+            ),
+            BuiltinFunction::Numerator => doc(
+                "(numerator num)",
+                "Returns the numerator of num in lowest terms.",
+            ),
+            BuiltinFunction::Denominator => doc(
+                "(denominator num)",
+                "Returns the denominator of num in lowest terms, which is always positive.",
+            ),
+            BuiltinFunction::IsEq => doc(
+                "(eq? a b)",
+                "Returns #t if a and b are the same object: lists must share their storage, while atoms compare by value.",
+            ),
+            BuiltinFunction::IsEqv => doc(
+                "(eqv? a b)",
+                "Like eq?, with numbers equal only if they also agree in exactness, so (eqv? 2 2.0) is #f.",
+            ),
+            BuiltinFunction::IsEqual => doc(
+                "(equal? a b)",
+                "Returns #t if a and b have the same structure and their atoms are eqv?.",
+            ),
+            BuiltinFunction::StringCiEq => {
+                doc("(string-ci=? str ...)", "Like string=?, ignoring case.")
+            }
+            BuiltinFunction::StringCiLt => {
+                doc("(string-ci<? str ...)", "Like string<?, ignoring case.")
+            }
+            BuiltinFunction::StringCiGt => {
+                doc("(string-ci>? str ...)", "Like string>?, ignoring case.")
+            }
+            BuiltinFunction::StringCiLte => {
+                doc("(string-ci<=? str ...)", "Like string<=?, ignoring case.")
+            }
+            BuiltinFunction::StringCiGte => {
+                doc("(string-ci>=? str ...)", "Like string>=?, ignoring case.")
+            }
+            BuiltinFunction::Compare => doc(
+                "(compare a b)",
+                "Returns -1, 0 or 1 as a sorts before, together with or after b. Any two values can be compared: the empty list sorts first, then booleans, numbers, characters, strings, symbols, lists, vectors and bytevectors, which compare element by element.",
+            ),
+            BuiltinFunction::Sort => doc(
+                "(sort list)",
+                "Returns the elements of list in the order given by compare.",
+            ),
+            BuiltinFunction::MakeHashTable => doc(
+                "(make-hash-table)",
+                "Returns a new empty hash table. Keys are compared with equal? and entries keep their insertion order.",
+            ),
+            BuiltinFunction::HashRef => doc(
+                "(hash-ref table key [default])",
+                "Returns the value for key in table, or default if it is missing. A missing key without a default is an error.",
+            ),
+            BuiltinFunction::HashSet => doc(
+                "(hash-set! table key value)",
+                "Sets the value for key in table.",
+            ),
+            BuiltinFunction::HashDelete => doc(
+                "(hash-delete! table key)",
+                "Removes key from table, if present.",
+            ),
+            BuiltinFunction::HashContains => doc(
+                "(hash-contains? table key)",
+                "Returns #t if table has a value for key.",
+            ),
+            BuiltinFunction::HashCount => doc(
+                "(hash-count table)",
+                "Returns the number of entries in table.",
+            ),
+            BuiltinFunction::HashKeys => doc(
+                "(hash-keys table)",
+                "Returns the keys of table in insertion order.",
+            ),
+            BuiltinFunction::HashValues => doc(
+                "(hash-values table)",
+                "Returns the values of table in insertion order.",
+            ),
+            BuiltinFunction::HashToAlist => doc(
+                "(hash->alist table)",
+                "Returns the entries of table as a list of (key value) lists.",
+            ),
+            BuiltinFunction::HashForEach => doc(
+                "(hash-for-each table proc)",
+                "Calls proc with the key and value of each entry of table, in insertion order.",
+            ),
+            BuiltinFunction::IsHashTable => {
+                doc("(is-hash-table obj)", "Returns #t if obj is a hash table.")
+            }
+            BuiltinFunction::MakeVector => doc(
+                "(make-vector n [fill])",
+                "Returns a vector of n elements, each fill or the empty list.",
+            ),
+            BuiltinFunction::Vector => {
+                doc("(vector obj ...)", "Returns a vector of its arguments.")
+            }
+            BuiltinFunction::VectorRef => doc(
+                "(vector-ref vec k)",
+                "Returns element k of vec. An index out of range is an error.",
+            ),
+            BuiltinFunction::VectorSet => doc(
+                "(vector-set! vec k obj)",
+                "Replaces element k of vec with obj. An index out of range is an error.",
+            ),
+            BuiltinFunction::VectorLength => doc(
+                "(vector-length vec)",
+                "Returns the number of elements in vec.",
+            ),
+            BuiltinFunction::VectorFill => doc(
+                "(vector-fill! vec obj)",
+                "Replaces every element of vec with obj.",
+            ),
+            BuiltinFunction::VectorToList => doc(
+                "(vector->list vec)",
+                "Returns the elements of vec as a list.",
+            ),
+            BuiltinFunction::ListToVector => doc(
+                "(list->vector list)",
+                "Returns a vector of the elements of list.",
+            ),
+            BuiltinFunction::Subvector => doc(
+                "(subvector vec start [end])",
+                "Returns a new vector of the elements of vec from start up to end, which defaults to the end of vec.",
+            ),
+            BuiltinFunction::VectorMap => doc(
+                "(vector-map proc vec)",
+                "Returns a new vector of the results of calling proc on each element of vec.",
+            ),
+            BuiltinFunction::IsVector => doc("(is-vector obj)", "Returns #t if obj is a vector."),
+            BuiltinFunction::CharToInteger => doc(
+                "(char->integer char)",
+                "Returns the Unicode code point of char.",
+            ),
+            BuiltinFunction::IntegerToChar => doc(
+                "(integer->char n)",
+                "Returns the character with Unicode code point n.",
+            ),
+            BuiltinFunction::CharUpcase => {
+                doc("(char-upcase char)", "Returns the upper case form of char.")
+            }
+            BuiltinFunction::CharDowncase => doc(
+                "(char-downcase char)",
+                "Returns the lower case form of char.",
+            ),
+            BuiltinFunction::IsCharAlphabetic => {
+                doc("(char-alphabetic? char)", "Returns #t if char is a letter.")
+            }
+            BuiltinFunction::IsCharNumeric => {
+                doc("(char-numeric? char)", "Returns #t if char is a digit.")
+            }
+            BuiltinFunction::IsCharWhitespace => doc(
+                "(char-whitespace? char)",
+                "Returns #t if char is whitespace.",
+            ),
+            BuiltinFunction::CharEq => doc(
+                "(char=? char ...)",
+                "Returns #t if the code points of the characters are equal.",
+            ),
+            BuiltinFunction::CharLt => doc(
+                "(char<? char ...)",
+                "Returns #t if the code points of the characters are strictly increasing.",
+            ),
+            BuiltinFunction::CharGt => doc(
+                "(char>? char ...)",
+                "Returns #t if the code points of the characters are strictly decreasing.",
+            ),
+            BuiltinFunction::CharLte => doc(
+                "(char<=? char ...)",
+                "Returns #t if the code points of the characters are non-decreasing.",
+            ),
+            BuiltinFunction::CharGte => doc(
+                "(char>=? char ...)",
+                "Returns #t if the code points of the characters are non-increasing.",
+            ),
+            BuiltinFunction::CharCiEq => doc("(char-ci=? char ...)", "Like char=?, ignoring case."),
+            BuiltinFunction::CharCiLt => doc("(char-ci<? char ...)", "Like char<?, ignoring case."),
+            BuiltinFunction::CharCiGt => doc("(char-ci>? char ...)", "Like char>?, ignoring case."),
+            BuiltinFunction::CharCiLte => {
+                doc("(char-ci<=? char ...)", "Like char<=?, ignoring case.")
+            }
+            BuiltinFunction::CharCiGte => {
+                doc("(char-ci>=? char ...)", "Like char>=?, ignoring case.")
+            }
+            BuiltinFunction::StringRef => doc(
+                "(string-ref str k)",
+                "Returns character k of str. An index out of range is an error.",
+            ),
+            BuiltinFunction::IsChar => doc("(is-char obj)", "Returns #t if obj is a character."),
+            BuiltinFunction::MakeBytevector => doc(
+                "(make-bytevector n [byte])",
+                "Returns a bytevector of n bytes, each byte or 0.",
+            ),
+            BuiltinFunction::Bytevector => doc(
+                "(bytevector byte ...)",
+                "Returns a bytevector of its arguments, which must be integers from 0 to 255.",
+            ),
+            BuiltinFunction::BytevectorLength => doc(
+                "(bytevector-length bv)",
+                "Returns the number of bytes in bv.",
+            ),
+            BuiltinFunction::BytevectorU8Ref => doc(
+                "(bytevector-u8-ref bv k)",
+                "Returns byte k of bv. An index out of range is an error.",
+            ),
+            BuiltinFunction::BytevectorU8Set => doc(
+                "(bytevector-u8-set! bv k byte)",
+                "Replaces byte k of bv. An index out of range is an error.",
+            ),
+            BuiltinFunction::BytevectorCopy => doc(
+                "(bytevector-copy bv [start [end]])",
+                "Returns a new bytevector of the bytes of bv from start up to end, which default to the whole of bv.",
+            ),
+            BuiltinFunction::BytevectorAppend => doc(
+                "(bytevector-append bv ...)",
+                "Returns a new bytevector of the bytes of each bv in turn.",
+            ),
+            BuiltinFunction::Utf8ToString => doc(
+                "(utf8->string bv [start [end]])",
+                "Decodes the bytes of bv from start up to end as UTF-8. Invalid UTF-8 is an error.",
+            ),
+            BuiltinFunction::StringToUtf8 => {
+                doc("(string->utf8 str)", "Returns the UTF-8 encoding of str.")
+            }
+            BuiltinFunction::BytevectorUintRef => doc(
+                "(bytevector-uint-ref bv k endianness size)",
+                "Returns the unsigned integer stored in size bytes of bv from index k. endianness is 'big or 'little.",
+            ),
+            BuiltinFunction::BytevectorSintRef => doc(
+                "(bytevector-sint-ref bv k endianness size)",
+                "Like bytevector-uint-ref, reading a two's complement signed integer.",
+            ),
+            BuiltinFunction::BytevectorUintSet => doc(
+                "(bytevector-uint-set! bv k n endianness size)",
+                "Stores the unsigned integer n in size bytes of bv from index k. n must fit in size bytes.",
+            ),
+            BuiltinFunction::BytevectorSintSet => doc(
+                "(bytevector-sint-set! bv k n endianness size)",
+                "Like bytevector-uint-set!, storing n in two's complement.",
+            ),
+            BuiltinFunction::IsBytevector => {
+                doc("(is-bytevector obj)", "Returns #t if obj is a bytevector.")
+            }
+            BuiltinFunction::Gensym => doc(
+                "(gensym [prefix])",
+                "Returns a fresh uninterned symbol whose name is prefix, \"g\" by default, followed by a counter.",
+            ),
+            BuiltinFunction::StringToUninternedSymbol => doc(
+                "(string->uninterned-symbol str)",
+                "Returns a new symbol named str that is not eq? to any other symbol, including those read as str.",
+            ),
+            BuiltinFunction::IsSymbolInterned => doc(
+                "(symbol-interned? sym)",
+                "Returns #t if sym is interned, that is, if reading its name gives the same symbol.",
+            ),
+            BuiltinFunction::Force => doc(
+                "(force promise)",
+                "Returns the value of promise, evaluating its expression the first time. Any other value is returned as is.",
+            ),
+            BuiltinFunction::MakePromise => doc(
+                "(make-promise obj)",
+                "Returns a promise already forced to obj, or obj itself if it is a promise.",
+            ),
+            BuiltinFunction::IsPromise => {
+                doc("(is-promise obj)", "Returns #t if obj is a promise.")
+            }
+            BuiltinFunction::StreamCar => doc(
+                "(stream-car stream)",
+                "Returns the first element of a non-empty stream.",
+            ),
+            BuiltinFunction::StreamCdr => doc(
+                "(stream-cdr stream)",
+                "Forces and returns the rest of a non-empty stream.",
+            ),
+            BuiltinFunction::StreamTake => doc(
+                "(stream-take n stream)",
+                "Returns a stream of the first n elements of stream, or all of them if it is shorter.",
+            ),
+            BuiltinFunction::StreamMap => doc(
+                "(stream-map proc stream)",
+                "Returns a stream of the results of calling proc on each element of stream, computed as the stream is forced.",
+            ),
+            BuiltinFunction::StreamFilter => doc(
+                "(stream-filter pred stream)",
+                "Returns a stream of the elements of stream for which pred returns true.",
+            ),
+            BuiltinFunction::StreamToList => doc(
+                "(stream->list stream [n])",
+                "Returns a list of the elements of stream, or of at most the first n. Without n the stream must be finite.",
+            ),
+            BuiltinFunction::CallCC => doc(
+                "(call/cc proc) | (call-with-current-continuation proc)",
+                "Calls proc with a continuation k. Calling (k [value]) makes call/cc return value at once. Continuations are escape-only: k can only be called until call/cc returns.",
+            ),
+            BuiltinFunction::DynamicWind => doc(
+                "(dynamic-wind before thunk after)",
+                "Calls before, thunk and after, returning the result of thunk. after is called however thunk is left, including by calling a continuation or an error.",
+            ),
+            BuiltinFunction::MakeGenerator => doc(
+                "(make-generator proc)",
+                "Returns a generator g that runs (proc yield) on demand. Each call (g) runs proc until it calls (yield value) and returns value; once proc returns, (g) returns end-of-generator.",
+            ),
+            BuiltinFunction::GeneratorToList => doc(
+                "(generator->list gen [n])",
+                "Returns a list of the values gen yields until it ends, or of at most the next n.",
+            ),
+            BuiltinFunction::GeneratorToStream => doc(
+                "(generator->stream gen)",
+                "Returns a stream of the values gen yields, asking gen for each one only when the stream is forced that far.",
+            ),
+            BuiltinFunction::MakeParameter => doc(
+                "(make-parameter value [converter])",
+                "Returns a parameter p whose value (p) is value, passed through converter if given. parameterize rebinds it, passing new values through converter too.",
+            ),
+            BuiltinFunction::RandomSeed => doc(
+                "(random-seed! n)",
+                "Restarts the random number generator from the integer n. A seed gives the same sequence on every run and platform.",
+            ),
+            BuiltinFunction::RandomInteger => doc(
+                "(random-integer n)",
+                "Returns a random integer from 0 up to but not including n.",
+            ),
+            BuiltinFunction::RandomReal => doc(
+                "(random-real)",
+                "Returns a random float from 0 up to but not including 1.",
+            ),
+            BuiltinFunction::RandomChoice => doc(
+                "(random-choice seq)",
+                "Returns a random element of a non-empty list or vector.",
+            ),
+            BuiltinFunction::Shuffle => doc(
+                "(shuffle seq)",
+                "Returns a new list or vector of the elements of seq in random order.",
+            ),
+            BuiltinFunction::CurrentTime => doc(
+                "(current-time)",
+                "Returns the number of milliseconds since the Unix epoch.",
+            ),
+            BuiltinFunction::CurrentDate => doc(
+                "(current-date)",
+                "Returns the current date in the local time zone.",
+            ),
+            BuiltinFunction::MakeDate => doc(
+                "(make-date year month day [hour minute second [offset]])",
+                "Returns the date at the given time, by default midnight, at offset seconds east of UTC, by default 0.",
+            ),
+            BuiltinFunction::DateYear => doc("(date-year date)", "Returns the year of date."),
+            BuiltinFunction::DateMonth => doc(
+                "(date-month date)",
+                "Returns the month of date, from 1 to 12.",
+            ),
+            BuiltinFunction::DateDay => doc(
+                "(date-day date)",
+                "Returns the day of the month of date, from 1.",
+            ),
+            BuiltinFunction::DateHour => doc(
+                "(date-hour date)",
+                "Returns the hour of date, from 0 to 23.",
+            ),
+            BuiltinFunction::DateMinute => doc(
+                "(date-minute date)",
+                "Returns the minute of date, from 0 to 59.",
+            ),
+            BuiltinFunction::DateSecond => doc(
+                "(date-second date)",
+                "Returns the second of date, from 0 to 59.",
+            ),
+            BuiltinFunction::DateMillisecond => doc(
+                "(date-millisecond date)",
+                "Returns the milliseconds past the second of date.",
+            ),
+            BuiltinFunction::DateWeekDay => doc(
+                "(date-week-day date)",
+                "Returns the day of the week of date, from 0 for Sunday to 6 for Saturday.",
+            ),
+            BuiltinFunction::DateYearDay => doc(
+                "(date-year-day date)",
+                "Returns the day of the year of date, from 1 for the first of January.",
+            ),
+            BuiltinFunction::DateZoneOffset => doc(
+                "(date-zone-offset date)",
+                "Returns the UTC offset of date in seconds east of UTC.",
+            ),
+            BuiltinFunction::DateToString => doc(
+                "(date->string date [format])",
+                "Formats date as RFC 3339, or with a strftime format like \"%Y-%m-%d %H:%M\".",
+            ),
+            BuiltinFunction::StringToDate => doc(
+                "(string->date str [format])",
+                "Parses an RFC 3339 date, or one in a strftime format. Without an offset in the format the date is in UTC, and without a time it is at midnight.",
+            ),
+            BuiltinFunction::MakeDuration => doc(
+                "(make-duration days [hours minutes seconds [milliseconds]])",
+                "Returns a duration of the given length, which may be negative.",
+            ),
+            BuiltinFunction::DurationToMilliseconds => doc(
+                "(duration->milliseconds duration)",
+                "Returns the length of duration in milliseconds.",
+            ),
+            BuiltinFunction::DateAdd => doc(
+                "(date-add date duration)",
+                "Returns the date duration after date.",
+            ),
+            BuiltinFunction::DateDifference => doc(
+                "(date-difference a b)",
+                "Returns the duration from date b to date a.",
+            ),
+            BuiltinFunction::DateToUtc => doc(
+                "(date->utc date)",
+                "Returns the same moment as date in UTC.",
+            ),
+            BuiltinFunction::DateToLocal => doc(
+                "(date->local date)",
+                "Returns the same moment as date in the local time zone.",
+            ),
+            BuiltinFunction::DateToTime => doc(
+                "(date->time date)",
+                "Returns the number of milliseconds from the Unix epoch to date.",
+            ),
+            BuiltinFunction::TimeToDate => doc(
+                "(time->date ms)",
+                "Returns the UTC date ms milliseconds after the Unix epoch.",
+            ),
+            BuiltinFunction::Sleep => doc("(sleep ms)", "Waits for ms milliseconds."),
+            BuiltinFunction::ReadFile => doc(
+                "(read-file path)",
+                "Returns the contents of the file at path as a string.",
+            ),
+            BuiltinFunction::WriteFile => doc(
+                "(write-file path str)",
+                "Replaces the contents of the file at path with str, creating the file if needed.",
+            ),
+            BuiltinFunction::AppendFile => doc(
+                "(append-file path str)",
+                "Adds str to the end of the file at path, creating the file if needed.",
+            ),
+            BuiltinFunction::ReadLines => doc(
+                "(read-lines path)",
+                "Returns the lines of the file at path as a list of strings, without their line endings.",
+            ),
+            BuiltinFunction::FileExists => doc(
+                "(file-exists? path)",
+                "Returns true if there is a file or directory at path.",
+            ),
+            BuiltinFunction::DeleteFile => doc("(delete-file path)", "Deletes the file at path."),
+            BuiltinFunction::RenameFile => doc(
+                "(rename-file from to)",
+                "Moves the file at from to to, replacing any file already there.",
+            ),
+            BuiltinFunction::MakeDirectory => doc(
+                "(make-directory path)",
+                "Creates the directory at path along with any missing parent directories.",
+            ),
+            BuiltinFunction::DirectoryList => doc(
+                "(directory-list path)",
+                "Returns the sorted names of the entries in the directory at path.",
+            ),
+            BuiltinFunction::FileSize => doc(
+                "(file-size path)",
+                "Returns the size in bytes of the file at path.",
+            ),
+            BuiltinFunction::FileMtime => doc(
+                "(file-mtime path)",
+                "Returns the UTC date the file at path was last modified.",
+            ),
+            BuiltinFunction::IsGenerator => {
+                doc("(is-generator obj)", "Returns #t if obj is a generator.")
+            }
+        }
+    }
+}
+
+impl BuiltinMacro {
+    pub fn doc(&self) -> Doc {
+        match *self {
+            BuiltinMacro::Match => doc(
+                "(match expr (pattern body) ...)",
+                "Evaluates the body of the first pattern that matches expr.",
+            ),
+            BuiltinMacro::Define => doc(
+                "(define name expr) | (define (name arg ...) [docstring] body ...)",
+                "Binds name to the value of expr, or defines a function. A leading string in a function body becomes its documentation.",
+            ),
+            BuiltinMacro::Lambda => doc(
+                "(lambda (arg ...) body ...)",
+                "Returns an anonymous function.",
+            ),
+            BuiltinMacro::Let => doc(
+                "(let ((name expr) ...) body ...)",
+                "Evaluates body with each name bound to the value of its expr.",
+            ),
+            BuiltinMacro::If => doc(
+                "(if test then [else])",
+                "Evaluates then if test is true, otherwise else.",
+            ),
+            BuiltinMacro::Cond => doc(
+                "(cond (test body ...) ...)",
+                "Evaluates the body of the first clause whose test is true.",
+            ),
+            BuiltinMacro::And => doc(
+                "(and expr ...)",
+                "Evaluates each expr in turn, stopping at the first false one.",
+            ),
+            BuiltinMacro::Or => doc(
+                "(or expr ...)",
+                "Evaluates each expr in turn, stopping at the first true one.",
+            ),
+            BuiltinMacro::Set => doc(
+                "(set name expr)",
+                "Rebinds an existing name to the value of expr.",
+            ),
+            BuiltinMacro::Eval => doc("(eval expr)", "Evaluates the value of expr."),
+            BuiltinMacro::Parse => doc("(parse str)", "Parses str into an unevaluated expression."),
+            BuiltinMacro::Module => doc(
+                "(module name (export sym ...) body ...)",
+                "Evaluates body in a fresh environment and makes the exported names available to import.",
+            ),
+            BuiltinMacro::Import => doc(
+                "(import name | (prefix name p:) ...)",
+                "Binds the exported names of each module, loading it through the load path if needed.",
+            ),
+            BuiltinMacro::Delay => doc(
+                "(delay expr)",
+                "Returns a promise to evaluate expr when it is first forced. Later forces return the same value.",
+            ),
+            BuiltinMacro::DelayForce => doc(
+                "(delay-force expr)",
+                "Like delay, for an expr that gives another promise; forcing it forces that promise in constant space.",
+            ),
+            BuiltinMacro::Parameterize => doc(
+                "(parameterize ((param value) ...) body ...)",
+                "Evaluates body with each parameter bound to its value. The bindings are seen by everything body calls, and nowhere else. current-output-port can be bound to a port the same way.",
+            ),
+            BuiltinMacro::StreamCons => doc(
+                "(stream-cons first rest)",
+                "Returns a stream of first followed by the stream rest, which is only evaluated when the stream's cdr is forced.",
+            ),
+        }
+    }
+}
+
+/// Returns the text `help` prints for `name`, or None if it is unbound.
+pub fn help_text(name: &str, stg: &LexicalVarStorage) -> Option<String> {
+    let (signature, description) = if let Some(f) = BUILTINS_FUNC_MAP.get(name) {
+        let doc = f.doc();
+        (doc.signature.to_string(), doc.description.to_string())
+    } else if let Some(m) = BUILTINS_MACRO_MAP.get(name) {
+        let doc = m.doc();
+        (doc.signature.to_string(), doc.description.to_string())
+    } else if let Some(f) = stg.get_func(&Symbol::new(name)) {
+        (
+            f.signature(name),
+            f.doc()
+                .map(str::to_string)
+                .unwrap_or_else(|| "No documentation.".to_string()),
+        )
+    } else if let Some(v) = stg.get(&Symbol::new(name)) {
+        (name.to_string(), format!("Variable bound to {}.", v))
+    } else if let Some(v) = BUILTINS_CONST_MAP.get(name) {
+        (name.to_string(), format!("Constant bound to {}.", v))
+    } else {
+        return None;
+    };
+    Some(format!("{}\n    {}", signature, description))
+}
+
+/// Returns every builtin and bound name containing `pattern`, sorted.
+pub fn apropos(pattern: &str, stg: &LexicalVarStorage) -> Vec<String> {
+    BUILTINS_FUNC_MAP
+        .keys()
+        .chain(BUILTINS_MACRO_MAP.keys())
+        .chain(BUILTINS_CONST_MAP.keys())
+        .map(|k| k.to_string())
+        .chain(stg.names())
+        .filter(|name| name.contains(pattern))
+        .collect::<BTreeSet<String>>()
+        .into_iter()
+        .collect()
+}
@@ -1,14 +1,24 @@
 use crate::{
+    builtins::{
+        bytevector, character, control, date, equality, file, format, generator, hash_table,
+        integer, math, numeric, order, parameter, promise, random, stream, string, symbol, vector,
+    },
     errors::EvaluatorError,
-    evaluator::{lisp_eval_int, EvalReturnType},
+    evaluator::{call_procedure, into_cons, lisp_eval, lisp_eval_int, EvalReturnType},
+    loader,
 };
 
-use super::{scope::LexicalVarStorage, Cons, ConsValue};
+use super::{
+    doc, generator::end_of_generator, module::Module, port::Port, promise::Promise,
+    scope::LexicalVarStorage, symbol::Symbol, Cons, ConsValue,
+};
 
 use lazy_static::lazy_static;
 use std::{
+    cmp::Ordering,
     collections::{HashMap, HashSet},
     fmt::Display,
+    sync::Arc,
 };
 
 lazy_static! {
@@ -56,6 +66,190 @@ lazy_static! {
         m.insert("load", BuiltinFunction::Load);
         m.insert("exit", BuiltinFunction::Exit);
         m.insert("help", BuiltinFunction::Help);
+        m.insert("apropos", BuiltinFunction::Apropos);
+        m.insert("string-append", BuiltinFunction::StringAppend);
+        m.insert("substring", BuiltinFunction::Substring);
+        m.insert("string-length", BuiltinFunction::StringLength);
+        m.insert("string-split", BuiltinFunction::StringSplit);
+        m.insert("string-join", BuiltinFunction::StringJoin);
+        m.insert("string-trim", BuiltinFunction::StringTrim);
+        m.insert("string-upcase", BuiltinFunction::StringUpcase);
+        m.insert("string-downcase", BuiltinFunction::StringDowncase);
+        m.insert("string-contains", BuiltinFunction::StringContains);
+        m.insert("string-replace", BuiltinFunction::StringReplace);
+        m.insert("string->list", BuiltinFunction::StringToList);
+        m.insert("list->string", BuiltinFunction::ListToString);
+        m.insert("string->number", BuiltinFunction::StringToNumber);
+        m.insert("number->string", BuiltinFunction::NumberToString);
+        m.insert("string->symbol", BuiltinFunction::StringToSymbol);
+        m.insert("symbol->string", BuiltinFunction::SymbolToString);
+        m.insert("string=?", BuiltinFunction::StringEq);
+        m.insert("string<?", BuiltinFunction::StringLt);
+        m.insert("string>?", BuiltinFunction::StringGt);
+        m.insert("string<=?", BuiltinFunction::StringLte);
+        m.insert("string>=?", BuiltinFunction::StringGte);
+        m.insert("format", BuiltinFunction::Format);
+        m.insert("current-output-port", BuiltinFunction::CurrentOutputPort);
+        m.insert("open-output-string", BuiltinFunction::OpenOutputString);
+        m.insert("get-output-string", BuiltinFunction::GetOutputString);
+        m.insert("abs", BuiltinFunction::Abs);
+        m.insert("min", BuiltinFunction::Min);
+        m.insert("max", BuiltinFunction::Max);
+        m.insert("floor", BuiltinFunction::Floor);
+        m.insert("ceiling", BuiltinFunction::Ceiling);
+        m.insert("round", BuiltinFunction::Round);
+        m.insert("truncate", BuiltinFunction::Truncate);
+        m.insert("sqrt", BuiltinFunction::Sqrt);
+        m.insert("exp", BuiltinFunction::Exp);
+        m.insert("log", BuiltinFunction::Log);
+        m.insert("sin", BuiltinFunction::Sin);
+        m.insert("cos", BuiltinFunction::Cos);
+        m.insert("tan", BuiltinFunction::Tan);
+        m.insert("asin", BuiltinFunction::Asin);
+        m.insert("acos", BuiltinFunction::Acos);
+        m.insert("atan", BuiltinFunction::Atan);
+        m.insert("exact->inexact", BuiltinFunction::ExactToInexact);
+        m.insert("inexact->exact", BuiltinFunction::InexactToExact);
+        m.insert("quotient", BuiltinFunction::Quotient);
+        m.insert("remainder", BuiltinFunction::Remainder);
+        m.insert("modulo", BuiltinFunction::Modulo);
+        m.insert("gcd", BuiltinFunction::Gcd);
+        m.insert("lcm", BuiltinFunction::Lcm);
+        m.insert("expt", BuiltinFunction::Expt);
+        m.insert("exact-integer-sqrt", BuiltinFunction::ExactIntegerSqrt);
+        m.insert("bit-and", BuiltinFunction::BitAnd);
+        m.insert("bit-or", BuiltinFunction::BitOr);
+        m.insert("bit-xor", BuiltinFunction::BitXor);
+        m.insert("bit-not", BuiltinFunction::BitNot);
+        m.insert("arithmetic-shift", BuiltinFunction::ArithmeticShift);
+        m.insert("bit-count", BuiltinFunction::BitCount);
+        m.insert("numerator", BuiltinFunction::Numerator);
+        m.insert("denominator", BuiltinFunction::Denominator);
+        m.insert("eq?", BuiltinFunction::IsEq);
+        m.insert("eqv?", BuiltinFunction::IsEqv);
+        m.insert("equal?", BuiltinFunction::IsEqual);
+        m.insert("string-ci=?", BuiltinFunction::StringCiEq);
+        m.insert("string-ci<?", BuiltinFunction::StringCiLt);
+        m.insert("string-ci>?", BuiltinFunction::StringCiGt);
+        m.insert("string-ci<=?", BuiltinFunction::StringCiLte);
+        m.insert("string-ci>=?", BuiltinFunction::StringCiGte);
+        m.insert("compare", BuiltinFunction::Compare);
+        m.insert("sort", BuiltinFunction::Sort);
+        m.insert("make-hash-table", BuiltinFunction::MakeHashTable);
+        m.insert("hash-ref", BuiltinFunction::HashRef);
+        m.insert("hash-set!", BuiltinFunction::HashSet);
+        m.insert("hash-delete!", BuiltinFunction::HashDelete);
+        m.insert("hash-contains?", BuiltinFunction::HashContains);
+        m.insert("hash-count", BuiltinFunction::HashCount);
+        m.insert("hash-keys", BuiltinFunction::HashKeys);
+        m.insert("hash-values", BuiltinFunction::HashValues);
+        m.insert("hash->alist", BuiltinFunction::HashToAlist);
+        m.insert("hash-for-each", BuiltinFunction::HashForEach);
+        m.insert("is-hash-table", BuiltinFunction::IsHashTable);
+        m.insert("make-vector", BuiltinFunction::MakeVector);
+        m.insert("vector", BuiltinFunction::Vector);
+        m.insert("vector-ref", BuiltinFunction::VectorRef);
+        m.insert("vector-set!", BuiltinFunction::VectorSet);
+        m.insert("vector-length", BuiltinFunction::VectorLength);
+        m.insert("vector-fill!", BuiltinFunction::VectorFill);
+        m.insert("vector->list", BuiltinFunction::VectorToList);
+        m.insert("list->vector", BuiltinFunction::ListToVector);
+        m.insert("subvector", BuiltinFunction::Subvector);
+        m.insert("vector-map", BuiltinFunction::VectorMap);
+        m.insert("is-vector", BuiltinFunction::IsVector);
+        m.insert("char->integer", BuiltinFunction::CharToInteger);
+        m.insert("integer->char", BuiltinFunction::IntegerToChar);
+        m.insert("char-upcase", BuiltinFunction::CharUpcase);
+        m.insert("char-downcase", BuiltinFunction::CharDowncase);
+        m.insert("char-alphabetic?", BuiltinFunction::IsCharAlphabetic);
+        m.insert("char-numeric?", BuiltinFunction::IsCharNumeric);
+        m.insert("char-whitespace?", BuiltinFunction::IsCharWhitespace);
+        m.insert("char=?", BuiltinFunction::CharEq);
+        m.insert("char<?", BuiltinFunction::CharLt);
+        m.insert("char>?", BuiltinFunction::CharGt);
+        m.insert("char<=?", BuiltinFunction::CharLte);
+        m.insert("char>=?", BuiltinFunction::CharGte);
+        m.insert("char-ci=?", BuiltinFunction::CharCiEq);
+        m.insert("char-ci<?", BuiltinFunction::CharCiLt);
+        m.insert("char-ci>?", BuiltinFunction::CharCiGt);
+        m.insert("char-ci<=?", BuiltinFunction::CharCiLte);
+        m.insert("char-ci>=?", BuiltinFunction::CharCiGte);
+        m.insert("string-ref", BuiltinFunction::StringRef);
+        m.insert("is-char", BuiltinFunction::IsChar);
+        m.insert("make-bytevector", BuiltinFunction::MakeBytevector);
+        m.insert("bytevector", BuiltinFunction::Bytevector);
+        m.insert("bytevector-length", BuiltinFunction::BytevectorLength);
+        m.insert("bytevector-u8-ref", BuiltinFunction::BytevectorU8Ref);
+        m.insert("bytevector-u8-set!", BuiltinFunction::BytevectorU8Set);
+        m.insert("bytevector-copy", BuiltinFunction::BytevectorCopy);
+        m.insert("bytevector-append", BuiltinFunction::BytevectorAppend);
+        m.insert("utf8->string", BuiltinFunction::Utf8ToString);
+        m.insert("string->utf8", BuiltinFunction::StringToUtf8);
+        m.insert("bytevector-uint-ref", BuiltinFunction::BytevectorUintRef);
+        m.insert("bytevector-sint-ref", BuiltinFunction::BytevectorSintRef);
+        m.insert("bytevector-uint-set!", BuiltinFunction::BytevectorUintSet);
+        m.insert("bytevector-sint-set!", BuiltinFunction::BytevectorSintSet);
+        m.insert("is-bytevector", BuiltinFunction::IsBytevector);
+        m.insert("gensym", BuiltinFunction::Gensym);
+        m.insert("string->uninterned-symbol", BuiltinFunction::StringToUninternedSymbol);
+        m.insert("symbol-interned?", BuiltinFunction::IsSymbolInterned);
+        m.insert("force", BuiltinFunction::Force);
+        m.insert("make-promise", BuiltinFunction::MakePromise);
+        m.insert("is-promise", BuiltinFunction::IsPromise);
+        m.insert("stream-car", BuiltinFunction::StreamCar);
+        m.insert("stream-cdr", BuiltinFunction::StreamCdr);
+        m.insert("stream-take", BuiltinFunction::StreamTake);
+        m.insert("stream-map", BuiltinFunction::StreamMap);
+        m.insert("stream-filter", BuiltinFunction::StreamFilter);
+        m.insert("stream->list", BuiltinFunction::StreamToList);
+        m.insert("call-with-current-continuation", BuiltinFunction::CallCC);
+        m.insert("call/cc", BuiltinFunction::CallCC);
+        m.insert("dynamic-wind", BuiltinFunction::DynamicWind);
+        m.insert("make-generator", BuiltinFunction::MakeGenerator);
+        m.insert("generator->list", BuiltinFunction::GeneratorToList);
+        m.insert("generator->stream", BuiltinFunction::GeneratorToStream);
+        m.insert("is-generator", BuiltinFunction::IsGenerator);
+        m.insert("make-parameter", BuiltinFunction::MakeParameter);
+        m.insert("random-seed!", BuiltinFunction::RandomSeed);
+        m.insert("random-integer", BuiltinFunction::RandomInteger);
+        m.insert("random-real", BuiltinFunction::RandomReal);
+        m.insert("random-choice", BuiltinFunction::RandomChoice);
+        m.insert("shuffle", BuiltinFunction::Shuffle);
+        m.insert("current-time", BuiltinFunction::CurrentTime);
+        m.insert("current-date", BuiltinFunction::CurrentDate);
+        m.insert("make-date", BuiltinFunction::MakeDate);
+        m.insert("date-year", BuiltinFunction::DateYear);
+        m.insert("date-month", BuiltinFunction::DateMonth);
+        m.insert("date-day", BuiltinFunction::DateDay);
+        m.insert("date-hour", BuiltinFunction::DateHour);
+        m.insert("date-minute", BuiltinFunction::DateMinute);
+        m.insert("date-second", BuiltinFunction::DateSecond);
+        m.insert("date-millisecond", BuiltinFunction::DateMillisecond);
+        m.insert("date-week-day", BuiltinFunction::DateWeekDay);
+        m.insert("date-year-day", BuiltinFunction::DateYearDay);
+        m.insert("date-zone-offset", BuiltinFunction::DateZoneOffset);
+        m.insert("date->string", BuiltinFunction::DateToString);
+        m.insert("string->date", BuiltinFunction::StringToDate);
+        m.insert("make-duration", BuiltinFunction::MakeDuration);
+        m.insert("duration->milliseconds", BuiltinFunction::DurationToMilliseconds);
+        m.insert("date-add", BuiltinFunction::DateAdd);
+        m.insert("date-difference", BuiltinFunction::DateDifference);
+        m.insert("date->utc", BuiltinFunction::DateToUtc);
+        m.insert("date->local", BuiltinFunction::DateToLocal);
+        m.insert("date->time", BuiltinFunction::DateToTime);
+        m.insert("time->date", BuiltinFunction::TimeToDate);
+        m.insert("sleep", BuiltinFunction::Sleep);
+        m.insert("read-file", BuiltinFunction::ReadFile);
+        m.insert("write-file", BuiltinFunction::WriteFile);
+        m.insert("append-file", BuiltinFunction::AppendFile);
+        m.insert("read-lines", BuiltinFunction::ReadLines);
+        m.insert("file-exists?", BuiltinFunction::FileExists);
+        m.insert("delete-file", BuiltinFunction::DeleteFile);
+        m.insert("rename-file", BuiltinFunction::RenameFile);
+        m.insert("make-directory", BuiltinFunction::MakeDirectory);
+        m.insert("directory-list", BuiltinFunction::DirectoryList);
+        m.insert("file-size", BuiltinFunction::FileSize);
+        m.insert("file-mtime", BuiltinFunction::FileMtime);
         m
     };
     pub static ref BUILTINS_FUNC_SET: HashSet<&'static str> =
@@ -73,10 +267,39 @@ lazy_static! {
         m.insert("or", BuiltinMacro::Or);
         m.insert("eval", BuiltinMacro::Eval);
         m.insert("parse", BuiltinMacro::Parse);
+        m.insert("module", BuiltinMacro::Module);
+        m.insert("import", BuiltinMacro::Import);
+        m.insert("delay", BuiltinMacro::Delay);
+        m.insert("delay-force", BuiltinMacro::DelayForce);
+        m.insert("stream-cons", BuiltinMacro::StreamCons);
+        m.insert("parameterize", BuiltinMacro::Parameterize);
         m
     };
     pub static ref BUILTINS_MACRO_SET: HashSet<&'static str> =
         BUILTINS_MACRO_MAP.keys().cloned().collect();
+    /// Names bound to a value in every environment unless shadowed by a
+    /// variable.
+    pub static ref BUILTINS_CONST_MAP: HashMap<&'static str, ConsValue> = {
+        let mut m = HashMap::new();
+        m.insert("pi", ConsValue::Float(std::f64::consts::PI));
+        m.insert("e", ConsValue::Float(std::f64::consts::E));
+        m.insert("end-of-generator", end_of_generator());
+        m
+    };
+    /// The builtin maps keyed by interned symbol, so that evaluating a symbol
+    /// does not hash its name.
+    static ref BUILTINS_FUNC_BY_SYMBOL: HashMap<Symbol, BuiltinFunction> = BUILTINS_FUNC_MAP
+        .iter()
+        .map(|(name, f)| (Symbol::new(name), *f))
+        .collect();
+    static ref BUILTINS_MACRO_BY_SYMBOL: HashMap<Symbol, BuiltinMacro> = BUILTINS_MACRO_MAP
+        .iter()
+        .map(|(name, m)| (Symbol::new(name), *m))
+        .collect();
+    pub(crate) static ref BUILTINS_CONST_BY_SYMBOL: HashMap<Symbol, ConsValue> = BUILTINS_CONST_MAP
+        .iter()
+        .map(|(name, v)| (Symbol::new(name), v.clone()))
+        .collect();
 }
 
 #[derive(Debug, Clone, Copy, PartialEq)]
@@ -123,359 +346,609 @@ pub enum BuiltinFunction {
     Load,
     Exit,
     Help,
+    Apropos,
+    StringAppend,
+    Substring,
+    StringLength,
+    StringSplit,
+    StringJoin,
+    StringTrim,
+    StringUpcase,
+    StringDowncase,
+    StringContains,
+    StringReplace,
+    StringToList,
+    ListToString,
+    StringToNumber,
+    NumberToString,
+    StringToSymbol,
+    SymbolToString,
+    StringEq,
+    StringLt,
+    StringGt,
+    StringLte,
+    StringGte,
+    Format,
+    CurrentOutputPort,
+    OpenOutputString,
+    GetOutputString,
+    Abs,
+    Min,
+    Max,
+    Floor,
+    Ceiling,
+    Round,
+    Truncate,
+    Sqrt,
+    Exp,
+    Log,
+    Sin,
+    Cos,
+    Tan,
+    Asin,
+    Acos,
+    Atan,
+    ExactToInexact,
+    InexactToExact,
+    Quotient,
+    Remainder,
+    Modulo,
+    Gcd,
+    Lcm,
+    Expt,
+    ExactIntegerSqrt,
+    BitAnd,
+    BitOr,
+    BitXor,
+    BitNot,
+    ArithmeticShift,
+    BitCount,
+    Numerator,
+    Denominator,
+    IsEq,
+    IsEqv,
+    IsEqual,
+    StringCiEq,
+    StringCiLt,
+    StringCiGt,
+    StringCiLte,
+    StringCiGte,
+    Compare,
+    Sort,
+    MakeHashTable,
+    HashRef,
+    HashSet,
+    HashDelete,
+    HashContains,
+    HashCount,
+    HashKeys,
+    HashValues,
+    HashToAlist,
+    HashForEach,
+    IsHashTable,
+    MakeVector,
+    Vector,
+    VectorRef,
+    VectorSet,
+    VectorLength,
+    VectorFill,
+    VectorToList,
+    ListToVector,
+    Subvector,
+    VectorMap,
+    IsVector,
+    CharToInteger,
+    IntegerToChar,
+    CharUpcase,
+    CharDowncase,
+    IsCharAlphabetic,
+    IsCharNumeric,
+    IsCharWhitespace,
+    CharEq,
+    CharLt,
+    CharGt,
+    CharLte,
+    CharGte,
+    CharCiEq,
+    CharCiLt,
+    CharCiGt,
+    CharCiLte,
+    CharCiGte,
+    StringRef,
+    IsChar,
+    MakeBytevector,
+    Bytevector,
+    BytevectorLength,
+    BytevectorU8Ref,
+    BytevectorU8Set,
+    BytevectorCopy,
+    BytevectorAppend,
+    Utf8ToString,
+    StringToUtf8,
+    BytevectorUintRef,
+    BytevectorSintRef,
+    BytevectorUintSet,
+    BytevectorSintSet,
+    IsBytevector,
+    Gensym,
+    StringToUninternedSymbol,
+    IsSymbolInterned,
+    Force,
+    MakePromise,
+    IsPromise,
+    StreamCar,
+    StreamCdr,
+    StreamTake,
+    StreamMap,
+    StreamFilter,
+    StreamToList,
+    CallCC,
+    DynamicWind,
+    MakeGenerator,
+    GeneratorToList,
+    GeneratorToStream,
+    IsGenerator,
+    MakeParameter,
+    RandomSeed,
+    RandomInteger,
+    RandomReal,
+    RandomChoice,
+    Shuffle,
+    CurrentTime,
+    CurrentDate,
+    MakeDate,
+    DateYear,
+    DateMonth,
+    DateDay,
+    DateHour,
+    DateMinute,
+    DateSecond,
+    DateMillisecond,
+    DateWeekDay,
+    DateYearDay,
+    DateZoneOffset,
+    DateToString,
+    StringToDate,
+    MakeDuration,
+    DurationToMilliseconds,
+    DateAdd,
+    DateDifference,
+    DateToUtc,
+    DateToLocal,
+    DateToTime,
+    TimeToDate,
+    Sleep,
+    ReadFile,
+    WriteFile,
+    AppendFile,
+    ReadLines,
+    FileExists,
+    DeleteFile,
+    RenameFile,
+    MakeDirectory,
+    DirectoryList,
+    FileSize,
+    FileMtime,
 }
 
 impl BuiltinFunction {
     pub fn get(symbol: &Cons) -> Option<Self> {
         if let Cons::Value(ConsValue::Symbol(s)) = symbol {
-            BUILTINS_FUNC_MAP.get(s.as_str()).copied()
+            BUILTINS_FUNC_BY_SYMBOL.get(s).copied()
         } else {
             None
         }
     }
-    pub fn call(&self, args: Vec<Cons>) -> Result<Cons, EvaluatorError> {
+    pub fn call(
+        &self,
+        args: Vec<Cons>,
+        stg: &mut LexicalVarStorage,
+    ) -> Result<Cons, EvaluatorError> {
         match *self {
-            BuiltinFunction::Add => {
-                let mut sum = 0.0;
-                for (index, arg) in args.iter().enumerate() {
-                    if let Cons::Value(ConsValue::Float(f)) = arg {
-                        if index == 0 {
-                            sum += f;
-                        } else {
-                            sum += f;
-                        }
-                    } else if let Cons::Value(ConsValue::Int(i)) = arg {
-                        if index == 0 {
-                            sum += *i as f64;
-                        } else {
-                            sum += *i as f64;
-                        }
-                    } else {
-                        return Err(EvaluatorError::InvalidArgument(
-                            "Invalid argument type for +".to_string(),
-                        ));
-                    }
-                }
-                if sum.fract() == 0.0 {
-                    Ok(Cons::Value(ConsValue::Int(sum as i64)))
-                } else {
-                    Ok(Cons::Value(ConsValue::Float(sum)))
+            BuiltinFunction::Add => numeric::add(&args),
+            BuiltinFunction::Sub => numeric::sub(&args),
+            BuiltinFunction::Mul => numeric::mul(&args),
+            BuiltinFunction::Div => numeric::div(&args),
+            BuiltinFunction::FloorDiv => numeric::floor_div(&args),
+            BuiltinFunction::Mod => integer::rem_all(&args),
+            BuiltinFunction::Pow => numeric::pow(&args),
+            BuiltinFunction::Eq => numeric::compare("=", &args, &[Ordering::Equal]),
+            BuiltinFunction::Neq => match numeric::compare("!=", &args, &[Ordering::Equal])? {
+                Cons::Value(ConsValue::Boolean(equal)) => {
+                    Ok(Cons::Value(ConsValue::Boolean(!equal)))
                 }
+                other => Ok(other),
+            },
+            BuiltinFunction::Lt => order::ordered("<", &args, &[Ordering::Less]),
+            BuiltinFunction::Gt => order::ordered(">", &args, &[Ordering::Greater]),
+            BuiltinFunction::Lte => order::ordered("<=", &args, &[Ordering::Less, Ordering::Equal]),
+            BuiltinFunction::Gte => {
+                order::ordered(">=", &args, &[Ordering::Greater, Ordering::Equal])
             }
-            BuiltinFunction::Sub => {
-                if args.len() == 1 {
-                    if let Cons::Value(ConsValue::Float(f)) = &args[0] {
-                        return Ok(Cons::Value(ConsValue::Float(-f)));
-                    } else if let Cons::Value(ConsValue::Int(i)) = &args[0] {
-                        return Ok(Cons::Value(ConsValue::Int(-i)));
-                    } else {
-                        return Err(EvaluatorError::InvalidArgument(
-                            "Invalid argument type for -".to_string(),
-                        ));
-                    }
-                }
-                let mut sum = 0.0;
-                for (index, arg) in args.iter().enumerate() {
-                    if let Cons::Value(ConsValue::Float(f)) = arg {
-                        if index == 0 {
-                            sum += f;
-                        } else {
-                            sum -= f;
-                        }
-                    } else if let Cons::Value(ConsValue::Int(i)) = arg {
-                        if index == 0 {
-                            sum += *i as f64;
-                        } else {
-                            sum -= *i as f64;
-                        }
-                    } else {
-                        return Err(EvaluatorError::InvalidArgument(
-                            "Invalid argument type for -".to_string(),
-                        ));
-                    }
-                }
-                if sum.fract() == 0.0 {
-                    Ok(Cons::Value(ConsValue::Int(sum as i64)))
-                } else {
-                    Ok(Cons::Value(ConsValue::Float(sum)))
+            BuiltinFunction::IsInt => match args.as_slice() {
+                [arg] => Ok(Cons::Value(ConsValue::Boolean(matches!(
+                    arg,
+                    Cons::Value(ConsValue::Int(_) | ConsValue::BigInt(_))
+                )))),
+                _ => Err(EvaluatorError::InvalidArgument(
+                    "Wrong number of arguments for is-int".to_string(),
+                )),
+            },
+            BuiltinFunction::IsFloat => match args.as_slice() {
+                [arg] => Ok(Cons::Value(ConsValue::Boolean(matches!(
+                    arg,
+                    Cons::Value(ConsValue::Float(_))
+                )))),
+                _ => Err(EvaluatorError::InvalidArgument(
+                    "Wrong number of arguments for is-float".to_string(),
+                )),
+            },
+            BuiltinFunction::Print | BuiltinFunction::Println => {
+                let mut text = args
+                    .iter()
+                    .map(|arg| arg.to_display_string())
+                    .collect::<Vec<String>>()
+                    .join(" ");
+                if *self == BuiltinFunction::Println {
+                    text.push('\n');
                 }
+                stg.output().write_str(&text)?;
+                Ok(Cons::Value(ConsValue::NIL))
             }
-            BuiltinFunction::Mul => {
-                let mut sum = 1.0;
-                for (index, arg) in args.iter().enumerate() {
-                    if let Cons::Value(ConsValue::Float(f)) = arg {
-                        if index == 0 {
-                            sum *= f;
-                        } else {
-                            sum *= f;
-                        }
-                    } else if let Cons::Value(ConsValue::Int(i)) = arg {
-                        if index == 0 {
-                            sum *= *i as f64;
-                        } else {
-                            sum *= *i as f64;
-                        }
-                    } else {
-                        return Err(EvaluatorError::InvalidArgument(
-                            "Invalid argument type for *".to_string(),
-                        ));
+            BuiltinFunction::Load => {
+                if let [Cons::Value(ConsValue::String(name))] = args.as_slice() {
+                    loader::check_file_system("load", stg)?;
+                    let load_paths = stg.modules().lock().unwrap().load_paths().to_vec();
+                    match loader::resolve_path(name, &load_paths) {
+                        Some(path) => loader::load_file(&path, stg),
+                        None => Err(EvaluatorError::LoadError(format!(
+                            "Could not find file {}",
+                            name
+                        ))),
                     }
-                }
-                if sum.fract() == 0.0 {
-                    Ok(Cons::Value(ConsValue::Int(sum as i64)))
                 } else {
-                    Ok(Cons::Value(ConsValue::Float(sum)))
+                    Err(EvaluatorError::InvalidArgument(
+                        "Invalid argument type for load".to_string(),
+                    ))
                 }
             }
-            BuiltinFunction::Div => {
-                if args.len() == 1 {
-                    if let Cons::Value(ConsValue::Int(i)) = &args[0] {
-                        return Ok(Cons::Value(ConsValue::Float(1.0 / *i as f64)));
-                    } else if let Cons::Value(ConsValue::Float(f)) = &args[0] {
-                        return Ok(Cons::Value(ConsValue::Float(1.0 / *f)));
-                    } else {
-                        return Err(EvaluatorError::InvalidArgument(
-                            "Invalid argument type for /".to_string(),
-                        ));
-                    }
-                }
-                let mut sum = 1.0;
-                for (index, arg) in args.iter().enumerate() {
-                    if let Cons::Value(ConsValue::Float(f)) = arg {
-                        if index == 0 {
-                            sum *= f;
-                        } else {
-                            sum /= f;
-                        }
-                    } else if let Cons::Value(ConsValue::Int(i)) = arg {
-                        if index == 0 {
-                            sum *= *i as f64;
-                        } else {
-                            sum /= *i as f64;
-                        }
-                    } else {
+            BuiltinFunction::Exit => {
+                let code = match args.as_slice() {
+                    [] | [Cons::Value(ConsValue::Boolean(true))] => 0,
+                    [Cons::Value(ConsValue::Boolean(false))] => 1,
+                    [Cons::Value(ConsValue::Int(i))] => i32::try_from(*i).map_err(|_| {
+                        EvaluatorError::InvalidArgument(format!("Exit code {} out of range", i))
+                    })?,
+                    _ => {
                         return Err(EvaluatorError::InvalidArgument(
-                            "Invalid argument type for /".to_string(),
+                            "Invalid argument type for exit".to_string(),
                         ));
                     }
-                }
-                if sum.fract() == 0.0 {
-                    Ok(Cons::Value(ConsValue::Int(sum as i64)))
-                } else {
-                    Ok(Cons::Value(ConsValue::Float(sum)))
-                }
+                };
+                Err(EvaluatorError::Exit(code))
             }
-            BuiltinFunction::FloorDiv => {
-                let mut sum = 1.0;
-                for (index, arg) in args.iter().enumerate() {
-                    if let Cons::Value(ConsValue::Float(f)) = arg {
-                        if index == 0 {
-                            sum *= f;
-                        } else {
-                            sum /= f;
-                        }
-                    } else if let Cons::Value(ConsValue::Int(i)) = arg {
-                        if index == 0 {
-                            sum *= *i as f64;
-                        } else {
-                            sum /= *i as f64;
-                        }
-                    } else {
+            BuiltinFunction::Help => {
+                let name = match args.as_slice() {
+                    [Cons::Value(ConsValue::Symbol(name))] => name.as_str(),
+                    [Cons::Value(ConsValue::String(name))] => name.as_str(),
+                    _ => {
                         return Err(EvaluatorError::InvalidArgument(
-                            "Invalid argument type for //".to_string(),
+                            "Invalid argument type for help".to_string(),
                         ));
                     }
-                }
-                Ok(Cons::Value(ConsValue::Int(sum as i64)))
-            }
-            BuiltinFunction::Mod => {
-                let mut sum = 1.0;
-                for (index, arg) in args.iter().enumerate() {
-                    if let Cons::Value(ConsValue::Float(f)) = arg {
-                        if index == 0 {
-                            sum *= f;
-                        } else {
-                            sum %= f;
-                        }
-                    } else if let Cons::Value(ConsValue::Int(i)) = arg {
-                        if index == 0 {
-                            sum *= *i as f64;
-                        } else {
-                            sum %= *i as f64;
-                        }
-                    } else {
-                        return Err(EvaluatorError::InvalidArgument(
-                            "Invalid argument type for %".to_string(),
-                        ));
+                };
+                match doc::help_text(name, stg) {
+                    Some(text) => {
+                        stg.output().write_str(&format!("{}\n", text))?;
+                        Ok(Cons::Value(ConsValue::NIL))
                     }
-                }
-                if sum.fract() == 0.0 {
-                    Ok(Cons::Value(ConsValue::Int(sum as i64)))
-                } else {
-                    Ok(Cons::Value(ConsValue::Float(sum)))
+                    None => Err(EvaluatorError::UndefinedSymbol(name.to_string())),
                 }
             }
-            BuiltinFunction::Pow => {
-                let mut sum = 1.0;
-                for (index, arg) in args.iter().enumerate() {
-                    if let Cons::Value(ConsValue::Float(f)) = arg {
-                        if index == 0 {
-                            sum *= f;
-                        } else {
-                            sum = sum.powf(*f);
-                        }
-                    } else if let Cons::Value(ConsValue::Int(i)) = arg {
-                        if index == 0 {
-                            sum *= *i as f64;
-                        } else {
-                            sum = sum.powf(*i as f64);
-                        }
-                    } else {
+            BuiltinFunction::Apropos => {
+                let pattern = match args.as_slice() {
+                    [Cons::Value(ConsValue::String(pattern))] => pattern.as_str(),
+                    [Cons::Value(ConsValue::Symbol(pattern))] => pattern.as_str(),
+                    _ => {
                         return Err(EvaluatorError::InvalidArgument(
-                            "Invalid argument type for ^".to_string(),
+                            "Invalid argument type for apropos".to_string(),
                         ));
                     }
-                }
-                if sum.fract() == 0.0 {
-                    Ok(Cons::Value(ConsValue::Int(sum as i64)))
-                } else {
-                    Ok(Cons::Value(ConsValue::Float(sum)))
-                }
+                };
+                Ok(doc::apropos(pattern, stg)
+                    .into_iter()
+                    .map(|name| Cons::Value(ConsValue::symbol(&name)))
+                    .collect())
             }
-            BuiltinFunction::Eq => Ok(Cons::Value(ConsValue::Boolean(
-                args.iter().all(|arg| arg == &args[0]),
-            ))),
-            BuiltinFunction::Neq => Ok(Cons::Value(ConsValue::Boolean(
-                args.iter().any(|arg| arg != &args[0]),
-            ))),
-            BuiltinFunction::Lt => {
-                for (left, right) in args.iter().zip(args.iter().skip(1)) {
-                    match (left, right) {
-                        (Cons::Value(ConsValue::Int(l)), Cons::Value(ConsValue::Int(r))) => {
-                            if l >= r {
-                                return Ok(Cons::Value(ConsValue::Boolean(false)));
-                            }
-                        }
-                        (Cons::Value(ConsValue::Float(l)), Cons::Value(ConsValue::Float(r))) => {
-                            if l >= r {
-                                return Ok(Cons::Value(ConsValue::Boolean(false)));
-                            }
-                        }
-                        (Cons::Value(ConsValue::Int(l)), Cons::Value(ConsValue::Float(r))) => {
-                            if *l as f64 >= *r {
-                                return Ok(Cons::Value(ConsValue::Boolean(false)));
-                            }
-                        }
-                        (Cons::Value(ConsValue::Float(l)), Cons::Value(ConsValue::Int(r))) => {
-                            if *l >= *r as f64 {
-                                return Ok(Cons::Value(ConsValue::Boolean(false)));
-                            }
-                        }
-                        _ => {
-                            return Err(EvaluatorError::InvalidArgument(
-                                "Invalid argument type for <".to_string(),
-                            ))
-                        }
-                    }
-                }
-                Ok(Cons::Value(ConsValue::Boolean(true)))
-            }
-            BuiltinFunction::Gt => {
-                for (left, right) in args.iter().zip(args.iter().skip(1)) {
-                    match (left, right) {
-                        (Cons::Value(ConsValue::Int(l)), Cons::Value(ConsValue::Int(r))) => {
-                            if l <= r {
-                                return Ok(Cons::Value(ConsValue::Boolean(false)));
-                            }
-                        }
-                        (Cons::Value(ConsValue::Float(l)), Cons::Value(ConsValue::Float(r))) => {
-                            if l <= r {
-                                return Ok(Cons::Value(ConsValue::Boolean(false)));
-                            }
-                        }
-                        (Cons::Value(ConsValue::Int(l)), Cons::Value(ConsValue::Float(r))) => {
-                            if *l as f64 <= *r {
-                                return Ok(Cons::Value(ConsValue::Boolean(false)));
-                            }
-                        }
-                        (Cons::Value(ConsValue::Float(l)), Cons::Value(ConsValue::Int(r))) => {
-                            if *l <= *r as f64 {
-                                return Ok(Cons::Value(ConsValue::Boolean(false)));
-                            }
-                        }
-                        _ => {
-                            return Err(EvaluatorError::InvalidArgument(
-                                "Invalid argument type for >".to_string(),
-                            ))
-                        }
-                    }
-                }
-                Ok(Cons::Value(ConsValue::Boolean(true)))
-            }
-            BuiltinFunction::Lte => {
-                for (left, right) in args.iter().zip(args.iter().skip(1)) {
-                    match (left, right) {
-                        (Cons::Value(ConsValue::Int(l)), Cons::Value(ConsValue::Int(r))) => {
-                            if l > r {
-                                return Ok(Cons::Value(ConsValue::Boolean(false)));
-                            }
-                        }
-                        (Cons::Value(ConsValue::Float(l)), Cons::Value(ConsValue::Float(r))) => {
-                            if l > r {
-                                return Ok(Cons::Value(ConsValue::Boolean(false)));
-                            }
-                        }
-                        (Cons::Value(ConsValue::Int(l)), Cons::Value(ConsValue::Float(r))) => {
-                            if *l as f64 > *r {
-                                return Ok(Cons::Value(ConsValue::Boolean(false)));
-                            }
-                        }
-                        (Cons::Value(ConsValue::Float(l)), Cons::Value(ConsValue::Int(r))) => {
-                            if *l > *r as f64 {
-                                return Ok(Cons::Value(ConsValue::Boolean(false)));
-                            }
-                        }
-                        _ => {
-                            return Err(EvaluatorError::InvalidArgument(
-                                "Invalid argument type for <=".to_string(),
-                            ))
-                        }
-                    }
-                }
-                Ok(Cons::Value(ConsValue::Boolean(true)))
+            BuiltinFunction::StringAppend => string::append(&args),
+            BuiltinFunction::Substring => string::substring(&args),
+            BuiltinFunction::StringLength => string::length(&args),
+            BuiltinFunction::StringSplit => string::split(&args),
+            BuiltinFunction::StringJoin => string::join(&args),
+            BuiltinFunction::StringTrim => string::trim(&args),
+            BuiltinFunction::StringUpcase => string::upcase(&args),
+            BuiltinFunction::StringDowncase => string::downcase(&args),
+            BuiltinFunction::StringContains => string::contains(&args),
+            BuiltinFunction::StringReplace => string::replace(&args),
+            BuiltinFunction::StringToList => string::to_list(&args),
+            BuiltinFunction::ListToString => string::from_list(&args),
+            BuiltinFunction::StringToNumber => string::to_number(&args),
+            BuiltinFunction::NumberToString => string::from_number(&args),
+            BuiltinFunction::StringToSymbol => string::to_symbol(&args),
+            BuiltinFunction::SymbolToString => string::from_symbol(&args),
+            BuiltinFunction::StringEq => string::compare("string=?", &args, &[Ordering::Equal]),
+            BuiltinFunction::StringLt => string::compare("string<?", &args, &[Ordering::Less]),
+            BuiltinFunction::StringGt => string::compare("string>?", &args, &[Ordering::Greater]),
+            BuiltinFunction::StringLte => {
+                string::compare("string<=?", &args, &[Ordering::Less, Ordering::Equal])
             }
-            BuiltinFunction::Gte => {
-                for (left, right) in args.iter().zip(args.iter().skip(1)) {
-                    match (left, right) {
-                        (Cons::Value(ConsValue::Int(l)), Cons::Value(ConsValue::Int(r))) => {
-                            if l < r {
-                                return Ok(Cons::Value(ConsValue::Boolean(false)));
-                            }
-                        }
-                        (Cons::Value(ConsValue::Float(l)), Cons::Value(ConsValue::Float(r))) => {
-                            if l < r {
-                                return Ok(Cons::Value(ConsValue::Boolean(false)));
-                            }
-                        }
-                        (Cons::Value(ConsValue::Int(l)), Cons::Value(ConsValue::Float(r))) => {
-                            if (*l as f64) < *r {
-                                return Ok(Cons::Value(ConsValue::Boolean(false)));
-                            }
-                        }
-                        (Cons::Value(ConsValue::Float(l)), Cons::Value(ConsValue::Int(r))) => {
-                            if *l < *r as f64 {
-                                return Ok(Cons::Value(ConsValue::Boolean(false)));
-                            }
-                        }
-                        _ => {
-                            return Err(EvaluatorError::InvalidArgument(
-                                "Invalid argument type for >=".to_string(),
-                            ))
-                        }
-                    }
-                }
-                Ok(Cons::Value(ConsValue::Boolean(true)))
+            BuiltinFunction::StringGte => {
+                string::compare("string>=?", &args, &[Ordering::Greater, Ordering::Equal])
+            }
+            BuiltinFunction::Format => format::format(&args, stg),
+            BuiltinFunction::CurrentOutputPort => {
+                Ok(Cons::Value(ConsValue::Port(stg.output().clone())))
+            }
+            BuiltinFunction::OpenOutputString => Ok(Cons::Value(ConsValue::Port(Port::string()))),
+            BuiltinFunction::GetOutputString => match args.as_slice() {
+                [Cons::Value(ConsValue::Port(port))] => match port.contents() {
+                    Some(contents) => Ok(Cons::Value(ConsValue::String(contents))),
+                    None => Err(EvaluatorError::InvalidArgument(
+                        "get-output-string needs a string port".to_string(),
+                    )),
+                },
+                _ => Err(EvaluatorError::InvalidArgument(
+                    "Invalid argument type for get-output-string".to_string(),
+                )),
+            },
+            BuiltinFunction::Abs => math::abs(&args),
+            BuiltinFunction::Min => math::min(&args),
+            BuiltinFunction::Max => math::max(&args),
+            BuiltinFunction::Floor => math::floor(&args),
+            BuiltinFunction::Ceiling => math::ceiling(&args),
+            BuiltinFunction::Round => math::round(&args),
+            BuiltinFunction::Truncate => math::truncate(&args),
+            BuiltinFunction::Sqrt => math::sqrt(&args),
+            BuiltinFunction::Exp => math::exp(&args),
+            BuiltinFunction::Log => math::log(&args),
+            BuiltinFunction::Sin => math::sin(&args),
+            BuiltinFunction::Cos => math::cos(&args),
+            BuiltinFunction::Tan => math::tan(&args),
+            BuiltinFunction::Asin => math::asin(&args),
+            BuiltinFunction::Acos => math::acos(&args),
+            BuiltinFunction::Atan => math::atan(&args),
+            BuiltinFunction::ExactToInexact => math::exact_to_inexact(&args),
+            BuiltinFunction::InexactToExact => math::inexact_to_exact(&args),
+            BuiltinFunction::Quotient => integer::quotient(&args),
+            BuiltinFunction::Remainder => integer::remainder(&args),
+            BuiltinFunction::Modulo => integer::modulo(&args),
+            BuiltinFunction::Gcd => integer::gcd(&args),
+            BuiltinFunction::Lcm => integer::lcm(&args),
+            BuiltinFunction::Expt => integer::expt(&args),
+            BuiltinFunction::ExactIntegerSqrt => integer::exact_integer_sqrt(&args),
+            BuiltinFunction::BitAnd => integer::bit_and(&args),
+            BuiltinFunction::BitOr => integer::bit_or(&args),
+            BuiltinFunction::BitXor => integer::bit_xor(&args),
+            BuiltinFunction::BitNot => integer::bit_not(&args),
+            BuiltinFunction::ArithmeticShift => integer::arithmetic_shift(&args),
+            BuiltinFunction::BitCount => integer::bit_count(&args),
+            BuiltinFunction::Numerator => numeric::numerator(&args),
+            BuiltinFunction::Denominator => numeric::denominator(&args),
+            BuiltinFunction::IsEq => equality::compare("eq?", &args, equality::is_eq),
+            BuiltinFunction::IsEqv => equality::compare("eqv?", &args, equality::is_eqv),
+            BuiltinFunction::IsEqual => equality::compare("equal?", &args, equality::is_equal),
+            BuiltinFunction::StringCiEq => {
+                string::compare_ci("string-ci=?", &args, &[Ordering::Equal])
+            }
+            BuiltinFunction::StringCiLt => {
+                string::compare_ci("string-ci<?", &args, &[Ordering::Less])
+            }
+            BuiltinFunction::StringCiGt => {
+                string::compare_ci("string-ci>?", &args, &[Ordering::Greater])
+            }
+            BuiltinFunction::StringCiLte => {
+                string::compare_ci("string-ci<=?", &args, &[Ordering::Less, Ordering::Equal])
+            }
+            BuiltinFunction::StringCiGte => {
+                string::compare_ci("string-ci>=?", &args, &[Ordering::Greater, Ordering::Equal])
+            }
+            BuiltinFunction::Compare => order::compare(&args),
+            BuiltinFunction::Sort => order::sort(&args),
+            BuiltinFunction::MakeHashTable => hash_table::make(&args),
+            BuiltinFunction::HashRef => hash_table::get(&args),
+            BuiltinFunction::HashSet => hash_table::set(&args),
+            BuiltinFunction::HashDelete => hash_table::delete(&args),
+            BuiltinFunction::HashContains => hash_table::contains(&args),
+            BuiltinFunction::HashCount => hash_table::count(&args),
+            BuiltinFunction::HashKeys => hash_table::keys(&args),
+            BuiltinFunction::HashValues => hash_table::values(&args),
+            BuiltinFunction::HashToAlist => hash_table::to_alist(&args),
+            BuiltinFunction::IsHashTable => match args.as_slice() {
+                [arg] => Ok(Cons::Value(ConsValue::Boolean(matches!(
+                    arg,
+                    Cons::Value(ConsValue::HashTable(_))
+                )))),
+                _ => Err(EvaluatorError::InvalidArgument(
+                    "Invalid argument type for is-hash-table".to_string(),
+                )),
+            },
+            BuiltinFunction::MakeVector => vector::make(&args),
+            BuiltinFunction::Vector => vector::from_args(&args),
+            BuiltinFunction::VectorRef => vector::get(&args),
+            BuiltinFunction::VectorSet => vector::set(&args),
+            BuiltinFunction::VectorLength => vector::length(&args),
+            BuiltinFunction::VectorFill => vector::fill(&args),
+            BuiltinFunction::VectorToList => vector::to_list(&args),
+            BuiltinFunction::ListToVector => vector::from_list(&args),
+            BuiltinFunction::Subvector => vector::subvector(&args),
+            BuiltinFunction::IsVector => match args.as_slice() {
+                [arg] => Ok(Cons::Value(ConsValue::Boolean(matches!(
+                    arg,
+                    Cons::Value(ConsValue::Vector(_))
+                )))),
+                _ => Err(EvaluatorError::InvalidArgument(
+                    "Invalid argument type for is-vector".to_string(),
+                )),
+            },
+            BuiltinFunction::CharToInteger => character::to_integer(&args),
+            BuiltinFunction::IntegerToChar => character::from_integer(&args),
+            BuiltinFunction::CharUpcase => character::upcase(&args),
+            BuiltinFunction::CharDowncase => character::downcase(&args),
+            BuiltinFunction::IsCharAlphabetic => {
+                character::test("char-alphabetic?", &args, char::is_alphabetic)
+            }
+            BuiltinFunction::IsCharNumeric => {
+                character::test("char-numeric?", &args, char::is_numeric)
+            }
+            BuiltinFunction::IsCharWhitespace => {
+                character::test("char-whitespace?", &args, char::is_whitespace)
+            }
+            BuiltinFunction::CharEq => character::compare("char=?", &args, &[Ordering::Equal]),
+            BuiltinFunction::CharLt => character::compare("char<?", &args, &[Ordering::Less]),
+            BuiltinFunction::CharGt => character::compare("char>?", &args, &[Ordering::Greater]),
+            BuiltinFunction::CharLte => {
+                character::compare("char<=?", &args, &[Ordering::Less, Ordering::Equal])
+            }
+            BuiltinFunction::CharGte => {
+                character::compare("char>=?", &args, &[Ordering::Greater, Ordering::Equal])
+            }
+            BuiltinFunction::CharCiEq => {
+                character::compare_ci("char-ci=?", &args, &[Ordering::Equal])
+            }
+            BuiltinFunction::CharCiLt => {
+                character::compare_ci("char-ci<?", &args, &[Ordering::Less])
+            }
+            BuiltinFunction::CharCiGt => {
+                character::compare_ci("char-ci>?", &args, &[Ordering::Greater])
+            }
+            BuiltinFunction::CharCiLte => {
+                character::compare_ci("char-ci<=?", &args, &[Ordering::Less, Ordering::Equal])
+            }
+            BuiltinFunction::CharCiGte => {
+                character::compare_ci("char-ci>=?", &args, &[Ordering::Greater, Ordering::Equal])
+            }
+            BuiltinFunction::StringRef => string::char_at(&args),
+            BuiltinFunction::IsChar => match args.as_slice() {
+                [arg] => Ok(Cons::Value(ConsValue::Boolean(matches!(
+                    arg,
+                    Cons::Value(ConsValue::Char(_))
+                )))),
+                _ => Err(EvaluatorError::InvalidArgument(
+                    "Invalid argument type for is-char".to_string(),
+                )),
+            },
+            BuiltinFunction::MakeBytevector => bytevector::make(&args),
+            BuiltinFunction::Bytevector => bytevector::from_args(&args),
+            BuiltinFunction::BytevectorLength => bytevector::length(&args),
+            BuiltinFunction::BytevectorU8Ref => bytevector::get(&args),
+            BuiltinFunction::BytevectorU8Set => bytevector::set(&args),
+            BuiltinFunction::BytevectorCopy => bytevector::copy(&args),
+            BuiltinFunction::BytevectorAppend => bytevector::append(&args),
+            BuiltinFunction::Utf8ToString => bytevector::to_string(&args),
+            BuiltinFunction::StringToUtf8 => bytevector::from_string(&args),
+            BuiltinFunction::BytevectorUintRef => {
+                bytevector::int_ref("bytevector-uint-ref", &args, false)
+            }
+            BuiltinFunction::BytevectorSintRef => {
+                bytevector::int_ref("bytevector-sint-ref", &args, true)
+            }
+            BuiltinFunction::BytevectorUintSet => {
+                bytevector::int_set("bytevector-uint-set!", &args, false)
             }
+            BuiltinFunction::BytevectorSintSet => {
+                bytevector::int_set("bytevector-sint-set!", &args, true)
+            }
+            BuiltinFunction::IsBytevector => match args.as_slice() {
+                [arg] => Ok(Cons::Value(ConsValue::Boolean(matches!(
+                    arg,
+                    Cons::Value(ConsValue::Bytevector(_))
+                )))),
+                _ => Err(EvaluatorError::InvalidArgument(
+                    "Invalid argument type for is-bytevector".to_string(),
+                )),
+            },
+            BuiltinFunction::Gensym => symbol::gensym(&args),
+            BuiltinFunction::StringToUninternedSymbol => symbol::uninterned(&args),
+            BuiltinFunction::IsSymbolInterned => symbol::is_interned(&args),
+            BuiltinFunction::Force => promise::force(&args),
+            BuiltinFunction::MakePromise => promise::make_promise(&args),
+            BuiltinFunction::IsPromise => match args.as_slice() {
+                [arg] => Ok(Cons::Value(ConsValue::Boolean(matches!(
+                    arg,
+                    Cons::Value(ConsValue::Promise(_))
+                )))),
+                _ => Err(EvaluatorError::InvalidArgument(
+                    "Invalid argument type for is-promise".to_string(),
+                )),
+            },
+            BuiltinFunction::StreamCar => stream::car(&args),
+            BuiltinFunction::StreamCdr => stream::cdr(&args),
+            BuiltinFunction::StreamTake => stream::take(&args, stg),
+            BuiltinFunction::StreamToList => stream::to_list(&args),
+            BuiltinFunction::GeneratorToList => generator::to_list(&args),
+            BuiltinFunction::RandomSeed => random::seed(&args, stg),
+            BuiltinFunction::RandomInteger => random::integer(&args, stg),
+            BuiltinFunction::RandomReal => random::real(&args, stg),
+            BuiltinFunction::RandomChoice => random::choice(&args, stg),
+            BuiltinFunction::Shuffle => random::shuffle(&args, stg),
+            BuiltinFunction::CurrentTime => date::current_time(&args, stg),
+            BuiltinFunction::CurrentDate => date::current_date(&args, stg),
+            BuiltinFunction::MakeDate => date::make(&args),
+            BuiltinFunction::DateYear => date::field("date-year", &args, date::year),
+            BuiltinFunction::DateMonth => date::field("date-month", &args, date::month),
+            BuiltinFunction::DateDay => date::field("date-day", &args, date::day),
+            BuiltinFunction::DateHour => date::field("date-hour", &args, date::hour),
+            BuiltinFunction::DateMinute => date::field("date-minute", &args, date::minute),
+            BuiltinFunction::DateSecond => date::field("date-second", &args, date::second),
+            BuiltinFunction::DateMillisecond => {
+                date::field("date-millisecond", &args, date::millisecond)
+            }
+            BuiltinFunction::DateWeekDay => date::field("date-week-day", &args, date::week_day),
+            BuiltinFunction::DateYearDay => date::field("date-year-day", &args, date::year_day),
+            BuiltinFunction::DateZoneOffset => {
+                date::field("date-zone-offset", &args, date::zone_offset)
+            }
+            BuiltinFunction::DateToString => date::to_string(&args),
+            BuiltinFunction::StringToDate => date::from_string(&args),
+            BuiltinFunction::MakeDuration => date::make_duration(&args),
+            BuiltinFunction::DurationToMilliseconds => date::duration_to_millis(&args),
+            BuiltinFunction::DateAdd => date::add(&args),
+            BuiltinFunction::DateDifference => date::difference(&args),
+            BuiltinFunction::DateToUtc => date::to_utc(&args),
+            BuiltinFunction::DateToLocal => date::to_local(&args, stg),
+            BuiltinFunction::DateToTime => date::to_time(&args),
+            BuiltinFunction::TimeToDate => date::from_time(&args),
+            BuiltinFunction::Sleep => date::sleep(&args, stg),
+            BuiltinFunction::ReadFile => file::read(&args, stg),
+            BuiltinFunction::WriteFile => file::write("write-file", false, &args, stg),
+            BuiltinFunction::AppendFile => file::write("append-file", true, &args, stg),
+            BuiltinFunction::ReadLines => file::read_lines(&args, stg),
+            BuiltinFunction::FileExists => file::exists(&args, stg),
+            BuiltinFunction::DeleteFile => file::delete(&args, stg),
+            BuiltinFunction::RenameFile => file::rename(&args, stg),
+            BuiltinFunction::MakeDirectory => file::make_directory(&args, stg),
+            BuiltinFunction::DirectoryList => file::directory_list(&args, stg),
+            BuiltinFunction::FileSize => file::size(&args, stg),
+            BuiltinFunction::FileMtime => file::mtime(&args, stg),
+            BuiltinFunction::GeneratorToStream => generator::to_stream(&args, stg),
+            BuiltinFunction::IsGenerator => match args.as_slice() {
+                [arg] => Ok(Cons::Value(ConsValue::Boolean(matches!(
+                    arg,
+                    Cons::Value(ConsValue::Generator(_))
+                )))),
+                _ => Err(EvaluatorError::InvalidArgument(
+                    "Invalid argument type for is-generator".to_string(),
+                )),
+            },
+            BuiltinFunction::Apply => control::apply(&args, stg),
+            BuiltinFunction::CallCC => control::call_cc(&args, stg),
+            BuiltinFunction::DynamicWind => control::dynamic_wind(&args, stg),
+            BuiltinFunction::HashForEach => hash_table::for_each(&args, stg),
+            BuiltinFunction::VectorMap => vector::map(&args, stg),
+            BuiltinFunction::StreamMap => stream::map(&args, stg),
+            BuiltinFunction::StreamFilter => stream::filter(&args, stg),
+            BuiltinFunction::MakeGenerator => generator::make(&args, stg),
+            BuiltinFunction::MakeParameter => parameter::make(&args, stg),
             _ => Ok(Cons::Value(ConsValue::NIL)),
         }
     }
@@ -494,8 +967,15 @@ pub enum BuiltinMacro {
     Set,
     Eval,
     Parse,
+    Module,
+    Import,
+    Delay,
+    DelayForce,
+    StreamCons,
+    Parameterize,
 }
 
+#[allow(clippy::large_enum_variant)]
 pub enum MacroReturn {
     None,
     Value(Cons),
@@ -505,7 +985,7 @@ pub enum MacroReturn {
 impl BuiltinMacro {
     pub fn get(symbol: &Cons) -> Option<Self> {
         if let Cons::Value(ConsValue::Symbol(s)) = symbol {
-            BUILTINS_MACRO_MAP.get(s.as_str()).copied()
+            BUILTINS_MACRO_BY_SYMBOL.get(s).copied()
         } else {
             None
         }
@@ -521,13 +1001,19 @@ impl BuiltinMacro {
                 // Here we execute (define name body)
                 // this is both variables and lambda functions
                 if let Cons::Value(ConsValue::Symbol(s)) = name {
-                    match lisp_eval_int(&body, stg)? {
+                    // body is the list (expr); evaluating the list itself
+                    // would call whatever expr gives.
+                    let expr = match body.split() {
+                        Some((expr, rest)) if rest.is_nil() => expr,
+                        _ => body,
+                    };
+                    match lisp_eval_int(&expr, stg)? {
                         EvalReturnType::CONS(c) => {
                             stg.put(&s, c);
                             Ok(MacroReturn::Value(Cons::Value(ConsValue::NIL)))
                         }
                         EvalReturnType::USER(f) => {
-                            stg.put_func(&s, f);
+                            stg.put_func(&s, Arc::unwrap_or_clone(f));
                             Ok(MacroReturn::Value(Cons::Value(ConsValue::NIL)))
                         }
                         _ => Err(EvaluatorError::InvalidArgument(
@@ -536,7 +1022,13 @@ impl BuiltinMacro {
                     }
                     // Here we execute (define (func-name arg1 ...) (body1) ...)
                 } else if let Some((car, cdr)) = name.split() {
-                    let uf = UserFunction::new(cdr.clone(), body.clone(), stg.fork());
+                    // A leading string is the docstring, unless it is the whole body.
+                    let uf = match body.split() {
+                        Some((Cons::Value(ConsValue::String(doc)), rest)) if !rest.is_nil() => {
+                            UserFunction::new(cdr.clone(), rest, stg.fork()).with_doc(doc)
+                        }
+                        _ => UserFunction::new(cdr.clone(), body.clone(), stg.fork()),
+                    };
                     if let Cons::Value(ConsValue::Symbol(s)) = car {
                         stg.put_func(&s, uf);
                         Ok(MacroReturn::Value(Cons::Value(ConsValue::NIL)))
@@ -560,6 +1052,163 @@ impl BuiltinMacro {
                     stg.fork(),
                 )))
             }
+            BuiltinMacro::Module => {
+                // Here we execute (module name (export sym ...) body ...)
+                let (name, rest) = args.split().ok_or_else(|| {
+                    EvaluatorError::InvalidArgument("Missing module name".to_string())
+                })?;
+                let Cons::Value(ConsValue::Symbol(name)) = name else {
+                    return Err(EvaluatorError::InvalidArgument(
+                        "Invalid module name".to_string(),
+                    ));
+                };
+                let (exports, body) = rest.split().ok_or_else(|| {
+                    EvaluatorError::InvalidArgument(format!(
+                        "Module {} is missing an export list",
+                        name
+                    ))
+                })?;
+                let exports = match exports {
+                    Cons::Cell(head, names)
+                        if *head == Cons::Value(ConsValue::symbol("export")) =>
+                    {
+                        Cons::clone(&names)
+                            .into_iter()
+                            .map(|n| match n {
+                                Cons::Value(ConsValue::Symbol(s)) => Ok(s.to_string()),
+                                _ => Err(EvaluatorError::InvalidArgument(format!(
+                                    "Invalid export {} in module {}",
+                                    n, name
+                                ))),
+                            })
+                            .collect::<Result<Vec<String>, EvaluatorError>>()?
+                    }
+                    _ => {
+                        return Err(EvaluatorError::InvalidArgument(format!(
+                            "Module {} is missing an export list",
+                            name
+                        )));
+                    }
+                };
+
+                let mut module_stg = stg.detached();
+                for expr in body {
+                    lisp_eval_int(&expr, &mut module_stg)?;
+                }
+                let module = Module::from_storage(name.as_str(), &exports, &module_stg)?;
+                stg.modules().lock().unwrap().register(module);
+                Ok(MacroReturn::Value(Cons::Value(ConsValue::NIL)))
+            }
+            BuiltinMacro::Import => {
+                // Here we execute (import name (prefix name p:) ...)
+                for spec in args.clone() {
+                    let (name, prefix) = match &spec {
+                        Cons::Value(ConsValue::Symbol(name)) => (name.to_string(), String::new()),
+                        Cons::Cell(..) => {
+                            match spec.clone().into_iter().collect::<Vec<_>>().as_slice() {
+                                [Cons::Value(ConsValue::Symbol(kw)), Cons::Value(ConsValue::Symbol(name)), Cons::Value(ConsValue::Symbol(prefix))]
+                                    if kw.as_str() == "prefix" =>
+                                {
+                                    (name.to_string(), prefix.to_string())
+                                }
+                                _ => {
+                                    return Err(EvaluatorError::InvalidArgument(format!(
+                                        "Invalid import specification {}",
+                                        spec
+                                    )));
+                                }
+                            }
+                        }
+                        _ => {
+                            return Err(EvaluatorError::InvalidArgument(format!(
+                                "Invalid import specification {}",
+                                spec
+                            )));
+                        }
+                    };
+                    loader::require_module(&name, stg)?.import_into(stg, &prefix);
+                }
+                Ok(MacroReturn::Value(Cons::Value(ConsValue::NIL)))
+            }
+            BuiltinMacro::Delay | BuiltinMacro::DelayForce => {
+                // Here we execute (delay expr), capturing the environment to
+                // evaluate expr in once it is forced.
+                let args: Vec<Cons> = args.clone().into_iter().collect();
+                let [expr] = args.as_slice() else {
+                    return Err(EvaluatorError::InvalidArgument(format!(
+                        "{:?} takes a single expression",
+                        self
+                    )));
+                };
+                let promise = if *self == BuiltinMacro::Delay {
+                    Promise::delayed(expr.clone(), stg.fork())
+                } else {
+                    Promise::delay_force(expr.clone(), stg.fork())
+                };
+                Ok(MacroReturn::Value(Cons::Value(ConsValue::Promise(promise))))
+            }
+            BuiltinMacro::StreamCons => {
+                // Here we execute (stream-cons first rest): first is evaluated
+                // now and rest only when the stream's cdr is forced.
+                let args: Vec<Cons> = args.clone().into_iter().collect();
+                let [first, rest] = args.as_slice() else {
+                    return Err(EvaluatorError::InvalidArgument(
+                        "stream-cons takes a first element and the rest of the stream".to_string(),
+                    ));
+                };
+                let first = lisp_eval(first, &mut stg.fork())?;
+                let rest = Promise::delayed(rest.clone(), stg.fork());
+                Ok(MacroReturn::Value(Cons::Cell(
+                    Arc::new(first),
+                    Arc::new(Cons::Value(ConsValue::Promise(rest))),
+                )))
+            }
+            BuiltinMacro::Parameterize => {
+                // Here we execute (parameterize ((param value) ...) body ...)
+                let (bindings, body) = args.split().ok_or_else(|| {
+                    EvaluatorError::InvalidArgument("Missing parameterize bindings".to_string())
+                })?;
+                // Every value is computed before any parameter is rebound.
+                let mut env = stg.fork();
+                for binding in bindings {
+                    let binding: Vec<Cons> = binding.into_iter().collect();
+                    let [param, value] = binding.as_slice() else {
+                        return Err(EvaluatorError::InvalidArgument(
+                            "Invalid parameterize binding".to_string(),
+                        ));
+                    };
+                    let value = lisp_eval(value, &mut stg.fork())?;
+                    match lisp_eval_int(param, &mut stg.fork())? {
+                        EvalReturnType::CONS(Cons::Value(ConsValue::Parameter(p))) => {
+                            let value = match p.converter() {
+                                Some(c) => into_cons(call_procedure(c, vec![value], stg)?)?,
+                                None => value,
+                            };
+                            env.bind_parameter(p, value);
+                        }
+                        EvalReturnType::FUNC(BuiltinFunction::CurrentOutputPort) => match value {
+                            Cons::Value(ConsValue::Port(port)) => env.set_output(port),
+                            _ => {
+                                return Err(EvaluatorError::InvalidArgument(format!(
+                                    "current-output-port can only be bound to a port, got {}",
+                                    value
+                                )));
+                            }
+                        },
+                        _ => {
+                            return Err(EvaluatorError::InvalidArgument(format!(
+                                "{} is not a parameter",
+                                param
+                            )));
+                        }
+                    }
+                }
+                let mut result = Cons::Value(ConsValue::NIL);
+                for expr in body {
+                    result = lisp_eval(&expr, &mut env)?;
+                }
+                Ok(MacroReturn::Value(result))
+            }
             _ => Err(EvaluatorError::UndefinedSymbol(
                 "Undefined symbol".to_string(),
             )),
@@ -572,6 +1221,8 @@ pub struct UserFunction {
     args: Cons,
     body: Cons,
     _environ: LexicalVarStorage,
+    module_scope: Option<Arc<LexicalVarStorage>>,
+    doc: Option<String>,
 }
 
 impl UserFunction {
@@ -580,6 +1231,33 @@ impl UserFunction {
             args,
             body,
             _environ,
+            module_scope: None,
+            doc: None,
+        }
+    }
+    pub fn with_doc(self, doc: String) -> Self {
+        Self {
+            doc: Some(doc),
+            ..self
+        }
+    }
+    pub fn doc(&self) -> Option<&str> {
+        self.doc.as_deref()
+    }
+    /// Renders how this function is called when bound to `name`.
+    pub fn signature(&self, name: &str) -> String {
+        let args: String = Cons::clone(&self.args)
+            .into_iter()
+            .map(|arg| format!(" {}", arg))
+            .collect();
+        format!("({}{})", name, args)
+    }
+    /// Returns a copy of this function whose body runs in the environment of
+    /// the module that exported it rather than in the caller's.
+    pub fn with_module_scope(&self, scope: Arc<LexicalVarStorage>) -> Self {
+        Self {
+            module_scope: Some(scope),
+            ..self.clone()
         }
     }
     pub(crate) fn call(
@@ -588,21 +1266,23 @@ impl UserFunction {
         stg: &mut LexicalVarStorage,
     ) -> Result<EvalReturnType, EvaluatorError> {
         let args: Vec<Cons> = args.into_iter().collect();
-        let mut combined_environment = stg.fork();
+        let mut combined_environment = match &self.module_scope {
+            Some(scope) => scope.fork(),
+            None => stg.fork(),
+        };
         for (index, elem) in Cons::clone(&self.args).into_iter().enumerate() {
             if let Cons::Value(ConsValue::Symbol(s)) = elem {
                 log::debug!("Adding arg: {} = {:?}", s, args);
-                match lisp_eval_int(&args[index], stg)? {
-                    EvalReturnType::CONS(c) => {
-                        combined_environment.put(&s, c);
-                    }
+                let arg = args.get(index).ok_or_else(|| {
+                    EvaluatorError::InvalidArgument(format!("Missing argument {}", s))
+                })?;
+                match lisp_eval_int(arg, stg)? {
                     EvalReturnType::USER(f) => {
-                        combined_environment.put_func(&s, f);
+                        combined_environment.put_func(&s, Arc::unwrap_or_clone(f));
                     }
-                    _ => {
-                        return Err(EvaluatorError::InvalidArgument(
-                            "Invalid argument".to_string(),
-                        ))
+                    // Builtins passed by name become procedure values.
+                    arg => {
+                        combined_environment.put(&s, into_cons(arg)?);
                     }
                 }
                 // combined_environment.put(&s, lisp_eval_int(&args[index], stg)?);
@@ -617,13 +1297,6 @@ impl UserFunction {
         }
         Ok(result)
     }
-    pub fn to_cons(&self) -> Cons {
-        Cons::from_iter(vec![
-            Cons::Value(ConsValue::Symbol("lambda".to_string())),
-            self.args.clone(),
-            self.body.clone(),
-        ])
-    }
 }
 
 impl Display for UserFunction {
@@ -0,0 +1,167 @@
+use std::{
+    fmt::{Display, Error, Formatter},
+    sync::{
+        mpsc::{self, Receiver, Sender},
+        Arc, Mutex, TryLockError,
+    },
+    thread::{self, ThreadId},
+};
+
+use lazy_static::lazy_static;
+
+use crate::errors::EvaluatorError;
+
+use super::{symbol::Symbol, Cons, ConsValue};
+
+lazy_static! {
+    /// An uninterned symbol, so no value read from source is `eq?` to it.
+    static ref END: Symbol = Symbol::uninterned("end-of-generator");
+}
+
+/// What a generator returns once its body has finished.
+pub fn end_of_generator() -> ConsValue {
+    ConsValue::Symbol(END.clone())
+}
+
+/// The stack each generator thread gets: the size of a main thread's stack
+/// on Linux, so a generator body can recurse as deeply as code run directly.
+const GENERATOR_STACK_SIZE: usize = 8 * 1024 * 1024;
+
+/// What the generator's thread hands back for each value asked for: the next
+/// value, None once the body has returned, or the error it failed with.
+type Step = Result<Option<Cons>, EvaluatorError>;
+
+fn stopped() -> EvaluatorError {
+    EvaluatorError::UncallableType("The generator is no longer running".to_string())
+}
+
+#[derive(Debug)]
+struct GeneratorState {
+    resume: Sender<()>,
+    steps: Receiver<Step>,
+    done: bool,
+}
+
+/// A generator made by `make-generator`. Its body runs on an OS thread of its
+/// own, with a stack of `GENERATOR_STACK_SIZE`, that waits at each yield until
+/// the next value is asked for. A suspended generator therefore keeps its
+/// thread and that stack until the last clone is dropped, which stops the
+/// thread at its next yield. Clones share the same generator.
+#[derive(Debug, Clone)]
+pub struct Generator(Arc<Mutex<GeneratorState>>);
+
+impl Generator {
+    /// Starts a generator whose body is `body`, called with the yield
+    /// procedure once the first value is asked for. Fails if the thread
+    /// cannot be created.
+    pub fn spawn<F>(body: F) -> Result<Self, EvaluatorError>
+    where
+        F: FnOnce(Yielder) -> Result<Cons, EvaluatorError> + Send + 'static,
+    {
+        let (resume_tx, resume_rx) = mpsc::channel();
+        let (steps_tx, steps_rx) = mpsc::channel();
+        thread::Builder::new()
+            .name("generator".to_string())
+            .stack_size(GENERATOR_STACK_SIZE)
+            .spawn(move || {
+                if resume_rx.recv().is_err() {
+                    return;
+                }
+                let yielder = Yielder(Arc::new(YielderState {
+                    steps: steps_tx.clone(),
+                    resume: Mutex::new(resume_rx),
+                    thread: thread::current().id(),
+                }));
+                let _ = steps_tx.send(body(yielder).map(|_| None));
+            })
+            .map_err(|e| {
+                EvaluatorError::IoError(format!("Could not start a generator thread: {}", e))
+            })?;
+        Ok(Generator(Arc::new(Mutex::new(GeneratorState {
+            resume: resume_tx,
+            steps: steps_rx,
+            done: false,
+        }))))
+    }
+
+    /// Runs the body up to its next yield and returns the value, or the end
+    /// marker once the body has returned.
+    pub fn next(&self) -> Result<Cons, EvaluatorError> {
+        let mut state = match self.0.try_lock() {
+            Ok(state) => state,
+            Err(TryLockError::WouldBlock) => {
+                return Err(EvaluatorError::UncallableType(
+                    "The generator is already running".to_string(),
+                ));
+            }
+            Err(TryLockError::Poisoned(e)) => e.into_inner(),
+        };
+        if state.done {
+            return Ok(Cons::Value(end_of_generator()));
+        }
+        let step = match state.resume.send(()) {
+            Ok(()) => state.steps.recv().unwrap_or_else(|_| Err(stopped())),
+            Err(_) => Err(stopped()),
+        };
+        match step {
+            Ok(Some(value)) => Ok(value),
+            Ok(None) => {
+                state.done = true;
+                Ok(Cons::Value(end_of_generator()))
+            }
+            Err(e) => {
+                state.done = true;
+                Err(e)
+            }
+        }
+    }
+}
+
+impl PartialEq for Generator {
+    fn eq(&self, other: &Self) -> bool {
+        Arc::ptr_eq(&self.0, &other.0)
+    }
+}
+
+impl Display for Generator {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), Error> {
+        write!(f, "#<generator>")
+    }
+}
+
+#[derive(Debug)]
+struct YielderState {
+    steps: Sender<Step>,
+    resume: Mutex<Receiver<()>>,
+    thread: ThreadId,
+}
+
+/// The yield procedure passed to a generator's body.
+#[derive(Debug, Clone)]
+pub struct Yielder(Arc<YielderState>);
+
+impl Yielder {
+    /// Hands `value` to whoever asked for the next one and waits until
+    /// another value is asked for.
+    pub fn yield_value(&self, value: Cons) -> Result<(), EvaluatorError> {
+        if thread::current().id() != self.0.thread {
+            return Err(EvaluatorError::UncallableType(
+                "yield can only be called from its own generator".to_string(),
+            ));
+        }
+        self.0.steps.send(Ok(Some(value))).map_err(|_| stopped())?;
+        self.0.resume.lock().unwrap().recv().map_err(|_| stopped())
+    }
+}
+
+impl PartialEq for Yielder {
+    fn eq(&self, other: &Self) -> bool {
+        Arc::ptr_eq(&self.0, &other.0)
+    }
+}
+
+impl Display for Yielder {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), Error> {
+        write!(f, "#<yield>")
+    }
+}
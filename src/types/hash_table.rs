@@ -0,0 +1,146 @@
+use std::{
+    fmt::{Display, Error, Formatter},
+    hash::{Hash, Hasher},
+    mem,
+    sync::{Arc, Mutex},
+};
+
+use indexmap::IndexMap;
+
+use crate::builtins::equality;
+
+use super::{Cons, ConsValue};
+
+/// A hash table key. Keys are the same when they are `equal?`, so the hash
+/// follows the structure of lists and the bit pattern of floats, matching how
+/// `eqv?` compares them.
+#[derive(Debug, Clone)]
+struct Key(Cons);
+
+impl PartialEq for Key {
+    fn eq(&self, other: &Self) -> bool {
+        equality::is_equal(&self.0, &other.0)
+    }
+}
+
+impl Eq for Key {}
+
+impl Hash for Key {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        hash_cons(&self.0, state);
+    }
+}
+
+fn hash_cons<H: Hasher>(c: &Cons, state: &mut H) {
+    match c {
+        Cons::Value(v) => {
+            mem::discriminant(v).hash(state);
+            match v {
+                ConsValue::Symbol(s) => s.hash(state),
+                ConsValue::String(s) | ConsValue::Comment(s) => s.hash(state),
+                ConsValue::Boolean(b) => b.hash(state),
+                ConsValue::Char(c) => c.hash(state),
+                ConsValue::Int(i) => i.hash(state),
+                ConsValue::BigInt(i) => i.hash(state),
+                ConsValue::Rational(r) => r.hash(state),
+                ConsValue::Float(f) => f.to_bits().hash(state),
+                ConsValue::Date(d) => d.hash(state),
+                ConsValue::Duration(d) => d.hash(state),
+                // Ports, tables, promises, continuations, generators,
+                // parameters and procedures are only equal to themselves,
+                // and vectors and bytevectors can change after being used as
+                // a key, so their kind is enough.
+                ConsValue::NIL
+                | ConsValue::Port(_)
+                | ConsValue::HashTable(_)
+                | ConsValue::Promise(_)
+                | ConsValue::Continuation(_)
+                | ConsValue::Generator(_)
+                | ConsValue::Yielder(_)
+                | ConsValue::Parameter(_)
+                | ConsValue::Procedure(_)
+                | ConsValue::Vector(_)
+                | ConsValue::Bytevector(_) => {}
+            }
+        }
+        Cons::Cell(car, cdr) => {
+            0u8.hash(state);
+            hash_cons(car, state);
+            hash_cons(cdr, state);
+        }
+        Cons::Quoted(q) => {
+            1u8.hash(state);
+            hash_cons(q, state);
+        }
+    }
+}
+
+/// A mutable hash table keyed by `equal?`. Entries keep their insertion
+/// order. Clones share the same underlying table.
+#[derive(Debug, Clone, Default)]
+pub struct HashTable(Arc<Mutex<IndexMap<Key, Cons>>>);
+
+impl HashTable {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn get(&self, key: &Cons) -> Option<Cons> {
+        self.0.lock().unwrap().get(&Key(key.clone())).cloned()
+    }
+
+    pub fn insert(&self, key: Cons, value: Cons) {
+        self.0.lock().unwrap().insert(Key(key), value);
+    }
+
+    pub fn remove(&self, key: &Cons) -> Option<Cons> {
+        self.0.lock().unwrap().shift_remove(&Key(key.clone()))
+    }
+
+    pub fn contains_key(&self, key: &Cons) -> bool {
+        self.0.lock().unwrap().contains_key(&Key(key.clone()))
+    }
+
+    pub fn len(&self) -> usize {
+        self.0.lock().unwrap().len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns a snapshot of the entries, so the table can be changed while
+    /// they are visited.
+    pub fn entries(&self) -> Vec<(Cons, Cons)> {
+        self.0
+            .lock()
+            .unwrap()
+            .iter()
+            .map(|(k, v)| (k.0.clone(), v.clone()))
+            .collect()
+    }
+}
+
+impl PartialEq for HashTable {
+    fn eq(&self, other: &Self) -> bool {
+        Arc::ptr_eq(&self.0, &other.0)
+    }
+}
+
+impl Display for HashTable {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), Error> {
+        // The table is already locked if it is being printed as part of
+        // itself.
+        let Ok(entries) = self.0.try_lock() else {
+            return write!(f, "#hash(...)");
+        };
+        write!(f, "#hash(")?;
+        for (i, (k, v)) in entries.iter().enumerate() {
+            if i > 0 {
+                write!(f, " ")?;
+            }
+            write!(f, "({} {})", k.0, v)?;
+        }
+        write!(f, ")")
+    }
+}
@@ -1,11 +1,39 @@
+pub mod bytevector;
+pub mod clock;
+pub mod continuation;
+pub mod doc;
 pub mod function;
+pub mod generator;
+pub mod hash_table;
+pub mod module;
+pub mod parameter;
+pub mod port;
+pub mod procedure;
+pub mod promise;
+pub mod random;
 pub mod scope;
+pub mod symbol;
+pub mod vector;
 
 use std::{
     fmt::{Display, Error, Formatter},
     sync::Arc,
 };
 
+use bytevector::Bytevector;
+use chrono::{DateTime, FixedOffset, TimeDelta};
+use continuation::Continuation;
+use generator::{Generator, Yielder};
+use hash_table::HashTable;
+use num_bigint::BigInt;
+use num_rational::BigRational;
+use parameter::Parameter;
+use port::Port;
+use procedure::Procedure;
+use promise::Promise;
+use symbol::Symbol;
+use vector::Vector;
+
 #[derive(Debug, PartialEq, Clone)]
 pub enum Cons {
     Value(ConsValue),
@@ -21,16 +49,10 @@ impl Cons {
         }
     }
     pub fn is_nil(&self) -> bool {
-        match self {
-            Cons::Value(ConsValue::NIL) => true,
-            _ => false,
-        }
+        matches!(self, Cons::Value(ConsValue::NIL))
     }
     pub fn is_quoted(&self) -> bool {
-        match self {
-            Cons::Quoted(_) => true,
-            _ => false,
-        }
+        matches!(self, Cons::Quoted(_))
     }
     pub fn car(&self) -> Cons {
         match self {
@@ -44,6 +66,24 @@ impl Cons {
             _ => Cons::Value(ConsValue::NIL),
         }
     }
+    /// Renders the value the way `print` and `~a` show it: like `Display`,
+    /// but with strings unquoted and characters written as themselves.
+    pub fn to_display_string(&self) -> String {
+        match self {
+            Cons::Value(ConsValue::String(s)) => s.clone(),
+            Cons::Value(ConsValue::Char(c)) => c.to_string(),
+            Cons::Value(v) => v.to_string(),
+            Cons::Cell(..) => {
+                let elems: Vec<String> = self
+                    .clone()
+                    .into_iter()
+                    .map(|c| c.to_display_string())
+                    .collect();
+                format!("({})", elems.join(" "))
+            }
+            Cons::Quoted(q) => format!("'{}", q.to_display_string()),
+        }
+    }
     pub fn split(&self) -> Option<(Cons, Cons)> {
         match self {
             Cons::Cell(car, cdr) => Some((car.as_ref().clone(), cdr.as_ref().clone())),
@@ -106,22 +146,76 @@ impl Display for Cons {
     }
 }
 
+/// The characters with a name in `#\name` syntax.
+const CHAR_NAMES: [(&str, char); 9] = [
+    ("space", ' '),
+    ("newline", '\n'),
+    ("tab", '\t'),
+    ("return", '\r'),
+    ("null", '\0'),
+    ("alarm", '\u{7}'),
+    ("backspace", '\u{8}'),
+    ("escape", '\u{1b}'),
+    ("delete", '\u{7f}'),
+];
+
+/// Looks up the character for a name like `space` in `#\space`.
+pub fn char_from_name(name: &str) -> Option<char> {
+    CHAR_NAMES.iter().find(|(n, _)| *n == name).map(|(_, c)| *c)
+}
+
 #[derive(Debug, PartialEq, Clone)]
 pub enum ConsValue {
     NIL,
-    Symbol(String),
+    Symbol(Symbol),
     String(String),
     Boolean(bool),
+    Char(char),
     Int(i64),
+    /// An integer outside the range of `Int`. Arithmetic only produces it for
+    /// such values, so equal numbers always have the same representation.
+    BigInt(Box<BigInt>),
+    /// An exact fraction in lowest terms whose denominator is not 1.
+    Rational(Box<BigRational>),
     Float(f64),
     Comment(String),
+    Port(Port),
+    HashTable(HashTable),
+    Vector(Vector),
+    Bytevector(Bytevector),
+    Promise(Promise),
+    Continuation(Continuation),
+    Generator(Generator),
+    Yielder(Yielder),
+    Parameter(Parameter),
+    Procedure(Procedure),
+    /// A moment in time with the UTC offset it is shown in. Dates are equal
+    /// when they are the same moment, whatever their offsets.
+    Date(DateTime<FixedOffset>),
+    Duration(TimeDelta),
 }
 
 impl ConsValue {
     pub fn is_nil(&self) -> bool {
-        match self {
-            ConsValue::NIL => true,
-            _ => false,
+        matches!(self, ConsValue::NIL)
+    }
+    /// Returns the interned symbol called `name`.
+    pub fn symbol(name: &str) -> ConsValue {
+        ConsValue::Symbol(Symbol::new(name))
+    }
+    /// Returns an `Int` if `n` fits in one, otherwise a `BigInt`.
+    pub fn integer(n: BigInt) -> ConsValue {
+        match i64::try_from(&n) {
+            Ok(i) => ConsValue::Int(i),
+            Err(_) => ConsValue::BigInt(Box::new(n)),
+        }
+    }
+    /// Returns an integer if `r` is whole, otherwise a `Rational`.
+    pub fn rational(r: BigRational) -> ConsValue {
+        if r.is_integer() {
+            ConsValue::integer(r.to_integer())
+        } else {
+            ConsValue::Rational(Box::new(r))
         }
     }
 }
@@ -133,9 +227,32 @@ impl Display for ConsValue {
             ConsValue::Symbol(s) => write!(f, "{}", s),
             ConsValue::String(s) => write!(f, "\"{}\"", s),
             ConsValue::Boolean(b) => write!(f, "{}", b),
+            ConsValue::Char(c) => match CHAR_NAMES.iter().find(|(_, named)| named == c) {
+                Some((name, _)) => write!(f, "#\\{}", name),
+                None if c.is_control() => write!(f, "#\\x{:x}", *c as u32),
+                None => write!(f, "#\\{}", c),
+            },
             ConsValue::Int(i) => write!(f, "{}", i),
+            ConsValue::BigInt(i) => write!(f, "{}", i),
+            ConsValue::Rational(r) => write!(f, "{}", r),
+            // Whole floats keep a ".0" so they read back as floats.
+            ConsValue::Float(fl) if fl.is_finite() && fl.fract() == 0.0 => {
+                write!(f, "{:.1}", fl)
+            }
             ConsValue::Float(fl) => write!(f, "{}", fl),
-            ConsValue::Comment(s) => write!(f, "; {}\n", s),
+            ConsValue::Comment(s) => writeln!(f, "; {}", s),
+            ConsValue::Port(p) => write!(f, "{}", p),
+            ConsValue::HashTable(t) => write!(f, "{}", t),
+            ConsValue::Vector(v) => write!(f, "{}", v),
+            ConsValue::Bytevector(b) => write!(f, "{}", b),
+            ConsValue::Promise(p) => write!(f, "{}", p),
+            ConsValue::Continuation(k) => write!(f, "{}", k),
+            ConsValue::Generator(g) => write!(f, "{}", g),
+            ConsValue::Yielder(y) => write!(f, "{}", y),
+            ConsValue::Parameter(p) => write!(f, "{}", p),
+            ConsValue::Procedure(p) => write!(f, "{}", p),
+            ConsValue::Date(d) => write!(f, "#<date {}>", d.to_rfc3339()),
+            ConsValue::Duration(d) => write!(f, "#<duration {}>", d),
         }
     }
 }
@@ -0,0 +1,120 @@
+use std::{
+    collections::{HashMap, HashSet},
+    env,
+    path::PathBuf,
+    sync::Arc,
+};
+
+use crate::errors::EvaluatorError;
+
+use super::{function::UserFunction, scope::LexicalVarStorage, symbol::Symbol, Cons};
+
+/// The exported bindings of a `(module name (export ...) body...)` form.
+#[derive(Debug, Clone)]
+pub struct Module {
+    name: String,
+    values: HashMap<String, Cons>,
+    funcs: HashMap<String, UserFunction>,
+}
+
+impl Module {
+    /// Collects the exported names out of the environment a module body was
+    /// evaluated in. Exported functions keep that environment so they can
+    /// still reach the module's private definitions once imported.
+    pub fn from_storage(
+        name: &str,
+        exports: &[String],
+        stg: &LexicalVarStorage,
+    ) -> Result<Self, EvaluatorError> {
+        let scope = Arc::new(stg.clone());
+        let mut values = HashMap::new();
+        let mut funcs = HashMap::new();
+        for export in exports {
+            let symbol = Symbol::new(export);
+            if let Some(f) = stg.get_func(&symbol) {
+                funcs.insert(export.clone(), f.with_module_scope(scope.clone()));
+            } else if let Some(v) = stg.get(&symbol) {
+                values.insert(export.clone(), v.clone());
+            } else {
+                return Err(EvaluatorError::ModuleError(format!(
+                    "Module {} exports undefined symbol {}",
+                    name, export
+                )));
+            }
+        }
+        Ok(Module {
+            name: name.to_string(),
+            values,
+            funcs,
+        })
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Binds every exported name into `stg`, each prefixed with `prefix`.
+    pub fn import_into(&self, stg: &mut LexicalVarStorage, prefix: &str) {
+        for (k, v) in &self.values {
+            stg.put(&Symbol::new(&format!("{}{}", prefix, k)), v.clone());
+        }
+        for (k, f) in &self.funcs {
+            stg.put_func(&Symbol::new(&format!("{}{}", prefix, k)), f.clone());
+        }
+    }
+}
+
+/// Interpreter-wide table of defined modules, shared by every fork of a
+/// `LexicalVarStorage`, along with the directories searched by `load` and
+/// `import`.
+#[derive(Debug)]
+pub struct ModuleRegistry {
+    modules: HashMap<String, Module>,
+    loading: HashSet<String>,
+    load_paths: Vec<PathBuf>,
+}
+
+impl ModuleRegistry {
+    pub fn new() -> Self {
+        let mut load_paths = vec![PathBuf::from(".")];
+        if let Some(paths) = env::var_os("BOXR_PATH") {
+            load_paths.extend(env::split_paths(&paths));
+        }
+        ModuleRegistry {
+            modules: HashMap::new(),
+            loading: HashSet::new(),
+            load_paths,
+        }
+    }
+
+    pub fn get(&self, name: &str) -> Option<&Module> {
+        self.modules.get(name)
+    }
+    pub fn register(&mut self, module: Module) {
+        self.modules.insert(module.name.clone(), module);
+    }
+
+    /// Marks `name` as being loaded, returning false if it already was (an
+    /// import cycle).
+    pub fn begin_loading(&mut self, name: &str) -> bool {
+        self.loading.insert(name.to_string())
+    }
+    pub fn finish_loading(&mut self, name: &str) {
+        self.loading.remove(name);
+    }
+
+    pub fn load_paths(&self) -> &[PathBuf] {
+        &self.load_paths
+    }
+    pub fn add_load_path(&mut self, path: PathBuf) {
+        if !self.load_paths.contains(&path) {
+            self.load_paths.push(path);
+        }
+    }
+}
+
+impl Default for ModuleRegistry {
+    fn default() -> Self {
+        Self::new()
+    }
+}
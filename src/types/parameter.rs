@@ -0,0 +1,52 @@
+use std::{
+    fmt::{Display, Error, Formatter},
+    sync::Arc,
+};
+
+use crate::evaluator::EvalReturnType;
+
+use super::Cons;
+
+#[derive(Debug)]
+struct ParameterState {
+    value: Cons,
+    converter: Option<EvalReturnType>,
+}
+
+/// A parameter made by `make-parameter`: a procedure returning its current
+/// value. `parameterize` binds it in the storage its body is evaluated in,
+/// like `current-output-port`, so the binding is seen by everything the body
+/// calls and by nothing else; see `LexicalVarStorage::parameter`. Clones are
+/// the same parameter.
+#[derive(Debug, Clone)]
+pub struct Parameter(Arc<ParameterState>);
+
+impl Parameter {
+    /// A parameter whose value is `value`, already converted. `converter` is
+    /// applied to the values given to `parameterize`.
+    pub(crate) fn new(value: Cons, converter: Option<EvalReturnType>) -> Self {
+        Parameter(Arc::new(ParameterState { value, converter }))
+    }
+
+    /// The value the parameter was made with, which it has wherever
+    /// `parameterize` has not bound it.
+    pub fn default_value(&self) -> Cons {
+        self.0.value.clone()
+    }
+
+    pub(crate) fn converter(&self) -> Option<EvalReturnType> {
+        self.0.converter.clone()
+    }
+}
+
+impl PartialEq for Parameter {
+    fn eq(&self, other: &Self) -> bool {
+        Arc::ptr_eq(&self.0, &other.0)
+    }
+}
+
+impl Display for Parameter {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), Error> {
+        write!(f, "#<parameter>")
+    }
+}
@@ -0,0 +1,67 @@
+use std::{
+    fmt::{Display, Error, Formatter},
+    io::Write,
+    sync::{Arc, Mutex},
+};
+
+use crate::errors::EvaluatorError;
+
+#[derive(Debug)]
+enum PortKind {
+    Stdout,
+    String(String),
+}
+
+/// An output port: somewhere `print` and `format` can write text to. Clones
+/// share the same underlying port.
+#[derive(Debug, Clone)]
+pub struct Port(Arc<Mutex<PortKind>>);
+
+impl Port {
+    pub fn stdout() -> Self {
+        Port(Arc::new(Mutex::new(PortKind::Stdout)))
+    }
+    /// A port that accumulates everything written to it in memory.
+    pub fn string() -> Self {
+        Port(Arc::new(Mutex::new(PortKind::String(String::new()))))
+    }
+
+    pub fn write_str(&self, s: &str) -> Result<(), EvaluatorError> {
+        match &mut *self.0.lock().unwrap() {
+            PortKind::Stdout => {
+                let mut stdout = std::io::stdout();
+                stdout
+                    .write_all(s.as_bytes())
+                    .and_then(|_| stdout.flush())
+                    .map_err(|e| EvaluatorError::InvalidArgument(format!("Write failed: {}", e)))
+            }
+            PortKind::String(buffer) => {
+                buffer.push_str(s);
+                Ok(())
+            }
+        }
+    }
+
+    /// Returns what has been written so far to a string port.
+    pub fn contents(&self) -> Option<String> {
+        match &*self.0.lock().unwrap() {
+            PortKind::Stdout => None,
+            PortKind::String(buffer) => Some(buffer.clone()),
+        }
+    }
+}
+
+impl PartialEq for Port {
+    fn eq(&self, other: &Self) -> bool {
+        Arc::ptr_eq(&self.0, &other.0)
+    }
+}
+
+impl Display for Port {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), Error> {
+        match &*self.0.lock().unwrap() {
+            PortKind::Stdout => write!(f, "#<output-port stdout>"),
+            PortKind::String(_) => write!(f, "#<output-port string>"),
+        }
+    }
+}
@@ -0,0 +1,45 @@
+use std::{
+    fmt::{Display, Error, Formatter},
+    sync::Arc,
+};
+
+use crate::evaluator::EvalReturnType;
+
+use super::function::{BuiltinFunction, UserFunction};
+
+/// A builtin or user function used as a value, such as the procedure passed
+/// to `apply` or `vector-map`. Calling it is the same as calling the function
+/// by name. Clones are the same procedure.
+#[derive(Debug, Clone)]
+pub enum Procedure {
+    Builtin(BuiltinFunction),
+    User(Arc<UserFunction>),
+}
+
+impl From<Procedure> for EvalReturnType {
+    fn from(proc: Procedure) -> Self {
+        match proc {
+            Procedure::Builtin(f) => EvalReturnType::FUNC(f),
+            Procedure::User(f) => EvalReturnType::USER(f),
+        }
+    }
+}
+
+impl PartialEq for Procedure {
+    fn eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (Procedure::Builtin(a), Procedure::Builtin(b)) => a == b,
+            (Procedure::User(a), Procedure::User(b)) => Arc::ptr_eq(a, b),
+            _ => false,
+        }
+    }
+}
+
+impl Display for Procedure {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), Error> {
+        match self {
+            Procedure::Builtin(b) => write!(f, "#<procedure {}>", b.name()),
+            Procedure::User(_) => write!(f, "#<procedure>"),
+        }
+    }
+}
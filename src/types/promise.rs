@@ -0,0 +1,101 @@
+use std::{
+    fmt::{Display, Error, Formatter},
+    sync::{Arc, Mutex},
+};
+
+use super::{scope::LexicalVarStorage, Cons};
+
+#[derive(Debug)]
+#[allow(clippy::large_enum_variant)]
+enum PromiseState {
+    /// Not forced yet: `expr` is evaluated in `env` on the first `force`. For
+    /// `delay-force`, `expr` gives another promise to force in its place.
+    Delayed {
+        expr: Cons,
+        env: LexicalVarStorage,
+        chained: bool,
+    },
+    Forced(Cons),
+}
+
+/// A delayed computation whose value is remembered once it has been forced.
+/// Clones share the same underlying promise.
+#[derive(Debug, Clone)]
+pub struct Promise(Arc<Mutex<PromiseState>>);
+
+/// The expression a promise still has to evaluate, as returned by
+/// `Promise::pending`.
+pub struct Pending {
+    pub expr: Cons,
+    pub env: LexicalVarStorage,
+    pub chained: bool,
+}
+
+impl Promise {
+    /// A promise to evaluate `expr` in `env`, as made by `delay`.
+    pub fn delayed(expr: Cons, env: LexicalVarStorage) -> Self {
+        Promise(Arc::new(Mutex::new(PromiseState::Delayed {
+            expr,
+            env,
+            chained: false,
+        })))
+    }
+    /// A promise to force the promise `expr` evaluates to, as made by
+    /// `delay-force`.
+    pub fn delay_force(expr: Cons, env: LexicalVarStorage) -> Self {
+        Promise(Arc::new(Mutex::new(PromiseState::Delayed {
+            expr,
+            env,
+            chained: true,
+        })))
+    }
+    /// A promise that is already forced to `value`.
+    pub fn forced(value: Cons) -> Self {
+        Promise(Arc::new(Mutex::new(PromiseState::Forced(value))))
+    }
+
+    /// Returns the value if the promise has been forced.
+    pub fn value(&self) -> Option<Cons> {
+        match &*self.0.lock().unwrap() {
+            PromiseState::Forced(value) => Some(value.clone()),
+            PromiseState::Delayed { .. } => None,
+        }
+    }
+
+    /// Returns what is left to evaluate if the promise has not been forced.
+    pub fn pending(&self) -> Option<Pending> {
+        match &*self.0.lock().unwrap() {
+            PromiseState::Forced(_) => None,
+            PromiseState::Delayed { expr, env, chained } => Some(Pending {
+                expr: expr.clone(),
+                env: env.clone(),
+                chained: *chained,
+            }),
+        }
+    }
+
+    /// Remembers `value` unless the promise was forced in the meantime, and
+    /// returns the value the promise ends up with.
+    pub fn resolve(&self, value: Cons) -> Cons {
+        let mut state = self.0.lock().unwrap();
+        match &*state {
+            PromiseState::Forced(existing) => existing.clone(),
+            PromiseState::Delayed { .. } => {
+                *state = PromiseState::Forced(value.clone());
+                value
+            }
+        }
+    }
+}
+
+impl PartialEq for Promise {
+    fn eq(&self, other: &Self) -> bool {
+        Arc::ptr_eq(&self.0, &other.0)
+    }
+}
+
+impl Display for Promise {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), Error> {
+        write!(f, "#<promise>")
+    }
+}
@@ -0,0 +1,91 @@
+use std::{
+    sync::{Arc, Mutex},
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+/// The state of a xoshiro256** generator. It is implemented here rather than
+/// taken from a crate so that a seed gives the same sequence on every
+/// platform and in every version.
+#[derive(Debug)]
+struct Xoshiro256([u64; 4]);
+
+impl Xoshiro256 {
+    /// Expands `seed` into a full state with SplitMix64, as recommended by the
+    /// xoshiro authors.
+    fn from_seed(seed: u64) -> Self {
+        let mut x = seed;
+        let mut next = || {
+            x = x.wrapping_add(0x9e3779b97f4a7c15);
+            let mut z = x;
+            z = (z ^ (z >> 30)).wrapping_mul(0xbf58476d1ce4e5b9);
+            z = (z ^ (z >> 27)).wrapping_mul(0x94d049bb133111eb);
+            z ^ (z >> 31)
+        };
+        Xoshiro256([next(), next(), next(), next()])
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        let s = &mut self.0;
+        let result = s[1].wrapping_mul(5).rotate_left(7).wrapping_mul(9);
+        let t = s[1] << 17;
+        s[2] ^= s[0];
+        s[3] ^= s[1];
+        s[1] ^= s[2];
+        s[0] ^= s[3];
+        s[2] ^= t;
+        s[3] = s[3].rotate_left(45);
+        result
+    }
+}
+
+/// The random number generator of one interpreter. Unless seeded it starts
+/// from the clock. Clones share the same generator.
+#[derive(Debug, Clone)]
+pub struct Random(Arc<Mutex<Xoshiro256>>);
+
+impl Random {
+    pub fn new() -> Self {
+        let nanos = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_nanos() as u64)
+            .unwrap_or_default();
+        Self::seeded(nanos)
+    }
+
+    pub fn seeded(seed: u64) -> Self {
+        Random(Arc::new(Mutex::new(Xoshiro256::from_seed(seed))))
+    }
+
+    /// Restarts the sequence from `seed`.
+    pub fn seed(&self, seed: u64) {
+        *self.0.lock().unwrap() = Xoshiro256::from_seed(seed);
+    }
+
+    pub fn next_u64(&self) -> u64 {
+        self.0.lock().unwrap().next_u64()
+    }
+
+    /// A uniformly distributed integer in `0..bound`, which must not be 0.
+    pub fn below(&self, bound: u64) -> u64 {
+        // Rejects the top few values that would make some results more likely
+        // than others.
+        let zone = u64::MAX - u64::MAX % bound;
+        loop {
+            let n = self.next_u64();
+            if n < zone {
+                return n % bound;
+            }
+        }
+    }
+
+    /// A uniformly distributed float in `[0, 1)`.
+    pub fn real(&self) -> f64 {
+        (self.next_u64() >> 11) as f64 / (1u64 << 53) as f64
+    }
+}
+
+impl Default for Random {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -1,13 +1,27 @@
-use std::{collections::HashMap, ops::Index};
+use std::{
+    collections::HashMap,
+    ops::Index,
+    path::PathBuf,
+    sync::{Arc, Mutex},
+};
 
-use super::{function::UserFunction, Cons, ConsValue};
+use super::{
+    clock::Clock, function::UserFunction, module::ModuleRegistry, parameter::Parameter, port::Port,
+    random::Random, symbol::Symbol, Cons, ConsValue,
+};
 
 #[derive(Debug, Clone)]
 pub struct LexicalVarStorage {
-    environ: HashMap<String, Cons>,
-    local: HashMap<String, Cons>,
-    environ_func: HashMap<String, UserFunction>,
-    local_func: HashMap<String, UserFunction>,
+    environ: HashMap<Symbol, Cons>,
+    local: HashMap<Symbol, Cons>,
+    environ_func: HashMap<Symbol, UserFunction>,
+    local_func: HashMap<Symbol, UserFunction>,
+    modules: Arc<Mutex<ModuleRegistry>>,
+    output: Port,
+    random: Random,
+    clock: Clock,
+    file_system: bool,
+    parameters: Vec<(Parameter, Cons)>,
 }
 
 impl LexicalVarStorage {
@@ -17,23 +31,111 @@ impl LexicalVarStorage {
             local: HashMap::new(),
             environ_func: HashMap::new(),
             local_func: HashMap::new(),
+            modules: Arc::new(Mutex::new(ModuleRegistry::new())),
+            output: Port::stdout(),
+            random: Random::new(),
+            clock: Clock::system(),
+            file_system: true,
+            parameters: Vec::new(),
         }
     }
 
-    pub fn get(&self, name: &str) -> Option<&Cons> {
+    /// Creates an empty storage that still shares the module registry, output
+    /// port, parameter bindings, random number generator and clock with
+    /// `self`, used to give each module body its own environment.
+    pub fn detached(&self) -> LexicalVarStorage {
+        LexicalVarStorage {
+            environ: HashMap::new(),
+            local: HashMap::new(),
+            environ_func: HashMap::new(),
+            local_func: HashMap::new(),
+            modules: self.modules.clone(),
+            output: self.output.clone(),
+            random: self.random.clone(),
+            clock: self.clock.clone(),
+            file_system: self.file_system,
+            parameters: self.parameters.clone(),
+        }
+    }
+
+    pub fn modules(&self) -> &Arc<Mutex<ModuleRegistry>> {
+        &self.modules
+    }
+    pub fn add_load_path(&self, path: PathBuf) {
+        self.modules.lock().unwrap().add_load_path(path);
+    }
+
+    /// The port `print` and `(format #t ...)` write to; stdout by default.
+    pub fn output(&self) -> &Port {
+        &self.output
+    }
+    pub fn set_output(&mut self, port: Port) {
+        self.output = port;
+    }
+
+    /// The value of `param` here: its innermost `parameterize` binding, or the
+    /// value it was made with.
+    pub fn parameter(&self, param: &Parameter) -> Cons {
+        self.parameters
+            .iter()
+            .rev()
+            .find(|(p, _)| p == param)
+            .map_or_else(|| param.default_value(), |(_, value)| value.clone())
+    }
+    pub fn bind_parameter(&mut self, param: Parameter, value: Cons) {
+        self.parameters.push((param, value));
+    }
+
+    /// The generator behind `random-integer` and friends, shared by the whole
+    /// interpreter.
+    pub fn random(&self) -> &Random {
+        &self.random
+    }
+
+    /// The clock behind `current-time`, `current-date` and `sleep`. Tests
+    /// replace it with `Clock::frozen` to get the same times on every run.
+    pub fn clock(&self) -> &Clock {
+        &self.clock
+    }
+    pub fn set_clock(&mut self, clock: Clock) {
+        self.clock = clock;
+    }
+
+    /// Whether scripts may `load` files, `import` modules from files and use
+    /// `read-file` and the other file system builtins. On by default;
+    /// embedders running untrusted code turn it off before evaluating
+    /// anything, since storages made from this one copy the setting.
+    pub fn file_system_enabled(&self) -> bool {
+        self.file_system
+    }
+    pub fn set_file_system_enabled(&mut self, enabled: bool) {
+        self.file_system = enabled;
+    }
+
+    pub fn get(&self, name: &Symbol) -> Option<&Cons> {
         self.local.get(name).or_else(|| self.environ.get(name))
     }
-    pub fn put(&mut self, name: &str, value: Cons) {
-        self.local.insert(name.to_string(), value);
+    pub fn put(&mut self, name: &Symbol, value: Cons) {
+        self.local.insert(name.clone(), value);
     }
 
-    pub fn get_func(&self, name: &str) -> Option<&UserFunction> {
+    pub fn get_func(&self, name: &Symbol) -> Option<&UserFunction> {
         self.local_func
             .get(name)
             .or_else(|| self.environ_func.get(name))
     }
-    pub fn put_func(&mut self, name: &str, value: UserFunction) {
-        self.local_func.insert(name.to_string(), value);
+    pub fn put_func(&mut self, name: &Symbol, value: UserFunction) {
+        self.local_func.insert(name.clone(), value);
+    }
+
+    /// Returns every variable and function name bound in this storage.
+    pub fn names(&self) -> impl Iterator<Item = String> + '_ {
+        self.environ
+            .keys()
+            .chain(self.local.keys())
+            .chain(self.environ_func.keys())
+            .chain(self.local_func.keys())
+            .map(Symbol::to_string)
     }
 
     pub fn fork(&self) -> LexicalVarStorage {
@@ -58,14 +160,26 @@ impl LexicalVarStorage {
             local: HashMap::new(),
             environ_func: environ_func_merge,
             local_func: HashMap::new(),
+            modules: self.modules.clone(),
+            output: self.output.clone(),
+            random: self.random.clone(),
+            clock: self.clock.clone(),
+            file_system: self.file_system,
+            parameters: self.parameters.clone(),
         }
     }
 }
 
-impl Index<&str> for LexicalVarStorage {
+impl Default for LexicalVarStorage {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Index<&Symbol> for LexicalVarStorage {
     type Output = Cons;
 
-    fn index(&self, name: &str) -> &Cons {
+    fn index(&self, name: &Symbol) -> &Cons {
         self.get(name).unwrap_or(&Cons::Value(ConsValue::NIL))
     }
 }
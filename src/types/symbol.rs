@@ -0,0 +1,83 @@
+use std::{
+    collections::HashSet,
+    fmt::{Display, Error, Formatter},
+    hash::{Hash, Hasher},
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc, Mutex,
+    },
+};
+
+use lazy_static::lazy_static;
+
+lazy_static! {
+    static ref INTERNED: Mutex<HashSet<Arc<str>>> = Mutex::new(HashSet::new());
+}
+
+static GENSYM_COUNTER: AtomicUsize = AtomicUsize::new(0);
+
+/// A symbol name. Interned symbols with the same name share their storage, so
+/// comparing and hashing symbols never looks at the name. Uninterned symbols
+/// are only equal to themselves, even if another symbol has the same name.
+#[derive(Debug, Clone)]
+pub struct Symbol(Arc<str>);
+
+impl Symbol {
+    /// Returns the interned symbol called `name`.
+    pub fn new(name: &str) -> Self {
+        let mut interned = INTERNED.lock().unwrap();
+        match interned.get(name) {
+            Some(name) => Symbol(name.clone()),
+            None => {
+                let name: Arc<str> = Arc::from(name);
+                interned.insert(name.clone());
+                Symbol(name)
+            }
+        }
+    }
+
+    /// Returns a fresh symbol called `name` that is distinct from every other
+    /// symbol.
+    pub fn uninterned(name: &str) -> Self {
+        Symbol(Arc::from(name))
+    }
+
+    /// Returns a fresh uninterned symbol with a generated name starting with
+    /// `prefix`.
+    pub fn gensym(prefix: &str) -> Self {
+        let n = GENSYM_COUNTER.fetch_add(1, Ordering::Relaxed);
+        Symbol::uninterned(&format!("{}{}", prefix, n))
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+
+    pub fn is_interned(&self) -> bool {
+        INTERNED
+            .lock()
+            .unwrap()
+            .get(self.as_str())
+            .is_some_and(|name| Arc::ptr_eq(name, &self.0))
+    }
+}
+
+impl PartialEq for Symbol {
+    fn eq(&self, other: &Self) -> bool {
+        Arc::ptr_eq(&self.0, &other.0)
+    }
+}
+
+impl Eq for Symbol {}
+
+impl Hash for Symbol {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        (Arc::as_ptr(&self.0) as *const u8 as usize).hash(state);
+    }
+}
+
+impl Display for Symbol {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), Error> {
+        write!(f, "{}", self.0)
+    }
+}
@@ -0,0 +1,77 @@
+use std::{
+    fmt::{Display, Error, Formatter},
+    sync::{Arc, Mutex},
+};
+
+use super::Cons;
+
+/// A mutable, fixed-length sequence with constant-time indexing. Clones share
+/// the same underlying storage.
+#[derive(Debug, Clone)]
+pub struct Vector(Arc<Mutex<Vec<Cons>>>);
+
+impl Vector {
+    pub fn new(elems: Vec<Cons>) -> Self {
+        Vector(Arc::new(Mutex::new(elems)))
+    }
+
+    pub fn len(&self) -> usize {
+        self.0.lock().unwrap().len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    pub fn get(&self, index: usize) -> Option<Cons> {
+        self.0.lock().unwrap().get(index).cloned()
+    }
+
+    /// Replaces the element at `index`, returning false if it is out of range.
+    pub fn set(&self, index: usize, value: Cons) -> bool {
+        match self.0.lock().unwrap().get_mut(index) {
+            Some(elem) => {
+                *elem = value;
+                true
+            }
+            None => false,
+        }
+    }
+
+    pub fn fill(&self, value: &Cons) {
+        self.0.lock().unwrap().fill(value.clone());
+    }
+
+    /// Returns a snapshot of the elements.
+    pub fn to_vec(&self) -> Vec<Cons> {
+        self.0.lock().unwrap().clone()
+    }
+
+    pub fn ptr_eq(&self, other: &Vector) -> bool {
+        Arc::ptr_eq(&self.0, &other.0)
+    }
+}
+
+impl PartialEq for Vector {
+    fn eq(&self, other: &Self) -> bool {
+        self.ptr_eq(other)
+    }
+}
+
+impl Display for Vector {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), Error> {
+        // The vector is already locked if it is being printed as part of
+        // itself.
+        let Ok(elems) = self.0.try_lock() else {
+            return write!(f, "#(...)");
+        };
+        write!(f, "#(")?;
+        for (i, elem) in elems.iter().enumerate() {
+            if i > 0 {
+                write!(f, " ")?;
+            }
+            write!(f, "{}", elem)?;
+        }
+        write!(f, ")")
+    }
+}